@@ -0,0 +1,292 @@
+//! Adaptive power ceilings: the CPU, GPU and ANE power peaks observed across
+//! sessions, per chip brand, raising the SoC ceilings they exceed.
+//!
+//! For a chip missing from the ceilings table (or mis-tabulated in it), the
+//! gauges would otherwise clip at a wrong scale forever. Instead, each sample's
+//! power is compared with the ceilings, and any ceiling not pinned by a
+//! `--max-*-w` flag rises to the new peak; the peaks are saved on exit and
+//! raise the ceilings again from the start of the next session.
+//!
+//! The peaks live in a JSON state file keyed by `cpu_brand_name`, at
+//! `$XDG_STATE_HOME/pumas/ceilings.json`, else
+//! `~/.local/state/pumas/ceilings.json`. `--fixed-ceilings` turns learning
+//! off, and `pumas info --reset-ceilings` forgets the peaks of this chip.
+//!
+//! Under `sudo`, the state file and the directories created for it go to the
+//! invoking user, as every file pumas writes does (see [`ownership`]).
+//!
+//! A reading above [`SANITY_FACTOR`] times the ceiling it would raise is taken
+//! for a glitch and ignored, so a single bogus `powermetrics` spike cannot
+//! rescale the gauges for good.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, error::Error, metrics::PowerConsumption, modules::soc::SocInfo, ownership};
+
+/// Readings above this multiple of the resolved ceiling are ignored.
+pub(crate) const SANITY_FACTOR: f64 = 4.0;
+
+/// Highest power observed per domain, in W.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Peaks {
+    cpu_w: f64,
+    gpu_w: f64,
+    ane_w: f64,
+}
+
+impl Peaks {
+    fn watts(self) -> (f64, f64, f64) {
+        (self.cpu_w, self.gpu_w, self.ane_w)
+    }
+
+    /// These peaks with the readings above [`SANITY_FACTOR`] times `base`
+    /// dropped.
+    fn bounded(self, base: Peaks) -> Peaks {
+        let bound = |w: f64, base: f64| if w <= base * SANITY_FACTOR { w } else { 0.0 };
+        Peaks {
+            cpu_w: bound(self.cpu_w, base.cpu_w),
+            gpu_w: bound(self.gpu_w, base.gpu_w),
+            ane_w: bound(self.ane_w, base.ane_w),
+        }
+    }
+
+    /// The highest of both, per domain.
+    fn max(self, other: Peaks) -> Peaks {
+        Peaks {
+            cpu_w: self.cpu_w.max(other.cpu_w),
+            gpu_w: self.gpu_w.max(other.gpu_w),
+            ane_w: self.ane_w.max(other.ane_w),
+        }
+    }
+}
+
+/// Learned peaks of one session, and where they are kept.
+pub(crate) struct Calibration {
+    /// The state file, or `None` when learning is off.
+    path: Option<PathBuf>,
+    /// Peaks of every chip in the state file, as loaded.
+    chips: BTreeMap<String, Peaks>,
+    /// This chip's peaks, earlier sessions included.
+    peaks: Peaks,
+    /// The ceilings as resolved before learning, for the sanity bound.
+    base: Peaks,
+}
+
+/// `$XDG_STATE_HOME/pumas/ceilings.json`, falling back to
+/// `$HOME/.local/state/pumas/ceilings.json`.
+pub(crate) fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state"))
+        })?;
+    Some(base.join("pumas").join("ceilings.json"))
+}
+
+fn ceilings_error(path: &Path, err: impl std::fmt::Display) -> Error {
+    Error::CeilingsFile(format!("{}: {err}", path.display()))
+}
+
+impl Calibration {
+    /// No learning: the ceilings stay as resolved.
+    pub(crate) fn off() -> Self {
+        Self {
+            path: None,
+            chips: BTreeMap::new(),
+            peaks: Peaks::default(),
+            base: Peaks::default(),
+        }
+    }
+
+    /// Load the peaks saved at `path` (none if it is missing), and raise the
+    /// ceilings of `soc` to those learned for its brand. Saved peaks beyond
+    /// the sanity bound are dropped.
+    pub(crate) fn load(path: PathBuf, soc: &mut SocInfo) -> Result<Self> {
+        let chips: BTreeMap<String, Peaks> = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| ceilings_error(&path, e))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(ceilings_error(&path, e)),
+        };
+        let base = Peaks {
+            cpu_w: soc.max_cpu_w,
+            gpu_w: soc.max_gpu_w,
+            ane_w: soc.max_ane_w,
+        };
+        let peaks = chips
+            .get(&soc.cpu_brand_name)
+            .copied()
+            .unwrap_or_default()
+            .bounded(base);
+        soc.raise_ceilings(peaks.watts());
+        Ok(Self {
+            path: Some(path),
+            chips,
+            peaks,
+            base,
+        })
+    }
+
+    /// Record a sample's power, raising the ceilings of `soc` it exceeds,
+    /// unless beyond the sanity bound. Returns whether any ceiling rose.
+    pub(crate) fn observe(&mut self, soc: &mut SocInfo, power: &PowerConsumption) -> bool {
+        if self.path.is_none() {
+            return false;
+        }
+        let sample = Peaks {
+            cpu_w: f64::from(power.cpu_w),
+            gpu_w: f64::from(power.gpu_w),
+            ane_w: f64::from(power.ane_w),
+        };
+        self.peaks = self.peaks.max(sample.bounded(self.base));
+        soc.raise_ceilings(self.peaks.watts())
+    }
+
+    /// Forget the peaks learned for `soc`'s brand, in the state file too, and
+    /// lower its ceilings back to the ones resolved before learning. Returns
+    /// whether there were any.
+    pub(crate) fn reset(&mut self, soc: &mut SocInfo) -> Result<bool> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        self.peaks = Peaks::default();
+        soc.restore_ceilings(self.base.watts());
+        if self.chips.remove(&soc.cpu_brand_name).is_none() {
+            return Ok(false);
+        }
+        write(path, &self.chips)?;
+        Ok(true)
+    }
+
+    /// Save the peaks of `soc`'s brand, next to the other chips' ones, if any
+    /// peak is new.
+    pub(crate) fn save(&self, soc: &SocInfo) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if self.chips.get(&soc.cpu_brand_name) == Some(&self.peaks) {
+            return Ok(());
+        }
+        let mut chips = self.chips.clone();
+        chips.insert(soc.cpu_brand_name.clone(), self.peaks);
+        write(path, &chips)
+    }
+
+    /// [`Calibration::save`], printing a failure as a warning: losing the
+    /// learned peaks must not turn a clean exit into an error.
+    pub(crate) fn save_or_warn(&self, soc: &SocInfo) {
+        if let Err(err) = self.save(soc) {
+            eprintln!("warning: power peaks not saved: {err}");
+        }
+    }
+}
+
+/// Write the peaks of every chip to `path`, through a temporary file, owned
+/// by the `sudo` user if any.
+fn write(path: &Path, chips: &BTreeMap<String, Peaks>) -> Result<()> {
+    let created = ownership::create_parent(path)?;
+    let text = serde_json::to_string_pretty(chips).map_err(|e| ceilings_error(path, e))?;
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, text)?;
+    ownership::hand_over(path, &tmp, &created)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::tests::test_soc;
+
+    fn power(cpu_w: f32, gpu_w: f32, ane_w: f32) -> PowerConsumption {
+        PowerConsumption {
+            cpu_w,
+            gpu_w,
+            ane_w,
+            package_w: cpu_w + gpu_w + ane_w,
+        }
+    }
+
+    #[test]
+    fn peaks_persist_per_brand_and_raise_ceilings() {
+        let path = std::env::temp_dir().join(format!("pumas-{}-ceilings.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        fs::write(
+            &path,
+            r#"{"Apple M2":{"cpu_w":30.0,"gpu_w":1.0,"ane_w":1.0}}"#,
+        )
+        .unwrap();
+
+        let mut soc = test_soc();
+        let mut calibration = Calibration::load(path.clone(), &mut soc).unwrap();
+        assert!(!calibration.observe(&mut soc, &power(5.0, 4.0, 1.0)));
+        assert!(calibration.observe(&mut soc, &power(24.0, 4.0, 1.0)));
+        assert_eq!(soc.max_cpu_w, 24.0);
+        assert!(soc.calibrated.cpu);
+        calibration.save(&soc).unwrap();
+
+        // The next session starts from the learned peak; other chips keep
+        // theirs.
+        let mut next = test_soc();
+        Calibration::load(path.clone(), &mut next).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!((next.max_cpu_w, next.max_gpu_w), (24.0, 20.0));
+        assert!(next.calibrated.cpu && !next.calibrated.gpu);
+        assert!(saved.contains("Apple M2"), "{saved}");
+
+        let mut fixed = test_soc();
+        assert!(!Calibration::off().observe(&mut fixed, &power(50.0, 50.0, 50.0)));
+        assert_eq!(fixed.max_cpu_w, 20.0);
+    }
+
+    #[test]
+    fn writes_create_the_state_directories() {
+        let root = std::env::temp_dir().join(format!("pumas-{}-state", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let file = root.join("state/pumas/ceilings.json");
+        write(&file, &BTreeMap::new()).unwrap();
+        let written = fs::read_to_string(&file).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(written, "{}");
+    }
+
+    #[test]
+    fn spikes_are_ignored_and_peaks_reset() {
+        let path = std::env::temp_dir().join(format!("pumas-{}-reset.json", std::process::id()));
+        // A bogus saved GPU peak, beyond 4 × 20 W, is dropped on load.
+        fs::write(
+            &path,
+            r#"{"Apple M1":{"cpu_w":30.0,"gpu_w":500.0,"ane_w":1.0}}"#,
+        )
+        .unwrap();
+
+        let mut soc = test_soc();
+        let mut calibration = Calibration::load(path.clone(), &mut soc).unwrap();
+        assert_eq!((soc.max_cpu_w, soc.max_gpu_w), (30.0, 20.0));
+
+        // A spike beyond the bound is ignored; a high but plausible one counts.
+        assert!(!calibration.observe(&mut soc, &power(5.0, 81.0, 1.0)));
+        assert!(calibration.observe(&mut soc, &power(5.0, 80.0, 1.0)));
+        assert_eq!(soc.max_gpu_w, 80.0);
+
+        assert!(calibration.reset(&mut soc).unwrap());
+        assert_eq!((soc.max_cpu_w, soc.max_gpu_w), (20.0, 20.0));
+        assert!(!soc.calibrated.any());
+        assert!(!calibration.reset(&mut soc).unwrap());
+        let mut next = test_soc();
+        Calibration::load(path.clone(), &mut next).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!((next.max_cpu_w, next.max_gpu_w), (20.0, 20.0));
+        assert!(!next.calibrated.any());
+        assert_eq!(saved.trim(), "{}");
+    }
+}
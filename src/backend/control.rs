@@ -0,0 +1,55 @@
+//! Frontend → collector control requests.
+//!
+//! The data plane stays one-directional: `Frame`s flow backend → frontend over
+//! their own channel. This is a separate, tiny command channel for the few
+//! settings the user can change while running. The collector drains it
+//! between samples with `try_recv`, so a request takes effect at the next
+//! sample boundary and never blocks streaming.
+
+use crate::config::TimeRange;
+
+/// Sampling intervals (ms) cycled through by the `+`/`-` keys.
+pub(crate) const SAMPLE_RATES_MS: [u16; 7] = [100, 250, 500, 1000, 2000, 5000, 10000];
+
+/// A runtime settings change requested by the frontend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Control {
+    /// Restart `powermetrics` with a new sampling interval (ms).
+    SampleRate(u16),
+    /// Draw `per_col` samples per sparkline column, across at most `span`
+    /// samples: frames carry that many raw points, and the retained history
+    /// grows to fit. It never shrinks, so zooming back out finds the samples
+    /// again.
+    Zoom { per_col: usize, span: usize },
+    /// Chart this time range from now on.
+    Range(TimeRange),
+    /// Restart every peak from the next sample.
+    ResetPeaks,
+}
+
+/// The next faster (`faster == true`) or slower step of [`SAMPLE_RATES_MS`]
+/// from `current`, which need not be one of the steps. Saturates at the ends.
+pub(crate) fn step_sample_rate(current: u16, faster: bool) -> u16 {
+    let next = if faster {
+        SAMPLE_RATES_MS.iter().rev().find(|&&r| r < current)
+    } else {
+        SAMPLE_RATES_MS.iter().find(|&&r| r > current)
+    };
+    next.copied().unwrap_or(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_rate_steps_saturate_and_snap_to_the_table() {
+        assert_eq!(step_sample_rate(1000, true), 500);
+        assert_eq!(step_sample_rate(1000, false), 2000);
+        assert_eq!(step_sample_rate(100, true), 100);
+        assert_eq!(step_sample_rate(10000, false), 10000);
+        // An off-table `--sample-rate` snaps to the neighbouring steps.
+        assert_eq!(step_sample_rate(300, true), 250);
+        assert_eq!(step_sample_rate(300, false), 500);
+    }
+}
@@ -10,7 +10,9 @@
 //!. The sparkline `spark` vectors carry data only; the view
 //! trims/scales to its allocated geometry.
 
-use crate::{modules::soc::SocInfo, units};
+use std::time::Duration;
+
+use crate::{config::TimeRange, metric_key::PowerDomain, modules::soc::SocInfo, units};
 
 /// A gauge + its sparkline, fully prepared.
 ///
@@ -19,25 +21,75 @@ use crate::{modules::soc::SocInfo, units};
 /// peak is already baked into `title`.
 #[derive(Clone)]
 pub(crate) struct Meter {
-    /// Pre-formatted gauge label, e.g. `"E-Cluster: 21.8 % @ 973 MHz (peak: 22.1 %)"`.
+    /// Pre-formatted gauge label, e.g.
+    /// `"E-Cluster: 21.8 % @ 973 MHz (peak: 22.1 %, session: 64.0 %)"`.
     pub title: String,
     /// Gauge fill ratio, `0.0..=1.0`.
     pub ratio: f64,
+    /// Gauge color role: gauge foreground, warning or critical.
+    pub role: ColorRole,
     /// Sparkline data (full history for Overview; last-N where a tab fixes N).
     pub spark: Vec<u64>,
+    /// `(min, max)` of each `spark` point when the points are rolled-up
+    /// bucket averages; empty for raw samples.
+    pub spark_span: Vec<(u64, u64)>,
+    /// Color role of each `spark` sample: history foreground, warning or
+    /// critical.
+    pub spark_roles: Vec<ColorRole>,
     /// Sparkline scaling ceiling, overshoot already applied.
     pub spark_max: u64,
+    /// Unit of the raw `spark` samples.
+    pub unit: SparkUnit,
 }
 
 /// A text title (no gauge) plus a sparkline. Used by the Overview Package block.
 #[derive(Clone)]
 pub(crate) struct SparkText {
-    /// Pre-formatted title, e.g. `"CPU+GPU+ANE: 130.55 mW (peak: 6.48 W)"`.
+    /// Pre-formatted title, e.g.
+    /// `"CPU+GPU+ANE: 130.55 mW (peak: 1.20 W, session: 6.48 W)"`.
     pub title: String,
     /// Sparkline data (full history).
     pub spark: Vec<u64>,
+    /// `(min, max)` of each rolled-up `spark` point; empty for raw samples.
+    pub spark_span: Vec<(u64, u64)>,
     /// Sparkline scaling ceiling = `signal.max` (Package has **no** overshoot).
     pub spark_max: u64,
+    /// Unit of the raw `spark` samples.
+    pub unit: SparkUnit,
+}
+
+/// Unit of the raw samples in a full-history `spark`, so the frontend can label
+/// a single sample (mouse hover) with the same [`units`] formatters the
+/// backend uses for titles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SparkUnit {
+    /// Percent, `0..=100`.
+    Percent,
+    /// Milliwatts.
+    Milliwatts,
+    /// Bytes.
+    Bytes,
+}
+
+impl SparkUnit {
+    /// Format one raw sample, e.g. `"42.0 %"`, `"3 W"`, `"53.0 GiB"`.
+    pub(crate) fn format(self, value: u64) -> String {
+        let v = value as f64;
+        match self {
+            SparkUnit::Percent => units::percent1(v),
+            SparkUnit::Milliwatts => units::watts(v / 1e3),
+            SparkUnit::Bytes => units::bibytes1(v),
+        }
+    }
+
+    /// Format one raw sample exactly, for a hover readout: as
+    /// [`format`](Self::format), but watts with two decimals, e.g. `"3.27 W"`.
+    pub(crate) fn format_exact(self, value: u64) -> String {
+        match self {
+            SparkUnit::Milliwatts => units::watts2(value as f64 / 1e3),
+            _ => self.format(value),
+        }
+    }
 }
 
 /// Thermal-pressure indicator: the text plus whether it is nominal (accent) or
@@ -46,14 +98,15 @@ pub(crate) struct SparkText {
 pub(crate) struct Thermals {
     /// Pressure text, e.g. `"Nominal"`.
     pub pressure: String,
-    /// `true` => accent color; `false` => Yellow.
-    pub is_nominal: bool,
+    /// Accent when nominal, warning when moderate, critical when heavier.
+    pub role: ColorRole,
 }
 
 /// The Overview tab snapshot.
 #[derive(Clone)]
 pub(crate) struct OverviewFrame {
-    /// Panel border title, e.g. `" CPU Clusters: 119.67 mW (peak: 6.42 W) "`.
+    /// Panel border title, e.g.
+    /// `" CPU Clusters: 119.67 mW (peak: 1.02 W, session: 6.42 W) "`.
     pub cpu_clusters_title: String,
     /// One meter per E-cluster, in order (frontend pairs via `chunks(2)`).
     pub e_meters: Vec<Meter>,
@@ -82,10 +135,14 @@ pub(crate) struct CpuRow {
     pub id_label: String,
     /// Activity gauge fill ratio.
     pub act_ratio: f64,
+    /// Activity gauge color role.
+    pub act_role: ColorRole,
     /// Activity label, `"{:.1}%"`.
     pub act_label: String,
-    /// Activity sparkline (last 8).
+    /// Activity sparkline (last 8 cells at the zoom).
     pub act_spark: Vec<u64>,
+    /// Color role of each `act_spark` sample.
+    pub act_spark_roles: Vec<ColorRole>,
     /// Activity sparkline ceiling (`1.05 * max`).
     pub act_spark_max: u64,
     /// Frequency value, e.g. `"972 MHz"`.
@@ -95,10 +152,20 @@ pub(crate) struct CpuRow {
     /// Frequency gauge's default label, `"{:3.0}%"`. The freq LineGauge has no
     /// explicit label, so the original draws this default.
     pub freq_label: String,
-    /// Frequency sparkline (last 8).
+    /// Frequency sparkline (last 8 cells at the zoom).
     pub freq_spark: Vec<u64>,
     /// Frequency sparkline ceiling (`1.05 * max`).
     pub freq_spark_max: u64,
+    /// Heatmap row label, `"{cluster:<3}{id:>3}"` with the cluster (`E`,
+    /// `P0`, …) on the cluster's first core only.
+    pub heat_label: String,
+    /// Full activity history in percent, for the heatmap. Percent fits a
+    /// byte, and these per-core histories would otherwise dominate the frame.
+    pub act_history: Vec<u8>,
+    /// Color role of each `act_history` sample.
+    pub act_history_roles: Vec<ColorRole>,
+    /// Full frequency history in percent of the maximum frequency.
+    pub freq_history: Vec<u8>,
 }
 
 /// A bordered CPU cluster block on the CPU tab.
@@ -132,10 +199,14 @@ pub(crate) struct CpuFrame {
 pub(crate) struct GpuFrame {
     /// Activity gauge fill ratio.
     pub act_ratio: f64,
+    /// Activity gauge color role.
+    pub act_role: ColorRole,
     /// Activity label, `"{:.1}%"`.
     pub act_label: String,
-    /// Activity sparkline (last 8).
+    /// Activity sparkline (last 8 cells at the zoom).
     pub act_spark: Vec<u64>,
+    /// Color role of each `act_spark` sample.
+    pub act_spark_roles: Vec<ColorRole>,
     /// Activity sparkline ceiling (`1.05 * max`).
     pub act_spark_max: u64,
     /// Frequency value, e.g. `"444 MHz"`.
@@ -145,16 +216,22 @@ pub(crate) struct GpuFrame {
     /// Frequency gauge's default label, `"{:3.0}%"`. The freq LineGauge has no
     /// explicit label, so the original draws this default.
     pub freq_label: String,
-    /// Frequency sparkline (last 8).
+    /// Frequency sparkline (last 8 cells at the zoom).
     pub freq_spark: Vec<u64>,
     /// Frequency sparkline ceiling (`1.05 * max`).
     pub freq_spark_max: u64,
     /// Power value, e.g. `"10.88 mW"`.
     pub power_value: String,
-    /// Power sparkline (last 8).
+    /// Power sparkline in mW (last 8 cells at the zoom).
     pub power_spark: Vec<u64>,
     /// Power sparkline ceiling (`1.05 * max`).
     pub power_spark_max: u64,
+    /// Full activity history in percent, for the line-chart style.
+    pub act_history: Vec<u64>,
+    /// Color role of each `act_history` sample.
+    pub act_history_roles: Vec<ColorRole>,
+    /// Full frequency history in percent of the maximum frequency.
+    pub freq_history: Vec<u64>,
     /// Peak text, `"Peak: {p1} | {w}"`.
     pub peak_text: String,
     /// Thermal pressure.
@@ -163,8 +240,8 @@ pub(crate) struct GpuFrame {
     pub freq_table: FreqTable,
 }
 
-/// Theme color role for a [`MemSpan`] (mapped to a concrete color in the
-/// frontend theme).
+/// Theme color role for a [`MemSpan`], a gauge, a sparkline bar or the thermal
+/// pressure (mapped to a concrete color in the frontend theme).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum ColorRole {
     /// Accent color.
@@ -173,6 +250,12 @@ pub(crate) enum ColorRole {
     GaugeFg,
     /// History foreground.
     HistoryFg,
+    /// Elevated value.
+    Warning,
+    /// Critical value.
+    Critical,
+    /// A power domain's own color on the Power tab.
+    Power(PowerDomain),
     /// Terminal default.
     Default,
 }
@@ -203,11 +286,75 @@ pub(crate) struct MemoryFrame {
     pub sysinfo_lines: Vec<MemLine>,
 }
 
+/// One stacked layer of the Power chart.
+#[derive(Clone)]
+pub(crate) struct PowerLayer {
+    /// Layer color role.
+    pub role: ColorRole,
+    /// Full history in milliwatts, aligned with [`Frame::timeline`].
+    pub history: Vec<u64>,
+}
+
+/// A horizontal reference line on the Power chart: an SoC power ceiling.
+#[derive(Clone)]
+pub(crate) struct PowerCeiling {
+    /// Pre-formatted label, e.g. `"CPU max 52 W"`.
+    pub label: String,
+    /// Ceiling in milliwatts.
+    pub value: u64,
+    /// Line color role.
+    pub role: ColorRole,
+}
+
+/// One row of the Power table: a domain, or the package total.
+#[derive(Clone)]
+pub(crate) struct PowerRow {
+    /// Row label, e.g. `"CPU"`.
+    pub label: String,
+    /// Label color role (the domain's layer color).
+    pub role: ColorRole,
+    /// Latest sample, e.g. `"1.25 W"`.
+    pub current: String,
+    /// Mean since the start, e.g. `"830.12 mW"`.
+    pub average: String,
+    /// Peak since the start.
+    pub peak: String,
+    /// Energy since the start, e.g. `"1.2 kJ"`.
+    pub energy: String,
+}
+
+/// Power tab: the stacked power chart and the per-domain table.
+#[derive(Clone)]
+pub(crate) struct PowerFrame {
+    /// Chart layers, bottom first: CPU, GPU, ANE, other.
+    pub layers: Vec<PowerLayer>,
+    /// Chart ceiling in milliwatts: the lowest SoC ceiling at or above the
+    /// stacked peak of the window, else that peak.
+    pub chart_max: u64,
+    /// Unit of the layer histories and ceilings.
+    pub unit: SparkUnit,
+    /// SoC ceilings, drawn when below `chart_max`.
+    pub ceilings: Vec<PowerCeiling>,
+    /// One row per domain, then the package total.
+    pub rows: Vec<PowerRow>,
+}
+
 /// One owned, `Clone` snapshot shipped per sample. SoC info and the header are
 /// session-static and intentionally **not** carried here (see
-/// [`RenderedHeader`] / [`render_soc_rows`]).
+/// [`RenderedHeader`] / [`render_soc_rows`]), except the power ceilings.
 #[derive(Clone)]
 pub(crate) struct Frame {
+    /// Time since the collector started, at this sample. Lets the frontend
+    /// label past frames (`"PAUSED @ t-42s"`) without a wall clock.
+    pub elapsed: Duration,
+    /// Time range of every full-history series: raw samples, or rolled-up
+    /// buckets.
+    pub range: TimeRange,
+    /// Sample times of the retained history, oldest first. Aligned with the
+    /// tail of every full-history `spark`, so `timeline[len - k]` is the time
+    /// of a spark's k-th newest sample (a bucket's last sample, when rolled
+    /// up).
+    pub timeline: Vec<Duration>,
     /// Overview tab.
     pub overview: OverviewFrame,
     /// CPU tab.
@@ -216,6 +363,13 @@ pub(crate) struct Frame {
     pub gpu: GpuFrame,
     /// Memory tab.
     pub memory: MemoryFrame,
+    /// Power tab.
+    pub power: PowerFrame,
+    /// Time since boot, e.g. `3d 4h 12m` (SoC tab).
+    pub uptime: String,
+    /// The `Max … power` rows of the SoC tab, from the ceilings as raised so
+    /// far (see [`render_ceiling_rows`]).
+    pub soc_ceilings: Vec<(String, String)>,
 }
 
 /// Session-static title-bar strings (built once, never per-frame).
@@ -241,7 +395,8 @@ pub(crate) fn render_header(soc: &SocInfo) -> RenderedHeader {
     }
 }
 
-/// Session-static SoC tab rows (built once).
+/// Session-static SoC tab rows (built once). The power ceilings, which
+/// calibration can raise mid-session, come with each frame instead.
 #[derive(Clone, Default)]
 pub(crate) struct SocRows {
     /// `(left_label, right_value)` rows; right value rendered bold.
@@ -250,22 +405,51 @@ pub(crate) struct SocRows {
 
 /// Build the SoC tab rows from the SoC info.
 pub(crate) fn render_soc_rows(soc: &SocInfo) -> SocRows {
-    SocRows {
-        rows: vec![
-            ("SoC brand name:".into(), soc.cpu_brand_name.clone()),
-            ("CPU cores:".into(), format!("{}", soc.num_cpu_cores)),
-            (
-                "- Efficiency cores:".into(),
-                format!("{}", soc.num_efficiency_cores),
-            ),
-            (
-                "- Performance cores:".into(),
-                format!("{}", soc.num_performance_cores),
+    let mut rows = vec![
+        ("SoC brand name:".into(), soc.cpu_brand_name.clone()),
+        ("Model:".into(), soc.model.clone()),
+        (
+            "macOS:".into(),
+            format!("{} ({})", soc.os_version, soc.os_build),
+        ),
+        (
+            "Unified memory:".into(),
+            units::bibytes0(soc.memory_bytes as f64),
+        ),
+        ("Dies:".into(), format!("{}", soc.num_dies)),
+        ("CPU cores:".into(), format!("{}", soc.num_cpu_cores)),
+        (
+            "- Efficiency cores:".into(),
+            format!("{}", soc.num_efficiency_cores),
+        ),
+        (
+            "- Performance cores:".into(),
+            format!("{}", soc.num_performance_cores),
+        ),
+    ];
+    if soc.num_super_cores > 0 {
+        rows.push(("- Super cores:".into(), format!("{}", soc.num_super_cores)));
+    }
+    rows.push(("GPU cores:".into(), format!("{}", soc.num_gpu_cores)));
+    for level in &soc.perf_levels {
+        rows.push((
+            format!("{} caches:", level.name),
+            format!(
+                "L1i {} / L1d {} per core, L2 {}",
+                units::bibytes0(level.l1i_bytes as f64),
+                units::bibytes0(level.l1d_bytes as f64),
+                units::bibytes0(level.l2_bytes as f64)
             ),
-            ("GPU cores:".into(), format!("{}", soc.num_gpu_cores)),
-            ("Max CPU power:".into(), units::watts(soc.max_cpu_w)),
-            ("Max GPU power:".into(), units::watts(soc.max_gpu_w)),
-            ("Max ANE power:".into(), units::watts(soc.max_ane_w)),
-        ],
+        ));
     }
+    SocRows { rows }
+}
+
+/// Build the SoC tab's power ceiling rows, following [`render_soc_rows`].
+pub(crate) fn render_ceiling_rows(soc: &SocInfo) -> Vec<(String, String)> {
+    vec![
+        ("Max CPU power:".into(), units::watts(soc.max_cpu_w)),
+        ("Max GPU power:".into(), units::watts(soc.max_gpu_w)),
+        ("Max ANE power:".into(), units::watts(soc.max_ane_w)),
+    ]
 }
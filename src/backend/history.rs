@@ -3,21 +3,175 @@
 //! `Signal<T>` is the metric ring buffer. `History` and
 //! `HistoryExt::get_or_default` give the collector thread sole ownership of all
 //! history state so the frontend holds none.
+//!
+//! Besides its `history_size` raw points, every signal rolls each sample up
+//! into fixed-width time [`TIERS`] (10 s, 1 min and 10 min buckets of
+//! min / avg / max), so the longer [`TimeRange`]s can chart hours of history
+//! in a few hundred points. A bucket covers `[k·width, (k+1)·width)` of the
+//! collector's clock; the newest, still open bucket is charted too. The
+//! [`Timeline`] rolls up in lockstep, keeping each bucket's last sample time.
+//!
+//! A signal also tracks two peaks: the session `peak`, and the
+//! [`recent_peak`](Signal::recent_peak) of the `--peak-window`, which the
+//! collector expires every sample. Both can be reset at runtime.
 
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
 
 use num_traits::{Bounded, Num, cast::ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::TimeRange,
+    metric_key::{MetricKey, PowerDomain},
+};
+
+/// Rollup tiers: bucket width and number of buckets kept (the open one
+/// included). Each covers one of the rolled-up [`TimeRange`]s.
+pub(crate) const TIERS: [(Duration, usize); 3] = [
+    (Duration::from_secs(10), 360),
+    (Duration::from_secs(60), 360),
+    (Duration::from_secs(600), 144),
+];
+
+/// The tier charting `range`, `None` for the raw points.
+fn tier(range: TimeRange) -> Option<usize> {
+    match range {
+        TimeRange::Recent => None,
+        TimeRange::Hour => Some(0),
+        TimeRange::SixHours => Some(1),
+        TimeRange::Day => Some(2),
+    }
+}
+
+/// What a frame charts of each signal: the points of a time range, but of
+/// the raw points only the newest `last`, the span the view shows at its
+/// zoom. The retained history may be longer, so zooming back out finds it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Window {
+    pub range: TimeRange,
+    pub last: usize,
+    /// History samples per CPU and GPU row sparkline cell.
+    pub per_col: usize,
+}
 
-use crate::metric_key::MetricKey;
+impl From<TimeRange> for Window {
+    /// Every point of `range`, unzoomed.
+    fn from(range: TimeRange) -> Self {
+        Self {
+            range,
+            last: usize::MAX,
+            per_col: 1,
+        }
+    }
+}
+
+/// Index of the `width`-wide bucket holding the sample taken at `elapsed`.
+fn bucket_index(elapsed: Duration, width: Duration) -> u128 {
+    elapsed.as_millis() / width.as_millis()
+}
+
+/// The samples that fell into one time bucket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Bucket {
+    min: u64,
+    max: u64,
+    sum: u64,
+    count: u64,
+}
+
+impl Bucket {
+    fn new(value: u64) -> Self {
+        Self {
+            min: value,
+            max: value,
+            sum: value,
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, value: u64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum = self.sum.saturating_add(value);
+        self.count += 1;
+    }
+
+    /// Mean of the samples, rounded.
+    fn avg(&self) -> u64 {
+        (self.sum + self.count / 2) / self.count.max(1)
+    }
+}
+
+/// One rollup tier of a signal or the timeline: closed buckets, oldest first,
+/// then the open one.
+#[derive(Serialize, Deserialize)]
+struct Rollup<T> {
+    closed: VecDeque<T>,
+    open: Option<(u128, T)>,
+    width: Duration,
+    capacity: usize,
+}
+
+impl<T> Rollup<T> {
+    fn new((width, capacity): (Duration, usize)) -> Self {
+        Self {
+            closed: VecDeque::new(),
+            open: None,
+            width,
+            capacity,
+        }
+    }
+
+    /// Fold a sample taken at `elapsed` into the open bucket with `merge`, or
+    /// close it and open a new one with `start` when `elapsed` is past it.
+    fn push(&mut self, elapsed: Duration, merge: impl FnOnce(&mut T), start: impl FnOnce() -> T) {
+        let index = bucket_index(elapsed, self.width);
+        match &mut self.open {
+            Some((open, acc)) if *open == index => merge(acc),
+            open => {
+                if let Some((_, done)) = open.replace((index, start())) {
+                    if self.closed.len() + 1 >= self.capacity {
+                        self.closed.pop_front();
+                    }
+                    self.closed.push_back(done);
+                }
+            }
+        }
+    }
+
+    /// Every bucket, oldest first, the open one last.
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.closed
+            .iter()
+            .chain(self.open.iter().map(|(_, acc)| acc))
+    }
+}
 
 /// A signal is a collection of points that can be used to draw a line graph.
+#[derive(Serialize, Deserialize)]
 pub(crate) struct Signal<T>
 where
     T: Num,
 {
+    /// Highest value since the start (or the last [`reset_peaks`]).
+    ///
+    /// [`reset_peaks`]: Self::reset_peaks
     pub(crate) peak: T,
     pub(crate) max: T,
     pub(crate) points: std::collections::VecDeque<u64>,
+    /// Number of points retained (the deque's own capacity may be larger).
+    capacity: usize,
+    /// One rollup per [`TIERS`] entry.
+    tiers: [Rollup<Bucket>; TIERS.len()],
+    /// Candidates for the peak of the recent window: samples pushed with
+    /// [`push_at`](Self::push_at) that no later sample exceeds, oldest (and
+    /// highest) first.
+    #[serde(default)]
+    recent: VecDeque<(Duration, T)>,
 }
 
 impl<T: Num + Bounded> Signal<T> {
@@ -26,6 +180,9 @@ impl<T: Num + Bounded> Signal<T> {
             peak: T::zero(),
             max,
             points: std::collections::VecDeque::with_capacity(capacity),
+            capacity,
+            tiers: TIERS.map(Rollup::new),
+            recent: VecDeque::new(),
         }
     }
 }
@@ -34,12 +191,63 @@ impl<T: Num + ToPrimitive + PartialOrd + Copy> Signal<T> {
     pub(crate) fn push(&mut self, value: T) {
         self.peak = if self.peak > value { self.peak } else { value };
 
-        if self.points.len() == self.points.capacity() {
+        if self.points.len() >= self.capacity {
             self.points.pop_front();
         }
         self.points.push_back(value.to_u64().unwrap_or(0));
         self.points.make_contiguous();
     }
+
+    /// [`push`](Self::push) a sample taken at `elapsed`, also rolling it up
+    /// into every tier and tracking it for the [`recent_peak`].
+    ///
+    /// [`recent_peak`]: Self::recent_peak
+    pub(crate) fn push_at(&mut self, value: T, elapsed: Duration) {
+        self.push(value);
+        while self.recent.back().is_some_and(|&(_, v)| v <= value) {
+            self.recent.pop_back();
+        }
+        self.recent.push_back((elapsed, value));
+        let point = value.to_u64().unwrap_or(0);
+        for tier in &mut self.tiers {
+            tier.push(elapsed, |b| b.add(point), || Bucket::new(point));
+        }
+    }
+}
+
+impl<T: Num + Copy> Signal<T> {
+    /// Highest value of the samples the last [`expire_peaks`] kept, zero if
+    /// none.
+    ///
+    /// [`expire_peaks`]: Self::expire_peaks
+    pub(crate) fn recent_peak(&self) -> T {
+        self.recent.front().map_or(T::zero(), |&(_, v)| v)
+    }
+
+    /// Limit the [`recent_peak`](Self::recent_peak) to the samples taken at
+    /// or after `since`.
+    pub(crate) fn expire_peaks(&mut self, since: Duration) {
+        while self.recent.front().is_some_and(|&(t, _)| t < since) {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Forget both peaks: they restart from the next sample.
+    pub(crate) fn reset_peaks(&mut self) {
+        self.peak = T::zero();
+        self.recent.clear();
+    }
+}
+
+impl<T: Num> Signal<T> {
+    /// Change the number of retained points at runtime, dropping the oldest
+    /// ones when shrinking. The peak is kept.
+    pub(crate) fn resize(&mut self, capacity: usize) {
+        let excess = self.points.len().saturating_sub(capacity);
+        self.points.drain(..excess);
+        self.points.make_contiguous();
+        self.capacity = capacity;
+    }
 }
 
 impl<T: Num> Signal<T> {
@@ -60,6 +268,137 @@ impl<T: Num> Signal<T> {
             &self.as_slice()[len - n..]
         }
     }
+
+    /// The points charted over `window`: the newest raw points, or the tier's
+    /// bucket averages. Aligned with [`Timeline::in_range`].
+    pub(crate) fn points_in(&self, window: impl Into<Window>) -> Cow<'_, [u64]> {
+        let Window { range, last, .. } = window.into();
+        match tier(range) {
+            None => Cow::Borrowed(self.as_slice_last_n(last)),
+            Some(t) => Cow::Owned(self.tiers[t].iter().map(Bucket::avg).collect()),
+        }
+    }
+
+    /// Like [`points_in`](Self::points_in), but each bucket's highest sample,
+    /// so a short burst still colors its bucket.
+    pub(crate) fn peaks_in(&self, window: impl Into<Window>) -> Cow<'_, [u64]> {
+        let Window { range, last, .. } = window.into();
+        match tier(range) {
+            None => Cow::Borrowed(self.as_slice_last_n(last)),
+            Some(t) => Cow::Owned(self.tiers[t].iter().map(|b| b.max).collect()),
+        }
+    }
+
+    /// `(min, max)` of each bucket of [`points_in`](Self::points_in); empty
+    /// for the raw points.
+    pub(crate) fn spans_in(&self, window: impl Into<Window>) -> Vec<(u64, u64)> {
+        tier(window.into().range).map_or_else(Vec::new, |t| {
+            self.tiers[t].iter().map(|b| (b.min, b.max)).collect()
+        })
+    }
+}
+
+/// Sample times (since the collector started) of the retained history, oldest
+/// first. Pushed and resized in lockstep with the signals, so it spans the same
+/// window and a signal's k-th newest point was taken at the k-th newest time.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Timeline {
+    times: VecDeque<Duration>,
+    capacity: usize,
+    /// Last sample time of each bucket, per [`TIERS`] entry.
+    tiers: [Rollup<Duration>; TIERS.len()],
+}
+
+impl Timeline {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            times: VecDeque::with_capacity(capacity),
+            capacity,
+            tiers: TIERS.map(Rollup::new),
+        }
+    }
+
+    pub(crate) fn push(&mut self, elapsed: Duration) {
+        if self.times.len() >= self.capacity {
+            self.times.pop_front();
+        }
+        self.times.push_back(elapsed);
+        for tier in &mut self.tiers {
+            tier.push(elapsed, |last| *last = elapsed, || elapsed);
+        }
+    }
+
+    /// See [`Signal::resize`].
+    pub(crate) fn resize(&mut self, capacity: usize) {
+        let excess = self.times.len().saturating_sub(capacity);
+        self.times.drain(..excess);
+        self.capacity = capacity;
+    }
+
+    /// The retained times, oldest first.
+    pub(crate) fn to_vec(&self) -> Vec<Duration> {
+        self.times.iter().copied().collect()
+    }
+
+    /// The times of [`Signal::points_in`]`(window)`, oldest first: the raw
+    /// sample times, or each bucket's last sample time.
+    pub(crate) fn in_range(&self, window: impl Into<Window>) -> Vec<Duration> {
+        let Window { range, last, .. } = window.into();
+        match tier(range) {
+            None => {
+                let mut times = self.to_vec();
+                times.drain(..times.len().saturating_sub(last));
+                times
+            }
+            Some(t) => self.tiers[t].iter().copied().collect(),
+        }
+    }
+}
+
+/// Energy drawn by each [`PowerDomain`] this session. Unlike the signals it is
+/// never trimmed: each sample adds its power times the time since the previous
+/// sample. It is not restored with a `--history-file`, so it always starts at
+/// zero.
+pub(crate) struct Energy {
+    joules: [f64; PowerDomain::ALL.len()],
+    start: Duration,
+    last: Duration,
+}
+
+impl Energy {
+    /// Start counting at `start` on the collector's clock.
+    pub(crate) fn starting_at(start: Duration) -> Self {
+        Self {
+            joules: [0.0; PowerDomain::ALL.len()],
+            start,
+            last: start,
+        }
+    }
+
+    /// When counting started, on the collector's clock.
+    pub(crate) fn start(&self) -> Duration {
+        self.start
+    }
+
+    /// Add a sample taken at `elapsed`, drawing `watts(domain)` since the
+    /// previous one (or since the start, for the first sample).
+    pub(crate) fn push(&mut self, elapsed: Duration, watts: impl Fn(PowerDomain) -> f32) {
+        let secs = elapsed.saturating_sub(self.last).as_secs_f64();
+        for (joules, domain) in self.joules.iter_mut().zip(PowerDomain::ALL) {
+            *joules += f64::from(watts(domain)) * secs;
+        }
+        self.last = elapsed;
+    }
+
+    /// Energy drawn by `domain`, in joules.
+    pub(crate) fn joules(&self, domain: PowerDomain) -> f64 {
+        self.joules[domain as usize]
+    }
+
+    /// Energy drawn by the whole package, in joules.
+    pub(crate) fn total(&self) -> f64 {
+        self.joules.iter().sum()
+    }
 }
 
 /// History of all signals, keyed by [`MetricKey`] (formerly `app::History`).
@@ -131,4 +470,137 @@ mod tests {
         assert_eq!(signal.as_slice(), &[1, 1, 0]);
         assert_eq!(signal.peak, 4.0);
     }
+
+    #[test]
+    fn test_signal_resize() {
+        let mut signal = Signal::<u32>::with_capacity(4, /* max */ 10);
+        for v in 1..=4 {
+            signal.push(v);
+        }
+
+        signal.resize(2);
+        assert_eq!(signal.as_slice(), &[3, 4]);
+        signal.push(5);
+        assert_eq!(signal.as_slice(), &[4, 5]);
+        assert_eq!(signal.peak, 5);
+
+        signal.resize(3);
+        signal.push(6);
+        signal.push(7);
+        assert_eq!(signal.as_slice(), &[5, 6, 7]);
+    }
+
+    #[test]
+    fn test_timeline_follows_signal_window() {
+        let mut timeline = Timeline::with_capacity(3);
+        for s in 0..5 {
+            timeline.push(Duration::from_secs(s));
+        }
+        let secs = |t: &Timeline| t.to_vec().iter().map(Duration::as_secs).collect::<Vec<_>>();
+        assert_eq!(secs(&timeline), [2, 3, 4]);
+        timeline.resize(2);
+        assert_eq!(secs(&timeline), [3, 4]);
+        timeline.resize(4);
+        timeline.push(Duration::from_secs(5));
+        timeline.push(Duration::from_secs(6));
+        assert_eq!(secs(&timeline), [3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn windows_trim_only_the_raw_points() {
+        let mut signal = Signal::<u32>::with_capacity(4, /* max */ 100);
+        let mut timeline = Timeline::with_capacity(4);
+        for s in 0..4 {
+            signal.push_at(s * 10, Duration::from_secs(u64::from(s)));
+            timeline.push(Duration::from_secs(u64::from(s)));
+        }
+        let last = |range| Window {
+            range,
+            last: 2,
+            per_col: 1,
+        };
+        assert_eq!(&*signal.points_in(last(TimeRange::Recent)), &[20, 30]);
+        assert_eq!(&*signal.peaks_in(last(TimeRange::Recent)), &[20, 30]);
+        assert_eq!(
+            timeline.in_range(last(TimeRange::Recent)),
+            [Duration::from_secs(2), Duration::from_secs(3)]
+        );
+        assert_eq!(&*signal.points_in(last(TimeRange::Hour)), &[15]);
+    }
+
+    #[test]
+    fn tiers_roll_up_min_avg_max_per_bucket() {
+        let mut signal = Signal::<u32>::with_capacity(2, /* max */ 100);
+        let mut timeline = Timeline::with_capacity(2);
+        for (secs, value) in [(0, 1), (4, 2), (9, 6), (10, 40), (15, 20), (25, 7)] {
+            signal.push_at(value, Duration::from_secs(secs));
+            timeline.push(Duration::from_secs(secs));
+        }
+        // Raw points are unaffected.
+        assert_eq!(&*signal.points_in(TimeRange::Recent), &[20, 7]);
+        // 10 s buckets: [0, 10), [10, 20), then the open [20, 30).
+        assert_eq!(&*signal.points_in(TimeRange::Hour), &[3, 30, 7]);
+        assert_eq!(&*signal.peaks_in(TimeRange::Hour), &[6, 40, 7]);
+        assert_eq!(signal.spans_in(TimeRange::Hour), [(1, 6), (20, 40), (7, 7)]);
+        assert!(signal.spans_in(TimeRange::Recent).is_empty());
+        // Each bucket is timed by its last sample.
+        let secs = |range| {
+            timeline
+                .in_range(range)
+                .iter()
+                .map(Duration::as_secs)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(secs(TimeRange::Hour), [9, 15, 25]);
+        assert_eq!(secs(TimeRange::Day), [25]);
+        assert_eq!(&*signal.points_in(TimeRange::Day), &[13]);
+    }
+
+    #[test]
+    fn tiers_keep_their_capacity() {
+        let mut signal = Signal::<u32>::with_capacity(128, /* max */ 100);
+        let mut timeline = Timeline::with_capacity(128);
+        for i in 0..400 {
+            let t = Duration::from_secs(i * 10);
+            signal.push_at(i as u32, t);
+            timeline.push(t);
+        }
+        assert_eq!(signal.points_in(TimeRange::Hour).len(), 360);
+        assert_eq!(signal.points_in(TimeRange::Hour)[359], 399);
+        assert_eq!(timeline.in_range(TimeRange::Hour).len(), 360);
+        // 4000 s: 66 full minutes and the open one.
+        assert_eq!(signal.points_in(TimeRange::SixHours).len(), 67);
+        assert_eq!(timeline.in_range(TimeRange::SixHours).len(), 67);
+    }
+
+    #[test]
+    fn recent_peaks_expire_and_reset() {
+        let mut signal = Signal::<u32>::with_capacity(8, /* max */ 100);
+        for (secs, value) in [(0, 90), (10, 30), (20, 50), (30, 40)] {
+            signal.push_at(value, Duration::from_secs(secs));
+        }
+        assert_eq!((signal.recent_peak(), signal.peak), (90, 90));
+        signal.expire_peaks(Duration::from_secs(5));
+        assert_eq!((signal.recent_peak(), signal.peak), (50, 90));
+        signal.expire_peaks(Duration::from_secs(25));
+        assert_eq!(signal.recent_peak(), 40);
+
+        signal.reset_peaks();
+        assert_eq!((signal.recent_peak(), signal.peak), (0, 0));
+        signal.push_at(10, Duration::from_secs(40));
+        assert_eq!((signal.recent_peak(), signal.peak), (10, 10));
+        // The points themselves are kept.
+        assert_eq!(signal.as_slice(), [90, 30, 50, 40, 10]);
+    }
+
+    #[test]
+    fn energy_integrates_power_between_samples() {
+        let mut energy = Energy::starting_at(Duration::ZERO);
+        let watts = |domain| if domain == PowerDomain::Cpu { 2.0 } else { 0.5 };
+        energy.push(Duration::from_millis(500), watts);
+        energy.push(Duration::from_millis(1500), watts);
+        assert!((energy.joules(PowerDomain::Cpu) - 3.0).abs() < 1e-9);
+        assert!((energy.joules(PowerDomain::Other) - 0.75).abs() < 1e-9);
+        assert!((energy.total() - 5.25).abs() < 1e-9);
+    }
 }
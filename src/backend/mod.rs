@@ -2,90 +2,293 @@
 //!
 //! A single OS thread owns the `powermetrics` subprocess, the sysinfo merge and
 //! all signal [`history`], and ships an owned [`Frame`] per sample over a
-//! `smol::channel`. The frontend never drives the backend.
+//! `smol::channel`. The frontend never pulls data from the backend.
 //!
 //! The same streaming loop powers `run --json`: [`run_exporter`] prints one JSON
-//! line per sample instead of building a `Frame`, byte-identical to the previous
-//! implementation.
+//! line per sample instead of building a `Frame`, with the recent and session
+//! peaks of the headline signals alongside the metrics.
+//!
+//! `pumas status` rides the same loop too: [`run_status`] renders a one-line
+//! [`Template`] per sample.
+//!
+//! Runtime settings (sample rate, history size) arrive on a separate
+//! [`control`] channel, drained between samples.
+//!
+//! Gauge and sparkline color roles are picked per sample by [`thresholds`];
+//! the gauges and labels show values averaged by [`smoothing`], while the
+//! history stays raw.
+//!
+//! With `--history-file`, the collector starts from the history [`persist`]
+//! restored and saves it again when it stops.
+//!
+//! Every loop also feeds each sample's power to the [`calibrate`] state, which
+//! raises the SoC power ceilings to observed peaks; the power signals are
+//! rescaled to match.
 
+pub(crate) mod calibrate;
+pub(crate) mod control;
 pub(crate) mod frame;
 pub(crate) mod history;
+pub(crate) mod persist;
+pub(crate) mod smoothing;
+pub(crate) mod thresholds;
 
 use std::{
     collections::HashMap,
-    io::{BufRead, BufReader, Read},
+    io::{BufRead, BufReader, IsTerminal, Read, Write},
     ops::ControlFlow,
     process::{self, Stdio},
-    time::Duration,
+    time::{Duration, Instant, SystemTime},
 };
 
-use smol::channel::Sender;
+use smol::channel::{Receiver, Sender};
 
 use crate::{
     Result,
-    config::RunConfig,
+    config::{Levels, MetricSmoothing, RunConfig, StatusConfig, Thresholds},
     error::Error as CrateError,
-    metric_key::{ClusterId, MetricKey},
+    metric_key::{ClusterId, MetricKey, PowerDomain},
     metrics::{ClusterMetrics, CpuMetrics, Metrics},
-    modules::{powermetrics, soc::SocInfo, sysinfo, vm_stat::VmStats},
+    modules::{powermetrics, soc::SocInfo, sysinfo},
+    status::Template,
     units,
 };
 
-use history::{History, HistoryExt};
+use calibrate::Calibration;
+use control::Control;
+use history::{Energy, History, HistoryExt, Signal, Timeline, Window};
+use persist::Restored;
+use smoothing::Smoother;
+use thresholds::{gauge_role, pressure_role, spark_roles};
 
-use frame::ColorRole::{Accent, Default as Def, GaugeFg, HistoryFg};
+use frame::ColorRole::{Accent, Default as Def, GaugeFg, HistoryFg, Power, Warning};
 use frame::{
     CpuCluster, CpuFrame, CpuRow, Frame, FreqTable, GpuFrame, MemLine, MemSpan, MemoryFrame, Meter,
-    OverviewFrame, SparkText, Thermals,
+    OverviewFrame, PowerCeiling, PowerFrame, PowerLayer, PowerRow, SparkText, SparkUnit, Thermals,
 };
 
 /// Overshoot keeping sparkline bars from touching the gauge above
 ///. Applied everywhere except the Overview Package block.
 const SPARKLINE_MAX_OVERSHOOT: f32 = 1.05;
 
-/// Fixed sparkline window (in samples) for the per-core CPU/GPU rows.
+/// Fixed sparkline window (in cells) for the per-core CPU/GPU rows; each cell
+/// is [`Window::per_col`] samples.
 const HISTORY_LENGTH: usize = 8;
 
 // ─── Public entry points ────────────────────────────────────────────────────
 
 /// Run the collector loop, shipping one [`Frame`] per sample over `tx`.
 ///
-/// Owns the `History`. On send error (the UI is gone) the powermetrics
-/// subprocess is killed and the loop returns.
-pub(crate) fn run_collector(soc: SocInfo, run_config: RunConfig, tx: Sender<Frame>) -> Result<()> {
+/// Owns the `History`, starting from the `restored` one if given and saving it
+/// to the `--history-file` when the loop ends. On send error (the UI is gone)
+/// the powermetrics subprocess is killed and the loop returns. Pending
+/// [`Control`] requests are applied after each sample; a sample-rate change
+/// kills `powermetrics` and restarts it at the new interval, keeping the
+/// history. `thresholds` set the gauge color levels, and `smoothing` how the
+/// displayed values are averaged. The `calibration` raises the ceilings of
+/// `soc`, and saves its peaks when the loop ends; failing that only warns.
+pub(crate) fn run_collector(
+    (mut soc, mut calibration): (SocInfo, Calibration),
+    run_config: RunConfig,
+    (thresholds, smoothing): (Thresholds, MetricSmoothing),
+    restored: Option<Restored>,
+    tx: Sender<Frame>,
+    control: Receiver<Control>,
+) -> Result<()> {
+    let mut tick_rate = Duration::from_millis(u64::from(run_config.sample_rate_ms));
+    let mut history_size = run_config.history_size;
+    let mut window = Window {
+        range: run_config.range,
+        last: history_size,
+        per_col: 1,
+    };
+    let peak_window = Duration::from_secs(u64::from(run_config.peak_window_s));
+    // A restored history continues on its own clock.
+    let (mut history, mut timeline, resumed) = match restored {
+        Some(r) => (r.history, r.timeline, r.elapsed),
+        None => (HashMap::new(), Timeline::with_capacity(0), Duration::ZERO),
+    };
+    history
+        .values_mut()
+        .for_each(|sig| sig.resize(history_size));
+    timeline.resize(history_size);
+    rescale_power(&mut history, &soc);
+    let mut energy = Energy::starting_at(resumed);
+    let mut smoother = Smoother::new(smoothing);
+    let start = Instant::now();
+
+    let streamed = loop {
+        let mut restart = None;
+        let streamed = stream(tick_rate, |metrics| {
+            let elapsed = resumed + start.elapsed();
+            if calibration.observe(&mut soc, &metrics.consumption) {
+                rescale_power(&mut history, &soc);
+            }
+            update_history(&mut history, &soc, history_size, metrics, elapsed);
+            expire_peaks(&mut history, elapsed, peak_window);
+            timeline.push(elapsed);
+            energy.push(elapsed, |domain| metrics.consumption.domain_w(domain));
+            let state = (&history, &timeline, &energy);
+            let shown = smoother.smooth(metrics);
+            let clock = (elapsed, soc.uptime(SystemTime::now()), window);
+            let frame = build_frame(&shown, &soc, state, &thresholds, clock);
+            if tx.send_blocking(frame).is_err() {
+                // UI dropped the receiver: stop streaming.
+                return ControlFlow::Break(());
+            }
+            while let Ok(request) = control.try_recv() {
+                match request {
+                    Control::SampleRate(ms) => {
+                        restart = Some(Duration::from_millis(u64::from(ms)));
+                    }
+                    Control::Zoom { per_col, span } => {
+                        window.per_col = per_col;
+                        window.last = span;
+                        if span > history_size {
+                            history_size = span;
+                            history.values_mut().for_each(|sig| sig.resize(span));
+                            timeline.resize(span);
+                        }
+                    }
+                    Control::Range(r) => window.range = r,
+                    Control::ResetPeaks => history.values_mut().for_each(Signal::reset_peaks),
+                }
+            }
+            if restart.is_some() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        match restart {
+            Some(rate) if streamed.is_ok() => tick_rate = rate,
+            _ => break streamed,
+        }
+    };
+
+    // Save even when powermetrics failed: what was collected is still good.
+    let saved = match &run_config.history_file {
+        Some(path) => persist::save(path, &soc, &history, &timeline, resumed + start.elapsed()),
+        None => Ok(()),
+    };
+    calibration.save_or_warn(&soc);
+    streamed.and(saved)
+}
+
+/// Run the JSON exporter loop: one `{"soc":…,"metrics":…,"peaks":…}` line per
+/// sample.
+pub(crate) fn run_exporter(
+    (mut soc, mut calibration): (SocInfo, Calibration),
+    run_config: RunConfig,
+) -> Result<()> {
     let tick_rate = Duration::from_millis(u64::from(run_config.sample_rate_ms));
-    let history_size = run_config.history_size;
+    let peak_window = Duration::from_secs(u64::from(run_config.peak_window_s));
+    // Only the peaks are exported: one raw point per signal is enough.
     let mut history: History = HashMap::new();
+    let start = Instant::now();
+    let streamed = stream(tick_rate, |metrics| {
+        let elapsed = start.elapsed();
+        if calibration.observe(&mut soc, &metrics.consumption) {
+            rescale_power(&mut history, &soc);
+        }
+        update_history(&mut history, &soc, 1, metrics, elapsed);
+        expire_peaks(&mut history, elapsed, peak_window);
+        let uptime = soc.uptime(SystemTime::now());
+        println!(
+            "{}",
+            export_line(&soc, uptime, metrics, &history, peak_window)
+        );
+        ControlFlow::Continue(())
+    });
+    calibration.save_or_warn(&soc);
+    streamed
+}
 
-    stream(tick_rate, |metrics| {
-        update_history(&mut history, &soc, history_size, metrics);
-        let frame = build_frame(metrics, &soc, &history);
-        if tx.send_blocking(frame).is_err() {
-            // UI dropped the receiver: stop streaming.
+/// Run the status-line loop: render `template` once per sample, rewriting a
+/// single line in place on a terminal (unless `lines`), else printing one line
+/// per sample. Stops after `count` samples, if given.
+pub(crate) fn run_status(
+    (mut soc, mut calibration): (SocInfo, Calibration),
+    args: StatusConfig,
+    mut template: Template,
+) -> Result<()> {
+    let tick_rate = Duration::from_millis(u64::from(args.sample_rate_ms));
+    let in_place = !args.lines && std::io::stdout().is_terminal();
+    let mut printed = 0;
+    let streamed = stream(tick_rate, |metrics| {
+        calibration.observe(&mut soc, &metrics.consumption);
+        let line = template.render(metrics, &soc);
+        let mut out = std::io::stdout().lock();
+        // A closed pipe (status bar gone) ends the loop like `count` does.
+        let written = if in_place {
+            write!(out, "\r\x1b[2K{line}").and_then(|()| out.flush())
+        } else {
+            writeln!(out, "{line}")
+        };
+        printed += 1;
+        if written.is_err() || args.count.is_some_and(|n| printed >= n) {
+            if in_place {
+                let _ = writeln!(out);
+            }
             ControlFlow::Break(())
         } else {
             ControlFlow::Continue(())
         }
-    })
+    });
+    calibration.save_or_warn(&soc);
+    streamed
 }
 
-/// Run the JSON exporter loop: one `{"soc":…,"metrics":…}` line per sample.
-pub(crate) fn run_exporter(soc: SocInfo, run_config: RunConfig) -> Result<()> {
-    let tick_rate = Duration::from_millis(u64::from(run_config.sample_rate_ms));
-    stream(tick_rate, |metrics| {
-        println!("{}", export_line(&soc, metrics));
-        ControlFlow::Continue(())
-    })
-}
+/// The signals whose peaks `run --json` exports, by JSON name.
+const EXPORTED_PEAKS: [(&str, MetricKey); 8] = [
+    ("cpu_w", MetricKey::CpuPowerW),
+    ("gpu_w", MetricKey::GpuPowerW),
+    ("ane_w", MetricKey::AnePowerW),
+    ("package_w", MetricKey::PackagePowerW),
+    ("gpu_active_percent", MetricKey::GpuActivePercent),
+    ("ane_active_percent", MetricKey::AneActivePercent),
+    ("ram_used_bytes", MetricKey::RamUsageBytes),
+    ("swap_used_bytes", MetricKey::SwapUsageBytes),
+];
 
 /// Serialize one sample exactly as `run --json` prints it (Display of the
-/// compact `serde_json::Value`).
-fn export_line(soc: &SocInfo, metrics: &Metrics) -> String {
-    serde_json::json!({ "soc": soc, "metrics": metrics }).to_string()
+/// compact `serde_json::Value`): the SoC with its `uptime` in whole seconds,
+/// the metrics, and the recent and session peaks of the [`EXPORTED_PEAKS`].
+fn export_line(
+    soc: &SocInfo,
+    uptime: Duration,
+    metrics: &Metrics,
+    history: &History,
+    peak_window: Duration,
+) -> String {
+    let mut peaks = serde_json::Map::new();
+    peaks.insert("window_s".into(), peak_window.as_secs().into());
+    for (name, key) in &EXPORTED_PEAKS {
+        let sig = history.get_or_default(key);
+        peaks.insert(
+            (*name).into(),
+            serde_json::json!({ "recent": sig.recent_peak(), "session": sig.peak }),
+        );
+    }
+    let soc = soc.json(uptime);
+    serde_json::json!({ "soc": soc, "metrics": metrics, "peaks": peaks }).to_string()
+}
+
+/// Limit every signal's recent peak to the `window` before `elapsed`.
+fn expire_peaks(history: &mut History, elapsed: Duration, window: Duration) {
+    let since = elapsed.saturating_sub(window);
+    history.values_mut().for_each(|sig| sig.expire_peaks(since));
 }
 
 // ─── powermetrics streaming ─────────────────────────────────────────────────
 
+/// The `powermetrics` binary.
+pub(crate) const POWERMETRICS: &str = "/usr/bin/powermetrics";
+
+/// The `powermetrics` samplers every loop reads.
+pub(crate) const SAMPLERS: [&str; 3] = ["cpu_power", "gpu_power", "thermal"];
+
 /// Stream metrics from `powermetrics`, invoking `on_sample` for each completed
 /// sample. Ported from the former `monitor::stream_metrics`; the only change is
 /// the per-sample callback in place of channel sends.
@@ -97,13 +300,14 @@ where
     F: FnMut(&Metrics) -> ControlFlow<()>,
 {
     let sample_rate_ms = format!("{}", tick_rate.as_millis());
+    let samplers = SAMPLERS.join(",");
 
-    let binary = "/usr/bin/powermetrics";
+    let binary = POWERMETRICS;
     let args = vec![
         "--sample-rate",
         sample_rate_ms.as_str(),
         "--samplers",
-        "cpu_power,gpu_power,thermal",
+        samplers.as_str(),
         "-f",
         "plist",
     ];
@@ -171,24 +375,40 @@ where
     Ok(())
 }
 
+/// Take a single sample through the same pipeline as every loop
+/// (`pumas doctor`). `None` if `powermetrics` ended, or its sample failed to
+/// parse, before one completed.
+pub(crate) fn sample_once(tick_rate: Duration) -> Result<Option<Metrics>> {
+    let mut sample = None;
+    stream(tick_rate, |metrics| {
+        sample = Some(metrics.clone());
+        ControlFlow::Break(())
+    })?;
+    Ok(sample)
+}
+
 // ─── History (ported from app::update_history) ──────────────────────────────
 
 /// Push the current sample into every signal, creating signals on first sight.
 /// Power signals scale to the SoC ceilings; memory to the reported totals; all
 /// others to 100%. Ported verbatim from `app::update_history`.
-fn update_history(history: &mut History, soc: &SocInfo, history_size: usize, metrics: &Metrics) {
-    use history::Signal;
-
+fn update_history(
+    history: &mut History,
+    soc: &SocInfo,
+    history_size: usize,
+    metrics: &Metrics,
+    elapsed: Duration,
+) {
     // Active ratios.
     for (idx, e_cluster) in metrics.e_clusters.iter().enumerate() {
         let key = MetricKey::ClusterActivePercent(ClusterId::efficiency(idx as u8));
         history
             .entry(key)
             .or_insert(Signal::with_capacity(history_size, 100.0))
-            .push(100.0 * e_cluster.active_ratio());
+            .push_at(100.0 * e_cluster.active_ratio(), elapsed);
 
         for cpu in &e_cluster.cpus {
-            push_cpu(history, history_size, cpu);
+            push_cpu(history, history_size, cpu, elapsed);
         }
     }
 
@@ -197,10 +417,10 @@ fn update_history(history: &mut History, soc: &SocInfo, history_size: usize, met
         history
             .entry(key)
             .or_insert(Signal::with_capacity(history_size, 100.0))
-            .push(100.0 * p_cluster.active_ratio());
+            .push_at(100.0 * p_cluster.active_ratio(), elapsed);
 
         for cpu in &p_cluster.cpus {
-            push_cpu(history, history_size, cpu);
+            push_cpu(history, history_size, cpu, elapsed);
         }
     }
 
@@ -209,43 +429,46 @@ fn update_history(history: &mut History, soc: &SocInfo, history_size: usize, met
         history
             .entry(key)
             .or_insert(Signal::with_capacity(history_size, 100.0))
-            .push(100.0 * s_cluster.active_ratio());
+            .push_at(100.0 * s_cluster.active_ratio(), elapsed);
 
         for cpu in &s_cluster.cpus {
-            push_cpu(history, history_size, cpu);
+            push_cpu(history, history_size, cpu, elapsed);
         }
     }
 
     history
         .entry(MetricKey::GpuActivePercent)
         .or_insert(Signal::with_capacity(history_size, 100.0))
-        .push(100.0 * metrics.gpu.active_ratio as f32);
+        .push_at(100.0 * metrics.gpu.active_ratio as f32, elapsed);
 
     history
         .entry(MetricKey::GpuFreqPercent)
         .or_insert(Signal::with_capacity(history_size, 100.0))
-        .push(100.0 * metrics.gpu.freq_ratio() as f32);
+        .push_at(100.0 * metrics.gpu.freq_ratio() as f32, elapsed);
 
     history
         .entry(MetricKey::AneActivePercent)
         .or_insert(Signal::with_capacity(history_size, 100.0))
-        .push(100.0 * metrics.consumption.ane_w / soc.max_ane_w as f32);
+        .push_at(
+            100.0 * metrics.consumption.ane_w / soc.max_ane_w as f32,
+            elapsed,
+        );
 
     // Power consumption.
     history
         .entry(MetricKey::CpuPowerW)
         .or_insert(Signal::with_capacity(history_size, soc.max_cpu_w as f32))
-        .push(metrics.consumption.cpu_w);
+        .push_at(metrics.consumption.cpu_w, elapsed);
 
     history
         .entry(MetricKey::GpuPowerW)
         .or_insert(Signal::with_capacity(history_size, soc.max_gpu_w as f32))
-        .push(metrics.consumption.gpu_w);
+        .push_at(metrics.consumption.gpu_w, elapsed);
 
     history
         .entry(MetricKey::AnePowerW)
         .or_insert(Signal::with_capacity(history_size, soc.max_ane_w as f32))
-        .push(metrics.consumption.ane_w);
+        .push_at(metrics.consumption.ane_w, elapsed);
 
     history
         .entry(MetricKey::PackagePowerW)
@@ -253,7 +476,26 @@ fn update_history(history: &mut History, soc: &SocInfo, history_size: usize, met
             history_size,
             soc.max_package_w as f32,
         ))
-        .push(metrics.consumption.package_w);
+        .push_at(metrics.consumption.package_w, elapsed);
+
+    // Stacked on the Power tab, in mW; every domain scales to the package
+    // ceiling.
+    for domain in PowerDomain::ALL {
+        history
+            .entry(MetricKey::PowerMilliwatts(domain))
+            .or_insert(Signal::with_capacity(
+                history_size,
+                1e3 * soc.max_package_w as f32,
+            ))
+            .push_at(1e3 * metrics.consumption.domain_w(domain), elapsed);
+    }
+    history
+        .entry(MetricKey::PackageMilliwatts)
+        .or_insert(Signal::with_capacity(
+            history_size,
+            1e3 * soc.max_package_w as f32,
+        ))
+        .push_at(1e3 * metrics.consumption.package_w, elapsed);
 
     // Memory usage.
     history
@@ -262,7 +504,7 @@ fn update_history(history: &mut History, soc: &SocInfo, history_size: usize, met
             history_size,
             metrics.memory.ram_total as f32,
         ))
-        .push(metrics.memory.ram_used as f32);
+        .push_at(metrics.memory.ram_used as f32, elapsed);
 
     history
         .entry(MetricKey::SwapUsageBytes)
@@ -270,63 +512,132 @@ fn update_history(history: &mut History, soc: &SocInfo, history_size: usize, met
             history_size,
             metrics.memory.swap_total as f32,
         ))
-        .push(metrics.memory.swap_used as f32);
+        .push_at(metrics.memory.swap_used as f32, elapsed);
 }
 
 /// Push a single CPU core's activity + frequency ratios into the history.
-fn push_cpu(history: &mut History, history_size: usize, cpu: &CpuMetrics) {
-    use history::Signal;
-
+fn push_cpu(history: &mut History, history_size: usize, cpu: &CpuMetrics, elapsed: Duration) {
     history
         .entry(MetricKey::CpuActivePercent(cpu.id))
         .or_insert(Signal::with_capacity(history_size, 100.0))
-        .push(100.0 * cpu.active_ratio as f32);
+        .push_at(100.0 * cpu.active_ratio as f32, elapsed);
 
     history
         .entry(MetricKey::CpuFreqPercent(cpu.id))
         .or_insert(Signal::with_capacity(history_size, 100.0))
-        .push(100.0 * cpu.freq_ratio() as f32);
+        .push_at(100.0 * cpu.freq_ratio() as f32, elapsed);
+}
+
+/// Rescale the power signals to the current SoC ceilings, as
+/// [`update_history`] would create them.
+fn rescale_power(history: &mut History, soc: &SocInfo) {
+    let package = soc.max_package_w as f32;
+    let ceilings = [
+        (MetricKey::CpuPowerW, soc.max_cpu_w as f32),
+        (MetricKey::GpuPowerW, soc.max_gpu_w as f32),
+        (MetricKey::AnePowerW, soc.max_ane_w as f32),
+        (MetricKey::PackagePowerW, package),
+    ]
+    .into_iter()
+    .chain(PowerDomain::ALL.map(|d| (MetricKey::PowerMilliwatts(d), 1e3 * package)))
+    .chain([(MetricKey::PackageMilliwatts, 1e3 * package)]);
+    for (key, max) in ceilings {
+        if let Some(sig) = history.get_mut(&key) {
+            sig.max = max;
+        }
+    }
 }
 
 // ─── Frame builder ──────────────────────────────────────────────────────────
 
 /// Build an owned [`Frame`] from the current `Metrics` + signal history. Every
 /// title/label string is formatted here (reusing [`units`]); the frontend does
-/// no formatting.
-fn build_frame(metrics: &Metrics, soc: &SocInfo, history: &History) -> Frame {
+/// no formatting, and every color role is picked here against `thresholds`.
+/// `elapsed` is the sample time since the collector started, `uptime` the time
+/// since boot, and `window` picks the raw points or a rollup tier for every
+/// full-history series.
+fn build_frame(
+    metrics: &Metrics,
+    soc: &SocInfo,
+    (history, timeline, energy): (&History, &Timeline, &Energy),
+    thresholds: &Thresholds,
+    (elapsed, uptime, window): (Duration, Duration, Window),
+) -> Frame {
     Frame {
-        overview: build_overview(metrics, soc, history),
-        cpu: build_cpu(metrics, history),
-        gpu: build_gpu(metrics, history),
+        elapsed,
+        range: window.range,
+        uptime: units::uptime(uptime),
+        soc_ceilings: frame::render_ceiling_rows(soc),
+        timeline: timeline.in_range(window),
+        overview: build_overview(metrics, soc, history, thresholds, window),
+        cpu: build_cpu(metrics, history, thresholds.cpu, window),
+        gpu: build_gpu(metrics, history, thresholds.gpu, window),
         memory: build_memory(metrics),
+        power: build_power(metrics, soc, history, energy, (elapsed, window)),
     }
 }
 
-fn build_overview(metrics: &Metrics, soc: &SocInfo, history: &History) -> OverviewFrame {
+/// The marker after a label whose power ceiling was raised to observed peaks
+/// rather than taken from the table.
+fn auto_mark(calibrated: bool) -> &'static str {
+    if calibrated { " (auto)" } else { "" }
+}
+
+fn build_overview(
+    metrics: &Metrics,
+    soc: &SocInfo,
+    history: &History,
+    thresholds: &Thresholds,
+    window: Window,
+) -> OverviewFrame {
     let cpu_pow = history.get_or_default(&MetricKey::CpuPowerW);
     let cpu_clusters_title = format!(
-        " CPU Clusters: {} (peak: {}) ",
+        " CPU Clusters: {} {} ",
         units::watts2(metrics.consumption.cpu_w),
-        units::watts2(cpu_pow.peak)
+        peak_note(|p| units::watts2(p(cpu_pow)))
     );
 
     let e_meters = metrics
         .e_clusters
         .iter()
         .enumerate()
-        .map(|(idx, c)| cluster_meter(c, ClusterId::efficiency(idx as u8), history))
+        .map(|(idx, c)| {
+            cluster_meter(
+                c,
+                ClusterId::efficiency(idx as u8),
+                history,
+                thresholds.cpu,
+                window,
+            )
+        })
         .collect();
     let p_meters = metrics
         .p_clusters
         .iter()
         .enumerate()
-        .map(|(idx, c)| cluster_meter(c, ClusterId::performance(idx as u8), history))
+        .map(|(idx, c)| {
+            cluster_meter(
+                c,
+                ClusterId::performance(idx as u8),
+                history,
+                thresholds.cpu,
+                window,
+            )
+        })
         .collect();
     let s_meters = metrics
         .s_clusters
         .iter()
         .enumerate()
-        .map(|(idx, c)| cluster_meter(c, ClusterId::super_core(idx as u8), history))
+        .map(|(idx, c)| {
+            cluster_meter(
+                c,
+                ClusterId::super_core(idx as u8),
+                history,
+                thresholds.cpu,
+                window,
+            )
+        })
         .collect();
 
     // GPU.
@@ -335,16 +646,23 @@ fn build_overview(metrics: &Metrics, soc: &SocInfo, history: &History) -> Overvi
     let gpu_pow = history.get_or_default(&MetricKey::GpuPowerW);
     let gpu_meter = Meter {
         title: format!(
-            "GPU: {} @ {} | {} (peak: {} | {})",
+            "GPU: {} @ {} | {} {}",
             units::percent1(gpu.active_ratio * 100.0),
             units::mhz(gpu.freq_mhz),
             units::watts2(metrics.consumption.gpu_w),
-            units::percent1(gpu_act.peak),
-            units::watts2(gpu_pow.peak)
+            peak_note(|p| format!(
+                "{} | {}",
+                units::percent1(p(gpu_act)),
+                units::watts2(p(gpu_pow))
+            ))
         ),
         ratio: gpu.active_ratio,
-        spark: gpu_act.as_slice().to_vec(),
+        role: gauge_role(thresholds.gpu, gpu.active_ratio * 100.0),
+        spark: gpu_act.points_in(window).into_owned(),
+        spark_span: gpu_act.spans_in(window),
+        spark_roles: spark_roles(thresholds.gpu, &gpu_act.peaks_in(window), 1.0),
         spark_max: (SPARKLINE_MAX_OVERSHOOT * gpu_act.max) as u64,
+        unit: SparkUnit::Percent,
     };
 
     // ANE.
@@ -353,27 +671,39 @@ fn build_overview(metrics: &Metrics, soc: &SocInfo, history: &History) -> Overvi
     let ane_pow = history.get_or_default(&MetricKey::AnePowerW);
     let ane_meter = Meter {
         title: format!(
-            "ANE: {} | {} (peak: {} | {})",
+            "ANE{}: {} | {} {}",
+            auto_mark(soc.calibrated.ane),
             units::percent1(ane_ratio * 100.0),
             units::watts2(metrics.consumption.ane_w),
-            units::percent1(ane_act.peak),
-            units::watts2(ane_pow.peak)
+            peak_note(|p| format!(
+                "{} | {}",
+                units::percent1(p(ane_act)),
+                units::watts2(p(ane_pow))
+            ))
         ),
         ratio: ane_ratio,
-        spark: ane_act.as_slice().to_vec(),
+        role: gauge_role(thresholds.ane, ane_ratio * 100.0),
+        spark: ane_act.points_in(window).into_owned(),
+        spark_span: ane_act.spans_in(window),
+        spark_roles: spark_roles(thresholds.ane, &ane_act.peaks_in(window), 1.0),
         spark_max: (SPARKLINE_MAX_OVERSHOOT * ane_act.max) as u64,
+        unit: SparkUnit::Percent,
     };
 
-    // Package (no overshoot).
+    // Package (no overshoot), drawn in mW so a sample reads exactly.
     let pkg = history.get_or_default(&MetricKey::PackagePowerW);
+    let pkg_mw = history.get_or_default(&MetricKey::PackageMilliwatts);
     let package = SparkText {
         title: format!(
-            "CPU+GPU+ANE: {} (peak: {})",
+            "CPU+GPU+ANE{}: {} {}",
+            auto_mark(soc.calibrated.any()),
             units::watts2(metrics.consumption.package_w),
-            units::watts2(pkg.peak)
+            peak_note(|p| units::watts2(p(pkg)))
         ),
-        spark: pkg.as_slice().to_vec(),
-        spark_max: pkg.max as u64,
+        spark: pkg_mw.points_in(window).into_owned(),
+        spark_span: pkg_mw.spans_in(window),
+        spark_max: pkg_mw.max as u64,
+        unit: SparkUnit::Milliwatts,
     };
 
     let thermals = build_thermals(metrics);
@@ -384,31 +714,51 @@ fn build_overview(metrics: &Metrics, soc: &SocInfo, history: &History) -> Overvi
     let ram_ratio = mem.ram_usage_ratio();
     let ram = Meter {
         title: format!(
-            "Memory Used: {} = {} / {} (peak: {} = {})",
+            "Memory Used: {} = {} / {} {}",
             units::percent1(ram_ratio * 100.0),
             units::bibytes1(mem.ram_used as f64),
             units::bibytes1(mem.ram_total as f64),
-            units::percent1(ram_sig.peak / mem.ram_total as f32 * 100.0),
-            units::bibytes1(ram_sig.peak),
+            peak_note(|p| format!(
+                "{} = {}",
+                units::percent1(p(ram_sig) / mem.ram_total as f32 * 100.0),
+                units::bibytes1(p(ram_sig))
+            )),
         ),
         ratio: ram_ratio,
-        spark: ram_sig.as_slice().to_vec(),
+        role: gauge_role(thresholds.memory, ram_ratio * 100.0),
+        spark: ram_sig.points_in(window).into_owned(),
+        spark_span: ram_sig.spans_in(window),
+        spark_roles: spark_roles(
+            thresholds.memory,
+            &ram_sig.peaks_in(window),
+            100.0 / mem.ram_total as f64,
+        ),
         spark_max: (SPARKLINE_MAX_OVERSHOOT * ram_sig.max) as u64,
+        unit: SparkUnit::Bytes,
     };
 
     let swap_sig = history.get_or_default(&MetricKey::SwapUsageBytes);
     let swap_ratio = mem.swap_usage_ratio();
     let swap = Meter {
         title: format!(
-            "SWAP: {} = {} / {} (peak: {})",
+            "SWAP: {} = {} / {} {}",
             units::percent1(swap_ratio * 100.0),
             units::bibytes1(mem.swap_used as f64),
             units::bibytes1(mem.swap_total as f64),
-            units::bibytes1(swap_sig.peak),
+            peak_note(|p| units::bibytes1(p(swap_sig))),
         ),
         ratio: swap_ratio,
-        spark: swap_sig.as_slice().to_vec(),
+        role: gauge_role(thresholds.swap, swap_ratio * 100.0),
+        spark: swap_sig.points_in(window).into_owned(),
+        spark_span: swap_sig.spans_in(window),
+        // An empty swap (total 0) never reaches a level.
+        spark_roles: spark_roles(
+            thresholds.swap,
+            &swap_sig.peaks_in(window),
+            100.0 / mem.swap_total.max(1) as f64,
+        ),
         spark_max: (SPARKLINE_MAX_OVERSHOOT * swap_sig.max) as u64,
+        unit: SparkUnit::Bytes,
     };
 
     OverviewFrame {
@@ -425,37 +775,58 @@ fn build_overview(metrics: &Metrics, soc: &SocInfo, history: &History) -> Overvi
     }
 }
 
+/// `(peak: …, session: …)`: what `fmt` makes of the signals' recent peaks,
+/// then of their session peaks. `fmt` reads a peak with the selector it is
+/// given.
+fn peak_note(fmt: impl Fn(fn(&Signal<f32>) -> f32) -> String) -> String {
+    format!(
+        "(peak: {}, session: {})",
+        fmt(Signal::recent_peak),
+        fmt(|s| s.peak)
+    )
+}
+
 /// Build an Overview cluster meter (full-history sparkline, 1.05 overshoot).
-fn cluster_meter(cluster: &ClusterMetrics, id: ClusterId, history: &History) -> Meter {
+fn cluster_meter(
+    cluster: &ClusterMetrics,
+    id: ClusterId,
+    history: &History,
+    levels: Levels,
+    window: Window,
+) -> Meter {
     let sig = history.get_or_default(&MetricKey::ClusterActivePercent(id));
     Meter {
         title: format!(
-            "{}: {} @ {} (peak: {})",
+            "{}: {} @ {} {}",
             cluster.name,
             units::percent1(cluster.active_ratio() * 100.0),
             units::mhz(cluster.freq_mhz),
-            units::percent1(sig.peak)
+            peak_note(|p| units::percent1(p(sig)))
         ),
         ratio: cluster.active_ratio() as f64,
-        spark: sig.as_slice().to_vec(),
+        role: gauge_role(levels, cluster.active_ratio() as f64 * 100.0),
+        spark: sig.points_in(window).into_owned(),
+        spark_span: sig.spans_in(window),
+        spark_roles: spark_roles(levels, &sig.peaks_in(window), 1.0),
         spark_max: (SPARKLINE_MAX_OVERSHOOT * sig.max) as u64,
+        unit: SparkUnit::Percent,
     }
 }
 
 fn build_thermals(metrics: &Metrics) -> Thermals {
     Thermals {
         pressure: metrics.thermal_pressure.clone(),
-        is_nominal: metrics.thermal_pressure == "Nominal",
+        role: pressure_role(&metrics.thermal_pressure),
     }
 }
 
-fn build_cpu(metrics: &Metrics, history: &History) -> CpuFrame {
+fn build_cpu(metrics: &Metrics, history: &History, levels: Levels, window: Window) -> CpuFrame {
     let clusters = metrics
         .e_clusters
         .iter()
         .chain(metrics.p_clusters.iter())
         .chain(metrics.s_clusters.iter())
-        .map(|c| cpu_cluster(c, history))
+        .map(|c| cpu_cluster(c, history, levels, window))
         .collect();
 
     CpuFrame {
@@ -464,27 +835,67 @@ fn build_cpu(metrics: &Metrics, history: &History) -> CpuFrame {
     }
 }
 
-fn cpu_cluster(cluster: &ClusterMetrics, history: &History) -> CpuCluster {
+fn cpu_cluster(
+    cluster: &ClusterMetrics,
+    history: &History,
+    levels: Levels,
+    window: Window,
+) -> CpuCluster {
+    // `E-Cluster` → `E`, `P1-Cluster` → `P1`: the heatmap's group label.
+    let short = cluster
+        .name
+        .strip_suffix("-Cluster")
+        .unwrap_or(&cluster.name);
     CpuCluster {
         title: format!(" {}: ", cluster.name),
-        cpus: cluster.cpus.iter().map(|c| cpu_row(c, history)).collect(),
+        cpus: cluster
+            .cpus
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let group = if i == 0 { short } else { "" };
+                cpu_row(c, group, history, levels, window)
+            })
+            .collect(),
     }
 }
 
-fn cpu_row(cpu: &CpuMetrics, history: &History) -> CpuRow {
+/// Saturate percent samples into bytes.
+fn percent_bytes(points: &[u64]) -> Vec<u8> {
+    points
+        .iter()
+        .map(|&p| u8::try_from(p).unwrap_or(u8::MAX))
+        .collect()
+}
+
+fn cpu_row(
+    cpu: &CpuMetrics,
+    group: &str,
+    history: &History,
+    levels: Levels,
+    window: Window,
+) -> CpuRow {
     let act = history.get_or_default(&MetricKey::CpuActivePercent(cpu.id));
     let freq = history.get_or_default(&MetricKey::CpuFreqPercent(cpu.id));
+    let cells = HISTORY_LENGTH * window.per_col;
+    let act_spark = act.as_slice_last_n(cells);
     CpuRow {
         id_label: format!("{:2} -", cpu.id),
         act_ratio: cpu.active_ratio,
+        act_role: gauge_role(levels, cpu.active_ratio * 100.0),
         act_label: format!("{:.1}%", cpu.active_ratio * 100.0),
-        act_spark: act.as_slice_last_n(HISTORY_LENGTH).to_vec(),
+        act_spark: act_spark.to_vec(),
+        act_spark_roles: spark_roles(levels, act_spark, 1.0),
         act_spark_max: (SPARKLINE_MAX_OVERSHOOT * act.max) as u64,
         freq_value: units::mhz(cpu.freq_mhz),
         freq_ratio: cpu.freq_ratio(),
         freq_label: format!("{:3.0}%", cpu.freq_ratio() * 100.0),
-        freq_spark: freq.as_slice_last_n(HISTORY_LENGTH).to_vec(),
+        freq_spark: freq.as_slice_last_n(cells).to_vec(),
         freq_spark_max: (SPARKLINE_MAX_OVERSHOOT * freq.max) as u64,
+        heat_label: format!("{group:<3}{:>3}", cpu.id),
+        act_history: percent_bytes(&act.points_in(window)),
+        act_history_roles: spark_roles(levels, &act.peaks_in(window), 1.0),
+        freq_history: percent_bytes(&freq.points_in(window)),
     }
 }
 
@@ -525,11 +936,12 @@ fn first_cluster_freqs(clusters: &[ClusterMetrics]) -> String {
         .join(" ")
 }
 
-fn build_gpu(metrics: &Metrics, history: &History) -> GpuFrame {
+fn build_gpu(metrics: &Metrics, history: &History, levels: Levels, window: Window) -> GpuFrame {
     let gpu = &metrics.gpu;
     let act = history.get_or_default(&MetricKey::GpuActivePercent);
     let freq = history.get_or_default(&MetricKey::GpuFreqPercent);
     let pow = history.get_or_default(&MetricKey::GpuPowerW);
+    let pow_mw = history.get_or_default(&MetricKey::PowerMilliwatts(PowerDomain::Gpu));
 
     let gpu_freqs = gpu
         .frequencies_mhz()
@@ -548,21 +960,30 @@ fn build_gpu(metrics: &Metrics, history: &History) -> GpuFrame {
         ],
     };
 
+    let cells = HISTORY_LENGTH * window.per_col;
+    let act_spark = act.as_slice_last_n(cells);
     GpuFrame {
         act_ratio: gpu.active_ratio,
+        act_role: gauge_role(levels, gpu.active_ratio * 100.0),
         act_label: format!("{:.1}%", gpu.active_ratio * 100.0),
-        act_spark: act.as_slice_last_n(HISTORY_LENGTH).to_vec(),
+        act_spark: act_spark.to_vec(),
+        act_spark_roles: spark_roles(levels, act_spark, 1.0),
         act_spark_max: (SPARKLINE_MAX_OVERSHOOT * act.max) as u64,
         freq_value: units::mhz(gpu.freq_mhz),
         freq_ratio: gpu.freq_ratio(),
         freq_label: format!("{:3.0}%", gpu.freq_ratio() * 100.0),
-        freq_spark: freq.as_slice_last_n(HISTORY_LENGTH).to_vec(),
+        freq_spark: freq.as_slice_last_n(cells).to_vec(),
         freq_spark_max: (SPARKLINE_MAX_OVERSHOOT * freq.max) as u64,
         power_value: units::watts2(metrics.consumption.gpu_w),
-        power_spark: pow.as_slice_last_n(HISTORY_LENGTH).to_vec(),
-        power_spark_max: (SPARKLINE_MAX_OVERSHOOT * pow.max) as u64,
+        power_spark: pow_mw.as_slice_last_n(cells).to_vec(),
+        power_spark_max: (SPARKLINE_MAX_OVERSHOOT * 1e3 * pow.max) as u64,
+        act_history: act.points_in(window).into_owned(),
+        act_history_roles: spark_roles(levels, &act.peaks_in(window), 1.0),
+        freq_history: freq.points_in(window).into_owned(),
         peak_text: format!(
-            "Peak: {} | {}",
+            "Peak: {} | {} (session: {} | {})",
+            units::percent1(act.recent_peak()),
+            units::watts2(pow.recent_peak()),
             units::percent1(act.peak),
             units::watts2(pow.peak)
         ),
@@ -571,11 +992,122 @@ fn build_gpu(metrics: &Metrics, history: &History) -> GpuFrame {
     }
 }
 
+/// Build the Power tab: the domain histories stacked bottom-up, the SoC
+/// ceilings as reference lines, and current / average / peak / energy per
+/// domain plus the package total. Averages are over the session so far (the
+/// energy's start to `elapsed`); the chart covers `window`.
+fn build_power(
+    metrics: &Metrics,
+    soc: &SocInfo,
+    history: &History,
+    energy: &Energy,
+    (elapsed, window): (Duration, Window),
+) -> PowerFrame {
+    let secs = elapsed
+        .saturating_sub(energy.start())
+        .as_secs_f64()
+        .max(f64::EPSILON);
+    let layers: Vec<PowerLayer> = PowerDomain::ALL
+        .into_iter()
+        .map(|domain| PowerLayer {
+            role: Power(domain),
+            history: history
+                .get_or_default(&MetricKey::PowerMilliwatts(domain))
+                .points_in(window)
+                .into_owned(),
+        })
+        .collect();
+
+    // All domain signals are pushed together, so their samples line up.
+    let len = layers.iter().map(|l| l.history.len()).max().unwrap_or(0);
+    let stacked_peak = (0..len)
+        .map(|i| layers.iter().filter_map(|l| l.history.get(i)).sum::<u64>())
+        .max()
+        .unwrap_or(0);
+
+    let mw = |w: f64| (w * 1e3) as u64;
+    let auto = soc.calibrated;
+    let ceilings = vec![
+        (
+            PowerDomain::Ane.label(),
+            soc.max_ane_w,
+            Power(PowerDomain::Ane),
+            auto.ane,
+        ),
+        (
+            PowerDomain::Cpu.label(),
+            soc.max_cpu_w,
+            Power(PowerDomain::Cpu),
+            auto.cpu,
+        ),
+        (
+            PowerDomain::Gpu.label(),
+            soc.max_gpu_w,
+            Power(PowerDomain::Gpu),
+            auto.gpu,
+        ),
+        ("Package", soc.max_package_w, Def, auto.any()),
+    ]
+    .into_iter()
+    .map(|(name, watts, role, calibrated)| PowerCeiling {
+        label: format!(
+            "{name} max {}{}",
+            units::watts(watts),
+            auto_mark(calibrated)
+        ),
+        value: mw(watts),
+        role,
+    })
+    .collect::<Vec<_>>();
+    let chart_max = ceilings
+        .iter()
+        .map(|c| c.value)
+        .filter(|&v| v >= stacked_peak)
+        .min()
+        .unwrap_or(stacked_peak)
+        .max(1);
+
+    let mut rows: Vec<PowerRow> = PowerDomain::ALL
+        .into_iter()
+        .map(|domain| {
+            let joules = energy.joules(domain);
+            let peak = history
+                .get_or_default(&MetricKey::PowerMilliwatts(domain))
+                .peak;
+            PowerRow {
+                label: domain.label().to_string(),
+                role: Power(domain),
+                current: units::watts2(metrics.consumption.domain_w(domain)),
+                average: units::watts2(joules / secs),
+                peak: units::watts2(peak / 1e3),
+                energy: units::joules1(joules),
+            }
+        })
+        .collect();
+    rows.push(PowerRow {
+        label: "Package".to_string(),
+        role: Def,
+        current: units::watts2(metrics.consumption.package_w),
+        average: units::watts2(energy.total() / secs),
+        peak: units::watts2(history.get_or_default(&MetricKey::PackagePowerW).peak),
+        energy: units::joules1(energy.total()),
+    });
+
+    PowerFrame {
+        layers,
+        chart_max,
+        unit: SparkUnit::Milliwatts,
+        ceilings,
+        rows,
+    }
+}
+
 /// Build the Memory tab lines. `vm_stat` is collected here, not on the UI
 /// thread.
 fn build_memory(metrics: &Metrics) -> MemoryFrame {
-    let vm_lines = match VmStats::collect() {
-        Ok(vm) => {
+    let mem = &metrics.memory;
+    let vm_lines = match &mem.vm_stats {
+        Some(vm) => {
             let page_to_gb =
                 |pages: u64| (pages * vm.page_size) as f64 / (1024.0 * 1024.0 * 1024.0);
             let total_gb = vm.total_memory() as f64 / (1024.0 * 1024.0 * 1024.0);
@@ -625,13 +1157,15 @@ fn build_memory(metrics: &Metrics) -> MemoryFrame {
                 ]),
             ]
         }
-        Err(_) => vec![
-            line(vec![span("Failed to collect VM statistics", Def)]),
-            line(vec![span("vm_stat command may not be available", Def)]),
+        None => vec![
+            line(vec![
+                span("vm_stat degraded: ", Warning),
+                span(mem.vm_stat_error.as_deref().unwrap_or("not run"), Def),
+            ]),
+            line(vec![span("RAM Used falls back to sysinfo", Def)]),
         ],
     };
 
-    let mem = &metrics.memory;
     let sysinfo_lines = vec![
         line(vec![
             span("RAM Used: ", Accent),
@@ -659,13 +1193,20 @@ fn build_memory(metrics: &Metrics) -> MemoryFrame {
             ),
         ]),
         blank(),
-        line(vec![
-            span("Note: ", HistoryFg),
-            span(
-                "RAM Used now uses vm_stat for Activity Monitor compatibility",
-                Def,
-            ),
-        ]),
+        if mem.vm_stats.is_some() {
+            line(vec![
+                span("Note: ", HistoryFg),
+                span(
+                    "RAM Used now uses vm_stat for Activity Monitor compatibility",
+                    Def,
+                ),
+            ])
+        } else {
+            line(vec![
+                span("Note: ", Warning),
+                span("RAM Used comes from sysinfo, vm_stat is degraded", Def),
+            ])
+        },
     ];
 
     MemoryFrame {
@@ -690,25 +1231,54 @@ fn blank() -> MemLine {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::config::TimeRange;
+    use crate::modules::soc::{CeilingSource, Domains, PerfLevel};
 
-    /// A synthetic SoC for deterministic Frame/JSON tests (no live `sysctl`).
-    fn test_soc() -> SocInfo {
+    /// A synthetic SoC for deterministic Frame/JSON tests (no live `sysctl`),
+    /// shared by every module's tests.
+    pub(crate) fn test_soc() -> SocInfo {
         SocInfo {
             cpu_brand_name: "Apple M1".into(),
+            model: "MacBookAir10,1".into(),
+            os_version: "15.1".into(),
+            os_build: "24B83".into(),
+            boot_time_s: 1_792_300_000,
+            memory_bytes: 16 << 30,
+            num_dies: 1,
             num_cpu_cores: 8,
             num_efficiency_cores: 4,
             num_performance_cores: 4,
+            num_super_cores: 0,
+            perf_levels: vec![
+                PerfLevel {
+                    name: "Performance".into(),
+                    cores: 4,
+                    l1i_bytes: 192 << 10,
+                    l1d_bytes: 128 << 10,
+                    l2_bytes: 12 << 20,
+                },
+                PerfLevel {
+                    name: "Efficiency".into(),
+                    cores: 4,
+                    l1i_bytes: 128 << 10,
+                    l1d_bytes: 64 << 10,
+                    l2_bytes: 4 << 20,
+                },
+            ],
             num_gpu_cores: 8,
             max_cpu_w: 20.0,
             max_gpu_w: 20.0,
             max_ane_w: 8.0,
             max_package_w: 48.0,
+            ceiling_source: CeilingSource::Builtin,
+            pinned: Domains::default(),
+            calibrated: Domains::default(),
         }
     }
 
-    fn m1_metrics() -> Metrics {
+    pub(crate) fn m1_metrics() -> Metrics {
         let content = std::fs::read_to_string("./tests/data/powermetrics-output-m1.xml")
             .expect("read m1 fixture");
         Metrics::from_bytes(content.as_bytes()).expect("parse m1 fixture")
@@ -721,9 +1291,25 @@ mod tests {
         let soc = test_soc();
         let metrics = m1_metrics();
         let mut history: History = HashMap::new();
-        update_history(&mut history, &soc, 128, &metrics);
+        update_history(&mut history, &soc, 128, &metrics, Duration::ZERO);
+
+        let frame = build_frame(
+            &metrics,
+            &soc,
+            (
+                &history,
+                &Timeline::with_capacity(0),
+                &Energy::starting_at(Duration::ZERO),
+            ),
+            &Thresholds::default(),
+            (
+                Duration::ZERO,
+                Duration::from_secs(3_600),
+                TimeRange::Recent.into(),
+            ),
+        );
 
-        let frame = build_frame(&metrics, &soc, &history);
+        assert_eq!(frame.uptime, "1h 0m");
 
         // Border title for the CPU Clusters panel.
         assert!(
@@ -745,14 +1331,13 @@ mod tests {
             "overshoot must lift the ceiling above max"
         );
 
-        // Package uses NO overshoot: spark_max == signal.max == max_package_w.
-        assert_eq!(frame.overview.package.spark_max, soc.max_package_w as u64);
+        // Package uses NO overshoot: spark_max == signal.max == max_package_w,
+        // in mW.
+        let package_mw = 1e3 * soc.max_package_w as f32;
+        assert_eq!(frame.overview.package.spark_max, package_mw as u64);
         // And that is strictly below the overshoot value every other meter uses,
         // proving the Package exception bites.
-        assert!(
-            frame.overview.package.spark_max
-                < (SPARKLINE_MAX_OVERSHOOT * soc.max_package_w as f32) as u64
-        );
+        assert!(frame.overview.package.spark_max < (SPARKLINE_MAX_OVERSHOOT * package_mw) as u64);
 
         // The freq line-gauge default label is built: MHz value + "{:3.0}%".
         let cpu0 = &frame.cpu.clusters[0].cpus[0];
@@ -768,6 +1353,116 @@ mod tests {
         );
     }
 
+    /// The Power tab stacks the four domains and scales to the lowest SoC
+    /// ceiling above their peak (the ANE's, for an idle M1).
+    #[test]
+    fn power_frame_scales_to_the_next_ceiling() {
+        let soc = test_soc();
+        let metrics = m1_metrics();
+        let mut history: History = HashMap::new();
+        update_history(&mut history, &soc, 128, &metrics, Duration::ZERO);
+        let mut energy = Energy::starting_at(Duration::ZERO);
+        energy.push(Duration::from_secs(2), |d| metrics.consumption.domain_w(d));
+
+        let secs = (Duration::from_secs(2), TimeRange::Recent.into());
+        let power = build_power(&metrics, &soc, &history, &energy, secs);
+        assert_eq!(power.layers.len(), 4);
+        assert_eq!(power.chart_max, 8000);
+        assert_eq!(power.ceilings[0].label, "ANE max 8 W");
+        let labels: Vec<&str> = power.rows.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, ["CPU", "GPU", "ANE", "Other", "Package"]);
+        // Averages over the only sample equal the current power.
+        assert_eq!(power.rows[0].average, power.rows[0].current);
+    }
+
+    #[test]
+    fn raised_ceilings_rescale_power_and_are_marked() {
+        let mut soc = test_soc();
+        let metrics = m1_metrics();
+        let mut history: History = HashMap::new();
+        update_history(&mut history, &soc, 128, &metrics, Duration::ZERO);
+
+        assert!(soc.raise_ceilings((20.0, 20.0, 10.0)));
+        rescale_power(&mut history, &soc);
+        let ane = history.get_or_default(&MetricKey::AnePowerW);
+        assert_eq!(ane.max, 10.0);
+        let stacked = history.get_or_default(&MetricKey::PowerMilliwatts(PowerDomain::Cpu));
+        assert_eq!(stacked.max, 50_000.0);
+
+        let overview = build_overview(
+            &metrics,
+            &soc,
+            &history,
+            &Thresholds::default(),
+            TimeRange::Recent.into(),
+        );
+        assert!(
+            overview.ane.title.starts_with("ANE (auto): "),
+            "{}",
+            overview.ane.title
+        );
+        assert!(overview.package.title.starts_with("CPU+GPU+ANE (auto): "));
+        let energy = Energy::starting_at(Duration::ZERO);
+        let secs = (Duration::from_secs(1), TimeRange::Recent.into());
+        let power = build_power(&metrics, &soc, &history, &energy, secs);
+        let labels: Vec<&str> = power.ceilings.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "ANE max 10 W (auto)",
+                "CPU max 20 W",
+                "GPU max 20 W",
+                "Package max 50 W (auto)"
+            ]
+        );
+
+        // The SoC tab's rows follow the raised ceilings from frame to frame.
+        let frame = build_frame(
+            &metrics,
+            &soc,
+            (&history, &Timeline::with_capacity(0), &energy),
+            &Thresholds::default(),
+            (Duration::ZERO, Duration::ZERO, TimeRange::Recent.into()),
+        );
+        assert_eq!(
+            frame.soc_ceilings[2],
+            ("Max ANE power:".to_string(), "10 W".to_string())
+        );
+    }
+
+    /// The Memory tab breaks the `vm_stat` statistics down, or says it is
+    /// degraded, and why.
+    #[test]
+    fn memory_frame_reports_degraded_vm_stat() {
+        let text = |lines: &[MemLine]| {
+            lines
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.text.as_str()).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        let mut metrics = m1_metrics();
+        metrics.memory.vm_stats = Some(crate::modules::vm_stat::VmStats {
+            page_size: 16384,
+            pages_anonymous: 65_536,
+            ..Default::default()
+        });
+        let frame = build_memory(&metrics);
+        assert!(text(&frame.vm_lines).contains(&"App Memory (Anonymous): 1.00 GB".to_string()));
+
+        metrics.memory.vm_stats = None;
+        metrics.memory.vm_stat_error = Some("vm_stat output lacks `Pages free`".into());
+        let frame = build_memory(&metrics);
+        assert_eq!(
+            text(&frame.vm_lines)[0],
+            "vm_stat degraded: vm_stat output lacks `Pages free`"
+        );
+        assert!(matches!(frame.vm_lines[0].spans[0].role, Warning));
+        assert_eq!(
+            text(&frame.sysinfo_lines).last().unwrap(),
+            "Note: RAM Used comes from sysinfo, vm_stat is degraded"
+        );
+    }
+
     /// `run --json` output is byte-identical to a committed golden line. Guards
     /// the JSON serialization (field set/format) against drift. Uses the raw
     /// powermetrics metrics (no live sysinfo merge) for determinism.
@@ -775,7 +1470,10 @@ mod tests {
     fn json_export_line_matches_golden() {
         let soc = test_soc();
         let metrics = m1_metrics();
-        let actual = export_line(&soc, &metrics);
+        let mut history: History = HashMap::new();
+        update_history(&mut history, &soc, 1, &metrics, Duration::ZERO);
+        let uptime = Duration::from_secs(93_784);
+        let actual = export_line(&soc, uptime, &metrics, &history, Duration::from_secs(60));
 
         let path = format!(
             "{}/tests/snapshots/json_export_m1.golden",
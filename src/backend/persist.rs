@@ -0,0 +1,219 @@
+//! `--history-file`: the collector's [`History`] saved on exit and reloaded on
+//! the next start.
+//!
+//! The state file is JSON: the [`SocInfo`] it was recorded on, every signal
+//! (raw points, peaks and rollup tiers), the [`Timeline`], and the collector's
+//! clock together with the wall-clock time it was saved. A file recorded on
+//! another SoC, or in another format version, is ignored and the session
+//! starts fresh. Only the chip counts (brand and core counts), not what may
+//! change between sessions: power ceilings (flags, learned peaks), OS
+//! version, boot time.
+//!
+//! A restored session resumes the clock where it stopped plus the time pumas
+//! was not running, so the restored samples keep their true age and the
+//! downtime shows up as a gap in the rollups.
+//!
+//! Saving writes a temporary file next to the state file and renames it over
+//! it, so an interrupted save leaves the previous state intact. Under `sudo`,
+//! the file goes to the invoking user (see [`ownership`]).
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use serde::Deserialize;
+
+use super::history::{History, Signal, Timeline};
+use crate::{Result, error::Error, metric_key::MetricKey, modules::soc::SocInfo, ownership};
+
+/// State file format version.
+const VERSION: u32 = 1;
+
+/// A state file, as read back.
+#[derive(Deserialize)]
+struct State {
+    saved_at: SystemTime,
+    elapsed: Duration,
+    signals: Vec<(MetricKey, Signal<f32>)>,
+    timeline: Timeline,
+}
+
+/// History restored from a state file.
+pub(crate) struct Restored {
+    pub(crate) history: History,
+    pub(crate) timeline: Timeline,
+    /// Collector clock to resume from.
+    pub(crate) elapsed: Duration,
+}
+
+fn history_error(path: &Path, err: impl std::fmt::Display) -> Error {
+    Error::HistoryFile(format!("{}: {err}", path.display()))
+}
+
+/// The [`SocInfo`] fields naming the chip.
+const CHIP: [&str; 5] = [
+    "cpu_brand_name",
+    "num_cpu_cores",
+    "num_efficiency_cores",
+    "num_performance_cores",
+    "num_gpu_cores",
+];
+
+/// The chip a serialized [`SocInfo`] describes.
+fn chip(soc: &serde_json::Value) -> Vec<&serde_json::Value> {
+    CHIP.iter().map(|name| &soc[name]).collect()
+}
+
+/// Save `history` and `timeline`, recorded on `soc`, at collector clock
+/// `elapsed`.
+pub(crate) fn save(
+    path: &Path,
+    soc: &SocInfo,
+    history: &History,
+    timeline: &Timeline,
+    elapsed: Duration,
+) -> Result<()> {
+    save_at(path, soc, (history, timeline), elapsed, SystemTime::now())
+}
+
+fn save_at(
+    path: &Path,
+    soc: &SocInfo,
+    (history, timeline): (&History, &Timeline),
+    elapsed: Duration,
+    now: SystemTime,
+) -> Result<()> {
+    let signals: Vec<_> = history.iter().collect();
+    let state = serde_json::json!({
+        "version": VERSION,
+        "soc": soc,
+        "saved_at": now,
+        "elapsed": elapsed,
+        "signals": signals,
+        "timeline": timeline,
+    });
+    let created = ownership::create_parent(path)?;
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, state.to_string())?;
+    ownership::hand_over(path, &tmp, &created)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// The history saved at `path`, if there is one and it was recorded on `soc`.
+pub(crate) fn load(path: &Path, soc: &SocInfo) -> Result<Option<Restored>> {
+    load_at(path, soc, SystemTime::now())
+}
+
+fn load_at(path: &Path, soc: &SocInfo, now: SystemTime) -> Result<Option<Restored>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(history_error(path, e)),
+    };
+    let state: serde_json::Value =
+        serde_json::from_str(&text).map_err(|e| history_error(path, e))?;
+    let soc = serde_json::to_value(soc).map_err(|e| history_error(path, e))?;
+    if state["version"] != VERSION || chip(&state["soc"]) != chip(&soc) {
+        return Ok(None);
+    }
+    let state = State::deserialize(state).map_err(|e| history_error(path, e))?;
+    // A clock set back since the save counts as no downtime.
+    let downtime = now.duration_since(state.saved_at).unwrap_or_default();
+    Ok(Some(Restored {
+        history: state.signals.into_iter().collect(),
+        timeline: state.timeline,
+        elapsed: state.elapsed + downtime,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf, time::UNIX_EPOCH};
+
+    use super::*;
+    use crate::{backend::tests::test_soc, config::TimeRange, metric_key::PowerDomain};
+
+    /// A state file path unique to this test process and `name`.
+    fn state_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("pumas-{}-{name}.json", std::process::id()))
+    }
+
+    fn recorded() -> (History, Timeline) {
+        let key = MetricKey::PowerMilliwatts(PowerDomain::Cpu);
+        let mut signal = Signal::with_capacity(4, 20_000.0);
+        let mut timeline = Timeline::with_capacity(4);
+        for (secs, mw) in [(0, 1000.0), (5, 3000.0), (10, 2000.0)] {
+            signal.push_at(mw, Duration::from_secs(secs));
+            timeline.push(Duration::from_secs(secs));
+        }
+        (HashMap::from([(key, signal)]), timeline)
+    }
+
+    #[test]
+    fn history_round_trips_and_resumes_the_clock() {
+        let path = state_path("round-trip");
+        let (history, timeline) = recorded();
+        let saved_at = UNIX_EPOCH + Duration::from_secs(1_792_333_530);
+        save_at(
+            &path,
+            &test_soc(),
+            (&history, &timeline),
+            Duration::from_secs(10),
+            saved_at,
+        )
+        .unwrap();
+
+        let later = saved_at + Duration::from_secs(60);
+        let restored = load_at(&path, &test_soc(), later)
+            .unwrap()
+            .expect("restored");
+        let _ = fs::remove_file(&path);
+        assert_eq!(restored.elapsed, Duration::from_secs(70));
+        let signal = &restored.history[&MetricKey::PowerMilliwatts(PowerDomain::Cpu)];
+        assert_eq!(signal.as_slice(), [1000, 3000, 2000]);
+        assert_eq!(signal.peak, 3000.0);
+        assert_eq!(&*signal.points_in(TimeRange::Hour), &[2000, 2000]);
+        assert_eq!(&*signal.peaks_in(TimeRange::Hour), &[3000, 2000]);
+        assert_eq!(restored.timeline.to_vec(), timeline.to_vec());
+        assert_eq!(
+            restored.timeline.in_range(TimeRange::Hour),
+            timeline.in_range(TimeRange::Hour)
+        );
+    }
+
+    #[test]
+    fn other_socs_and_missing_files_start_fresh() {
+        let path = state_path("other-soc");
+        assert!(load(&path, &test_soc()).unwrap().is_none());
+
+        let (history, timeline) = recorded();
+        save(
+            &path,
+            &test_soc(),
+            &history,
+            &timeline,
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        let other = SocInfo {
+            cpu_brand_name: "Apple M2".into(),
+            ..test_soc()
+        };
+        assert!(load(&path, &other).unwrap().is_none());
+        // Raised ceilings are still the same chip.
+        let raised = SocInfo {
+            max_cpu_w: 30.0,
+            ..test_soc()
+        };
+        assert!(load(&path, &raised).unwrap().is_some());
+
+        fs::write(&path, "{").unwrap();
+        let err = load(&path, &test_soc());
+        let _ = fs::remove_file(&path);
+        assert!(matches!(err, Err(Error::HistoryFile(_))));
+    }
+}
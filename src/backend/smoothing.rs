@@ -0,0 +1,175 @@
+//! Display smoothing: each sample's values averaged over the recent samples
+//! before the frame builder turns them into gauges and labels.
+//!
+//! The [`Smoother`] hands the frame builder a smoothed copy of each sample's
+//! [`Metrics`]. The history, the energy totals and `run --json` keep using the
+//! raw sample, so the charts and exports are unaffected. Values are visited in
+//! a fixed order (clusters and their cores, the GPU, then power), the same
+//! from one sample to the next on a given machine, so each value's filter is
+//! simply its position in that order.
+
+use std::collections::VecDeque;
+
+use crate::{
+    config::{MetricSmoothing, Smoothing},
+    metrics::Metrics,
+};
+
+/// Smoothing state of one value.
+#[derive(Default)]
+struct Filter {
+    /// Current exponentially weighted average.
+    average: Option<f64>,
+    /// Last samples, newest last, for a moving mean.
+    window: VecDeque<f64>,
+}
+
+impl Filter {
+    /// Add `value` and return its smoothed counterpart.
+    fn apply(&mut self, how: Smoothing, value: f64) -> f64 {
+        match how {
+            Smoothing::None => value,
+            Smoothing::Ewma(alpha) => {
+                let average = self
+                    .average
+                    .map_or(value, |avg| avg + alpha * (value - avg));
+                self.average = Some(average);
+                average
+            }
+            Smoothing::Mean(n) => {
+                self.window.push_back(value);
+                while self.window.len() > n {
+                    self.window.pop_front();
+                }
+                self.window.iter().sum::<f64>() / self.window.len() as f64
+            }
+        }
+    }
+}
+
+/// The filters of one sample's values, visited in order.
+struct Cursor<'a> {
+    filters: &'a mut Vec<Filter>,
+    next: usize,
+}
+
+impl Cursor<'_> {
+    fn smooth(&mut self, how: Smoothing, value: &mut f64) {
+        if self.next == self.filters.len() {
+            self.filters.push(Filter::default());
+        }
+        *value = self.filters[self.next].apply(how, *value);
+        self.next += 1;
+    }
+
+    fn smooth_f32(&mut self, how: Smoothing, value: &mut f32) {
+        let mut wide = f64::from(*value);
+        self.smooth(how, &mut wide);
+        *value = wide as f32;
+    }
+}
+
+/// Smooths the displayed values of successive samples.
+pub(crate) struct Smoother {
+    smoothing: MetricSmoothing,
+    filters: Vec<Filter>,
+}
+
+impl Smoother {
+    pub(crate) fn new(smoothing: MetricSmoothing) -> Self {
+        Self {
+            smoothing,
+            filters: Vec::new(),
+        }
+    }
+
+    /// `metrics` as displayed: every smoothed value replaced by its average
+    /// over the recent samples.
+    pub(crate) fn smooth(&mut self, metrics: &Metrics) -> Metrics {
+        let mut shown = metrics.clone();
+        let s = self.smoothing;
+        let mut values = Cursor {
+            filters: &mut self.filters,
+            next: 0,
+        };
+
+        // A cluster's activity is the mean of its cores', so it follows them.
+        let clusters = shown
+            .e_clusters
+            .iter_mut()
+            .chain(&mut shown.p_clusters)
+            .chain(&mut shown.s_clusters);
+        for cluster in clusters {
+            values.smooth(s.cpu_mhz, &mut cluster.freq_mhz);
+            for core in &mut cluster.cpus {
+                values.smooth(s.cpu_pct, &mut core.active_ratio);
+                values.smooth(s.cpu_mhz, &mut core.freq_mhz);
+            }
+        }
+
+        values.smooth(s.gpu_pct, &mut shown.gpu.active_ratio);
+        values.smooth(s.gpu_mhz, &mut shown.gpu.freq_mhz);
+
+        let watts = &mut shown.consumption;
+        for (how, value) in [
+            (s.cpu_w, &mut watts.cpu_w),
+            (s.gpu_w, &mut watts.gpu_w),
+            (s.ane_w, &mut watts.ane_w),
+            (s.package_w, &mut watts.package_w),
+        ] {
+            values.smooth_f32(how, value);
+        }
+        shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::tests::m1_metrics;
+
+    #[test]
+    fn ewma_and_mean_filters() {
+        let mut ewma = Filter::default();
+        let smoothed: Vec<f64> = [10.0, 20.0, 20.0]
+            .into_iter()
+            .map(|v| ewma.apply(Smoothing::Ewma(0.5), v))
+            .collect();
+        assert_eq!(smoothed, [10.0, 15.0, 17.5]);
+
+        let mut mean = Filter::default();
+        let smoothed: Vec<f64> = [3.0, 6.0, 9.0, 30.0]
+            .into_iter()
+            .map(|v| mean.apply(Smoothing::Mean(3), v))
+            .collect();
+        assert_eq!(smoothed, [3.0, 4.5, 6.0, 15.0]);
+
+        assert_eq!(Filter::default().apply(Smoothing::None, 7.0), 7.0);
+    }
+
+    #[test]
+    fn metrics_smooth_independently() {
+        let mut smoother = Smoother::new(MetricSmoothing {
+            cpu_pct: Smoothing::Mean(2),
+            package_w: Smoothing::Ewma(0.5),
+            ..MetricSmoothing::default()
+        });
+        let first = m1_metrics();
+        let mut second = m1_metrics();
+        second.consumption.package_w += 1.0;
+        second.consumption.cpu_w += 1.0;
+        second.gpu.active_ratio += 0.5;
+        second.e_clusters[0].cpus[0].active_ratio += 0.5;
+
+        smoother.smooth(&first);
+        let shown = smoother.smooth(&second);
+        let package = f64::from(shown.consumption.package_w - first.consumption.package_w);
+        assert!((package - 0.5).abs() < 1e-6, "{package}");
+        assert_eq!(shown.consumption.cpu_w, second.consumption.cpu_w);
+        assert_eq!(shown.gpu.active_ratio, second.gpu.active_ratio);
+        let core = shown.e_clusters[0].cpus[0].active_ratio;
+        assert!((core - first.e_clusters[0].cpus[0].active_ratio - 0.25).abs() < 1e-9);
+        // The raw sample is left alone.
+        assert_eq!(second.gpu.active_ratio, first.gpu.active_ratio + 0.5);
+    }
+}
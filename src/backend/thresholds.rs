@@ -0,0 +1,71 @@
+//! Value-dependent color roles.
+//!
+//! Gauges and sparkline bars change color as a reading approaches its limit:
+//! each metric family has a warning and a critical level, in percent (see
+//! [`Thresholds`](crate::config::Thresholds)). Roles are picked here, per
+//! sample, so the frontend only maps them to theme colors.
+
+use crate::config::Levels;
+
+use super::frame::ColorRole;
+
+/// The warning or critical role of a reading, if it reaches either level.
+fn level(levels: Levels, percent: f64) -> Option<ColorRole> {
+    if percent >= levels.critical {
+        Some(ColorRole::Critical)
+    } else if percent >= levels.warning {
+        Some(ColorRole::Warning)
+    } else {
+        None
+    }
+}
+
+/// Role of a gauge filled to `percent`: gauge foreground below the levels.
+pub(crate) fn gauge_role(levels: Levels, percent: f64) -> ColorRole {
+    level(levels, percent).unwrap_or(ColorRole::GaugeFg)
+}
+
+/// Role of each sparkline sample, converted to percent by `to_percent`:
+/// history foreground below the levels.
+pub(crate) fn spark_roles(levels: Levels, samples: &[u64], to_percent: f64) -> Vec<ColorRole> {
+    samples
+        .iter()
+        .map(|&v| level(levels, v as f64 * to_percent).unwrap_or(ColorRole::HistoryFg))
+        .collect()
+}
+
+/// Role of a macOS thermal pressure level (`Nominal`, `Moderate`, `Heavy`,
+/// `Trapping`, `Sleeping`): accent when nominal, then warning, then critical.
+pub(crate) fn pressure_role(pressure: &str) -> ColorRole {
+    match pressure {
+        "Nominal" => ColorRole::Accent,
+        "Moderate" => ColorRole::Warning,
+        _ => ColorRole::Critical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Thresholds;
+
+    #[test]
+    fn roles_follow_the_levels() {
+        let swap = Thresholds::default().swap;
+        assert_eq!(gauge_role(swap, 10.0), ColorRole::GaugeFg);
+        assert_eq!(gauge_role(swap, 50.0), ColorRole::Warning);
+        assert_eq!(gauge_role(swap, 80.0), ColorRole::Critical);
+
+        // Samples in bytes of a 1000-byte swap.
+        assert_eq!(
+            spark_roles(swap, &[100, 600, 900], 100.0 / 1000.0),
+            [
+                ColorRole::HistoryFg,
+                ColorRole::Warning,
+                ColorRole::Critical
+            ]
+        );
+        assert_eq!(pressure_role("Nominal"), ColorRole::Accent);
+        assert_eq!(pressure_role("Heavy"), ColorRole::Critical);
+    }
+}
@@ -16,6 +16,24 @@ fn main() -> Result<()> {
             monitor::run(args)?;
         }
 
+        Command::Status { args } => {
+            monitor::status(args)?;
+        }
+
+        Command::Snapshot { args } => {
+            monitor::snapshot(args)?;
+        }
+
+        Command::Info { args } => {
+            monitor::info(args)?;
+        }
+
+        Command::Doctor => {
+            if !monitor::doctor()? {
+                std::process::exit(1);
+            }
+        }
+
         Command::GenerateCompletion { shell } => {
             let mut app = Config::command();
             let name = app.get_name().to_string();
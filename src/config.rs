@@ -1,7 +1,28 @@
 //! Configuration.
+//!
+//! Command-line flags are parsed by clap into [`Config`]. Settings that do not
+//! fit on a command line (key bindings, extra color roles, gauge thresholds,
+//! per-chip power ceilings) live in an optional TOML file, loaded into
+//! [`FileConfig`].
+//!
+//! Colors resolve in layers: a named built-in theme or a user theme file
+//! (`--theme`, else the file's `theme`), then the file's `[colors]` section,
+//! then the `--*-color` flags. `--monochrome` (or a non-empty `NO_COLOR`)
+//! drops them all. Power ceilings layer the same way: the built-in per-chip
+//! table, then the file's `[[ceilings]]`, then the `--max-*-w` flags.
+
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
+use serde::Deserialize;
+
+use crate::{Result, error::Error};
 
 /// Power usage monitor for Apple Silicon.
 #[derive(Debug, Parser)]
@@ -23,6 +44,37 @@ pub enum Command {
         args: RunConfig,
     },
 
+    /// Print a one-line summary per sample, for status bars.
+    Status {
+        /// Configuration
+        #[command(flatten)]
+        args: StatusConfig,
+    },
+
+    /// Render one frame of a tab after a few samples, without the interactive
+    /// UI, as text, ANSI or SVG.
+    Snapshot {
+        /// Configuration
+        #[command(flatten)]
+        args: SnapshotConfig,
+    },
+
+    /// Print the detected SoC, its power ceilings and data sources, without sudo
+    ///
+    /// Runs only the SoC discovery (`sysctl`, `system_profiler`): `powermetrics`
+    /// is not started, only checked for.
+    Info {
+        /// Configuration
+        #[command(flatten)]
+        args: InfoConfig,
+    },
+
+    /// Check the Mac, the tools pumas runs and the terminal, with fixes
+    ///
+    /// Run with sudo to also check the sudoers drop-in's contents and take one
+    /// sample. Exits with status 1 if a check fails.
+    Doctor,
+
     /// Print a shell completion script to stdout.
     GenerateCompletion {
         /// Shell for which you want completion.
@@ -47,55 +99,1083 @@ pub struct RunConfig {
     #[arg(long, default_value = "128")]
     pub history_size: usize,
 
-    /// ASCII code for labels, max: 255, default: green.
-    #[arg(long, default_value = "2")]
-    pub accent_color: u8,
+    /// Peak window [s], min=1.
+    ///
+    /// Gauge titles show the peak of this recent window next to the session
+    /// peak (reset both with `R`).
+    #[arg(long = "peak-window", default_value = "60",
+        value_parser = clap::value_parser!(u16).range(1..))]
+    pub peak_window_s: u16,
+
+    /// Smoothing of the displayed values: `none`, `ewma:<alpha>` (weight of
+    /// the newest sample, 0 < alpha <= 1) or `mean:<n>` (average of the last
+    /// n samples). Overrides the configuration file's `[smoothing]` section
+    /// for every metric; charts and `--json` stay raw.
+    #[arg(long)]
+    pub smoothing: Option<Smoothing>,
+
+    /// Color theme: `dark`, `light`, `solarized`, `high-contrast`, or the path
+    /// of a theme file. Default: `dark`.
+    #[arg(long, env = "PUMAS_THEME")]
+    pub theme: Option<String>,
+
+    /// Accent color for labels: 0~255, `#rrggbb` or `default`.
+    #[arg(long)]
+    pub accent_color: Option<UiColor>,
 
-    /// ASCII code, max=255, default: green.
-    #[arg(long, default_value = "2")]
-    pub gauge_fg_color: u8,
+    /// Gauge foreground color: 0~255, `#rrggbb` or `default`.
+    #[arg(long)]
+    pub gauge_fg_color: Option<UiColor>,
 
-    /// ASCII code, max=255, default: white.
-    #[arg(long, default_value = "7")]
-    pub gauge_bg_color: u8,
+    /// Gauge background color: 0~255, `#rrggbb` or `default`.
+    #[arg(long)]
+    pub gauge_bg_color: Option<UiColor>,
 
-    /// ASCII code, max=255, default: blue.
-    #[arg(long, default_value = "4")]
-    pub history_fg_color: u8,
+    /// History foreground color: 0~255, `#rrggbb` or `default`.
+    #[arg(long)]
+    pub history_fg_color: Option<UiColor>,
 
-    /// ASCII code, max=255, default: white.
-    #[arg(long, default_value = "7")]
-    pub history_bg_color: u8,
+    /// History background color: 0~255, `#rrggbb` or `default`.
+    #[arg(long)]
+    pub history_bg_color: Option<UiColor>,
+
+    /// History chart style on the Overview and GPU tabs: block sparklines or
+    /// braille line charts with axes (toggle with `c`).
+    #[arg(long, value_enum, default_value_t = ChartStyle::Sparkline)]
+    pub chart_style: ChartStyle,
+
+    /// CPU tab layout: a row per core with short sparklines, or a per-core
+    /// heatmap of activity or frequency over the whole history (cycle with
+    /// `m`).
+    #[arg(long, value_enum, default_value_t = CpuView::Rows)]
+    pub cpu_view: CpuView,
+
+    /// Time range of the history charts: the raw recent samples, or the last
+    /// hour, 6 hours or 24 hours from rolled-up history (cycle with `r`).
+    #[arg(long, value_enum, default_value_t = TimeRange::Recent)]
+    pub range: TimeRange,
+
+    /// Draw with ASCII characters only: no block elements, box-drawing or
+    /// braille.
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Draw without colors, marking warning and critical state in bold
+    /// instead. Also enabled by a non-empty `NO_COLOR`.
+    #[arg(long)]
+    pub monochrome: bool,
 
     /// Print metrics to stdout as JSON instead of running the UI.
     #[arg(long, default_value = "false")]
     pub json: bool,
+
+    /// Draw a compact Overview below the prompt instead of taking over the
+    /// terminal; the last frame stays in the scrollback on exit.
+    #[arg(long, conflicts_with = "json")]
+    pub inline: bool,
+
+    /// Format of the screen exports saved with `e`.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Svg)]
+    pub export_format: ExportFormat,
+
+    /// Record the session to this file as an asciicast v2 recording, for
+    /// asciinema players.
+    #[arg(long, value_name = "FILE", conflicts_with = "json")]
+    pub cast: Option<PathBuf>,
+
+    /// Save the history to this file on exit and restore it on the next start
+    /// (if recorded on the same SoC), keeping sparklines and peaks across
+    /// restarts.
+    #[arg(
+        long,
+        value_name = "FILE",
+        env = "PUMAS_HISTORY_FILE",
+        conflicts_with = "json"
+    )]
+    pub history_file: Option<PathBuf>,
+
+    /// Power ceilings over the built-in table.
+    #[command(flatten)]
+    pub ceilings: CeilingArgs,
+
+    /// Configuration file (TOML).
+    ///
+    /// Defaults to `$XDG_CONFIG_HOME/pumas/config.toml`, or
+    /// `~/.config/pumas/config.toml`. A missing default file is not an error.
+    #[arg(long, env = "PUMAS_CONFIG")]
+    pub config: Option<PathBuf>,
+}
+
+/// Status-line configuration.
+#[derive(Debug, clap::Args)]
+pub struct StatusConfig {
+    /// Update rate [ms], min=100.
+    #[arg(short='i', long="sample-rate", default_value = "1000",
+        value_parser = clap::value_parser!(u16).range(100..))]
+    pub sample_rate_ms: u16,
+
+    /// Line template: metric placeholders such as `{cpu.pct}`, `{package_w:.1}`,
+    /// `{gpu_w:unit}` or `{cpu.pct:spark}`; see the README for the full list.
+    #[arg(
+        short,
+        long,
+        default_value = "CPU {cpu.pct}% GPU {gpu.pct}% {package_w:.1}W {thermal}"
+    )]
+    pub format: String,
+
+    /// Exit after this many samples (e.g. `-n 1` for a tmux `#(…)` command).
+    #[arg(short = 'n', long)]
+    pub count: Option<usize>,
+
+    /// Print one line per sample even on a terminal, instead of rewriting a
+    /// single line in place.
+    #[arg(long)]
+    pub lines: bool,
+
+    /// Power ceilings over the built-in table.
+    #[command(flatten)]
+    pub ceilings: CeilingArgs,
+
+    /// Configuration file.
+    #[command(flatten)]
+    pub config: ConfigFileArgs,
+}
+
+/// `pumas info` configuration.
+#[derive(Debug, clap::Args)]
+pub struct InfoConfig {
+    /// Print the report as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Power ceilings over the built-in table.
+    #[command(flatten)]
+    pub ceilings: CeilingArgs,
+
+    /// Configuration file.
+    #[command(flatten)]
+    pub config: ConfigFileArgs,
+
+    /// Forget the power peaks learned for this chip, then report.
+    #[arg(long, conflicts_with = "fixed_ceilings")]
+    pub reset_ceilings: bool,
+}
+
+/// Power ceiling flags, shared by `run`, `status` and `info`.
+#[derive(Clone, Copy, Debug, Default, clap::Args)]
+pub struct CeilingArgs {
+    /// CPU power [W] of a full gauge, over the built-in table and the
+    /// configuration file's `[[ceilings]]`.
+    #[arg(long, value_name = "WATTS", value_parser = positive_watts)]
+    pub max_cpu_w: Option<f64>,
+
+    /// GPU power [W] of a full gauge.
+    #[arg(long, value_name = "WATTS", value_parser = positive_watts)]
+    pub max_gpu_w: Option<f64>,
+
+    /// ANE power [W] of a full gauge.
+    #[arg(long, value_name = "WATTS", value_parser = positive_watts)]
+    pub max_ane_w: Option<f64>,
+
+    /// Keep the power ceilings as resolved, instead of raising them to the
+    /// peaks observed in this and earlier sessions.
+    #[arg(long)]
+    pub fixed_ceilings: bool,
+}
+
+impl CeilingArgs {
+    /// Whether all three ceilings are given, leaving nothing to look up.
+    pub fn is_complete(&self) -> bool {
+        self.max_cpu_w.is_some() && self.max_gpu_w.is_some() && self.max_ane_w.is_some()
+    }
+}
+
+/// The `--config` flag of `status` and `info`, which read only the
+/// configuration file's `[[ceilings]]`.
+#[derive(Debug, clap::Args)]
+pub struct ConfigFileArgs {
+    /// Configuration file (TOML), for its `[[ceilings]]`; see `run --config`.
+    #[arg(long, env = "PUMAS_CONFIG")]
+    pub config: Option<PathBuf>,
+}
+
+impl ConfigFileArgs {
+    /// Load the configuration file, as [`RunConfig::file_config`] does.
+    pub fn file_config(&self) -> Result<FileConfig> {
+        FileConfig::locate(self.config.as_deref())
+    }
+}
+
+/// Parse a power ceiling: a finite number of watts above zero.
+fn positive_watts(s: &str) -> std::result::Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(w) if w.is_finite() && w > 0.0 => Ok(w),
+        _ => Err(format!("expected watts above 0, got `{s}`")),
+    }
+}
+
+/// Snapshot configuration: what to render, where, plus the `run` options
+/// (sample rate, theme and colors, chart style, CPU view, glyphs).
+#[derive(Debug, clap::Args)]
+pub struct SnapshotConfig {
+    /// Tab to render.
+    #[arg(long, value_enum, default_value_t = Tab::Overview)]
+    pub tab: Tab,
+
+    /// Screen width in columns, min=60.
+    #[arg(long, default_value = "120", value_parser = clap::value_parser!(u16).range(60..))]
+    pub width: u16,
+
+    /// Screen height in rows, min=12. Taller tabs are cut off, as on screen.
+    #[arg(long, default_value = "40", value_parser = clap::value_parser!(u16).range(12..))]
+    pub height: u16,
+
+    /// Samples to collect before rendering, so the charts have some history.
+    #[arg(short = 'n', long, default_value = "5",
+        value_parser = clap::value_parser!(u64).range(1..))]
+    pub samples: u64,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Text)]
+    pub format: ExportFormat,
+
+    /// Write to this file instead of stdout.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Run options
+    #[command(flatten)]
+    pub run: RunConfig,
 }
 
 impl RunConfig {
-    /// Return colors.
-    pub fn colors(&self) -> UiColors {
-        UiColors {
+    /// Load the configuration file: the `--config` path if given (which must
+    /// exist), else the default location if present, else an empty config.
+    pub fn file_config(&self) -> Result<FileConfig> {
+        FileConfig::locate(self.config.as_deref())
+    }
+
+    /// Resolve the colors: the selected theme, then the configuration file's
+    /// `[colors]`, then the color flags.
+    pub fn colors(&self, file: &FileConfig) -> Result<UiColors> {
+        let mut colors = match self.theme.as_deref().or(file.theme.as_deref()) {
+            Some(theme) => UiColors::load_theme(theme)?,
+            None => UiColors::default(),
+        };
+        colors.apply(&file.colors);
+        colors.apply(&ColorOverrides {
             accent: self.accent_color,
             gauge_fg: self.gauge_fg_color,
             gauge_bg: self.gauge_bg_color,
             history_fg: self.history_fg_color,
             history_bg: self.history_bg_color,
+            ..ColorOverrides::default()
+        });
+        Ok(colors)
+    }
+
+    /// Resolve the smoothing per metric: `--smoothing` for all of them, else
+    /// the configuration file's override for the metric, else its default.
+    pub fn smoothing(&self, file: &FileConfig) -> MetricSmoothing {
+        let overrides = &file.smoothing;
+        let default = overrides.default.unwrap_or_default();
+        let pick = |metric: Option<Smoothing>| self.smoothing.or(metric).unwrap_or(default);
+        MetricSmoothing {
+            cpu_pct: pick(overrides.cpu_pct),
+            cpu_mhz: pick(overrides.cpu_mhz),
+            gpu_pct: pick(overrides.gpu_pct),
+            gpu_mhz: pick(overrides.gpu_mhz),
+            cpu_w: pick(overrides.cpu_w),
+            gpu_w: pick(overrides.gpu_w),
+            ane_w: pick(overrides.ane_w),
+            package_w: pick(overrides.package_w),
+        }
+    }
+
+    /// Whether to draw without colors: `--monochrome`, or `no_color` (the
+    /// `NO_COLOR` environment variable) set to anything but the empty string.
+    pub fn is_monochrome(&self, no_color: Option<&OsStr>) -> bool {
+        self.monochrome || no_color.is_some_and(|v| !v.is_empty())
+    }
+}
+
+/// How the UI draws metric history.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChartStyle {
+    /// 8-level block sparklines.
+    #[default]
+    Sparkline,
+    /// Braille line charts with a labelled Y axis and time ticks.
+    Braille,
+}
+
+impl ChartStyle {
+    /// The other style.
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Sparkline => Self::Braille,
+            Self::Braille => Self::Sparkline,
+        }
+    }
+}
+
+/// A tab of the UI.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Tab {
+    /// Utilization and power at a glance.
+    #[default]
+    Overview,
+    /// Per-core activity and frequency.
+    Cpu,
+    /// GPU activity, frequency and power.
+    Gpu,
+    /// Memory and swap.
+    Memory,
+    /// Chip description.
+    Soc,
+    /// Power breakdown by domain.
+    Power,
+}
+
+impl Tab {
+    /// Position in the tab bar.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// How a rendered screen is written out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Plain text: glyphs only.
+    Text,
+    /// Text with ANSI color and weight escapes, for `cat` or `less -R`.
+    Ansi,
+    /// An SVG image with the theme's colors.
+    #[default]
+    Svg,
+}
+
+impl ExportFormat {
+    /// File name extension.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Ansi => "ans",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// How the CPU tab lays out its cores.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum CpuView {
+    /// A row per core: activity and frequency gauges with short sparklines.
+    #[default]
+    Rows,
+    /// A heatmap of each core's active ratio, one column per sample.
+    Activity,
+    /// A heatmap of each core's frequency ratio, one column per sample.
+    Frequency,
+}
+
+impl CpuView {
+    /// The next view, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Rows => Self::Activity,
+            Self::Activity => Self::Frequency,
+            Self::Frequency => Self::Rows,
+        }
+    }
+}
+
+/// How far back the history charts reach.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimeRange {
+    /// The last `--history-size` raw samples.
+    #[default]
+    Recent,
+    /// The last hour, in 10 s buckets.
+    #[value(name = "1h")]
+    Hour,
+    /// The last 6 hours, in 1 min buckets.
+    #[value(name = "6h")]
+    SixHours,
+    /// The last 24 hours, in 10 min buckets.
+    #[value(name = "24h")]
+    Day,
+}
+
+impl TimeRange {
+    /// The next range, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Recent => Self::Hour,
+            Self::Hour => Self::SixHours,
+            Self::SixHours => Self::Day,
+            Self::Day => Self::Recent,
+        }
+    }
+
+    /// Short label for the tab bar, `None` for the raw samples.
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            Self::Recent => None,
+            Self::Hour => Some("1h"),
+            Self::SixHours => Some("6h"),
+            Self::Day => Some("24h"),
+        }
+    }
+}
+
+/// How a displayed value is smoothed over successive samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Smoothing {
+    /// The raw value of each sample.
+    #[default]
+    None,
+    /// Exponentially weighted moving average: each sample weighs alpha, the
+    /// previous average 1 - alpha.
+    Ewma(f64),
+    /// Average of the last n samples.
+    Mean(usize),
+}
+
+impl FromStr for Smoothing {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid =
+            || format!("invalid smoothing `{s}`: expected `none`, `ewma:<alpha>` or `mean:<n>`");
+        let (method, param) = s.split_once(':').unwrap_or((s, ""));
+        match (method, param) {
+            ("none", "") => Ok(Self::None),
+            ("ewma", alpha) => match alpha.parse() {
+                Ok(alpha) if alpha > 0.0 && alpha <= 1.0 => Ok(Self::Ewma(alpha)),
+                _ => Err(invalid()),
+            },
+            ("mean", n) => match n.parse() {
+                Ok(n) if n > 0 => Ok(Self::Mean(n)),
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
         }
     }
 }
 
-/// Hold color configuration.
-#[derive(Debug)]
+impl TryFrom<String> for Smoothing {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Smoothing overrides: the `[smoothing]` section. Unset metrics use
+/// `default`; `--smoothing` overrides all of them.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SmoothingOverrides {
+    /// Every metric without its own entry.
+    pub default: Option<Smoothing>,
+    /// See [`MetricSmoothing::cpu_pct`].
+    pub cpu_pct: Option<Smoothing>,
+    /// See [`MetricSmoothing::cpu_mhz`].
+    pub cpu_mhz: Option<Smoothing>,
+    /// See [`MetricSmoothing::gpu_pct`].
+    pub gpu_pct: Option<Smoothing>,
+    /// See [`MetricSmoothing::gpu_mhz`].
+    pub gpu_mhz: Option<Smoothing>,
+    /// See [`MetricSmoothing::cpu_w`].
+    pub cpu_w: Option<Smoothing>,
+    /// See [`MetricSmoothing::gpu_w`].
+    pub gpu_w: Option<Smoothing>,
+    /// See [`MetricSmoothing::ane_w`].
+    pub ane_w: Option<Smoothing>,
+    /// See [`MetricSmoothing::package_w`].
+    pub package_w: Option<Smoothing>,
+}
+
+/// Resolved smoothing per metric.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MetricSmoothing {
+    /// CPU core activity (and with it, cluster activity).
+    pub cpu_pct: Smoothing,
+    /// CPU cluster and core frequency.
+    pub cpu_mhz: Smoothing,
+    /// GPU activity.
+    pub gpu_pct: Smoothing,
+    /// GPU frequency.
+    pub gpu_mhz: Smoothing,
+    /// CPU power.
+    pub cpu_w: Smoothing,
+    /// GPU power.
+    pub gpu_w: Smoothing,
+    /// ANE power (and with it, ANE usage).
+    pub ane_w: Smoothing,
+    /// Package power.
+    pub package_w: Smoothing,
+}
+
+/// A configurable color: an index into the 256-color palette, a `#rrggbb`
+/// truecolor value, or the terminal's own color (`default`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawColor")]
+pub enum UiColor {
+    /// The terminal's default foreground or background.
+    Default,
+    /// A 256-color palette index, e.g. `2` (green).
+    Indexed(u8),
+    /// A truecolor value, e.g. `#268bd2`.
+    Rgb(u8, u8, u8),
+}
+
+impl FromStr for UiColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("invalid color `{s}`: expected 0~255, `#rrggbb` or `default`");
+        if s.eq_ignore_ascii_case("default") {
+            return Ok(Self::Default);
+        }
+        if let Some(hex) = s.strip_prefix('#') {
+            if hex.len() != 6 || !hex.is_ascii() {
+                return Err(invalid());
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+            return Ok(Self::Rgb(channel(0)?, channel(2)?, channel(4)?));
+        }
+        s.parse().map(Self::Indexed).map_err(|_| invalid())
+    }
+}
+
+/// A color as written in TOML: a bare palette index or a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawColor {
+    Index(u8),
+    Text(String),
+}
+
+impl TryFrom<RawColor> for UiColor {
+    type Error = String;
+
+    fn try_from(raw: RawColor) -> std::result::Result<Self, Self::Error> {
+        match raw {
+            RawColor::Index(i) => Ok(Self::Indexed(i)),
+            RawColor::Text(s) => s.parse(),
+        }
+    }
+}
+
+/// Names of the built-in themes, for `--theme`.
+pub const THEMES: [&str; 4] = ["dark", "light", "solarized", "high-contrast"];
+
+/// Hold color configuration, one color per UI role.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UiColors {
-    /// Accent color: ASCII code in 0~255.
-    pub accent: u8,
-    /// Gauge foreground color: ASCII code in 0~255.
-    pub gauge_fg: u8,
-    /// Gauge background color: ASCII code in 0~255.
-    pub gauge_bg: u8,
-    /// History foreground color: ASCII code in 0~255.
-    pub history_fg: u8,
-    /// History background color: ASCII code in 0~255.
-    pub history_bg: u8,
+    /// Accent color for labels and highlights.
+    pub accent: UiColor,
+    /// Gauge foreground color.
+    pub gauge_fg: UiColor,
+    /// Gauge background color.
+    pub gauge_bg: UiColor,
+    /// History (sparkline) foreground color.
+    pub history_fg: UiColor,
+    /// History (sparkline) background color.
+    pub history_bg: UiColor,
+    /// Elevated values, e.g. moderate thermal pressure or a paused view.
+    pub warning: UiColor,
+    /// Critical values, e.g. heavy thermal pressure.
+    pub critical: UiColor,
+    /// Panel borders.
+    pub border: UiColor,
+    /// Panel titles and the program name.
+    pub title: UiColor,
+    /// CPU layer of the Power chart.
+    pub power_cpu: UiColor,
+    /// GPU layer of the Power chart.
+    pub power_gpu: UiColor,
+    /// ANE layer of the Power chart.
+    pub power_ane: UiColor,
+    /// "Other" layer of the Power chart.
+    pub power_other: UiColor,
+}
+
+impl Default for UiColors {
+    /// The `dark` theme, i.e. the historical defaults.
+    fn default() -> Self {
+        Self::builtin("dark").expect("built-in theme")
+    }
+}
+
+impl UiColors {
+    /// The built-in theme called `name` (one of [`THEMES`]).
+    pub fn builtin(name: &str) -> Option<Self> {
+        use UiColor::{Default, Indexed as I, Rgb};
+        let colors = match name {
+            "dark" => Self {
+                accent: I(2),
+                gauge_fg: I(2),
+                gauge_bg: I(7),
+                history_fg: I(4),
+                history_bg: I(7),
+                warning: I(3),
+                critical: I(1),
+                border: Default,
+                title: Default,
+                power_cpu: I(4),
+                power_gpu: I(2),
+                power_ane: I(5),
+                power_other: I(8),
+            },
+            "light" => Self {
+                accent: I(4),
+                gauge_fg: I(4),
+                gauge_bg: I(252),
+                history_fg: I(6),
+                history_bg: I(252),
+                warning: I(130),
+                critical: I(160),
+                border: I(244),
+                title: Default,
+                power_cpu: I(25),
+                power_gpu: I(28),
+                power_ane: I(127),
+                power_other: I(246),
+            },
+            "solarized" => Self {
+                accent: Rgb(0x26, 0x8b, 0xd2),
+                gauge_fg: Rgb(0x85, 0x99, 0x00),
+                gauge_bg: Rgb(0x07, 0x36, 0x42),
+                history_fg: Rgb(0x2a, 0xa1, 0x98),
+                history_bg: Rgb(0x07, 0x36, 0x42),
+                warning: Rgb(0xb5, 0x89, 0x00),
+                critical: Rgb(0xdc, 0x32, 0x2f),
+                border: Rgb(0x58, 0x6e, 0x75),
+                title: Rgb(0x93, 0xa1, 0xa1),
+                power_cpu: Rgb(0x26, 0x8b, 0xd2),
+                power_gpu: Rgb(0x85, 0x99, 0x00),
+                power_ane: Rgb(0xd3, 0x36, 0x82),
+                power_other: Rgb(0x58, 0x6e, 0x75),
+            },
+            "high-contrast" => Self {
+                accent: I(14),
+                gauge_fg: I(10),
+                gauge_bg: I(8),
+                history_fg: I(14),
+                history_bg: I(8),
+                warning: I(11),
+                critical: I(9),
+                border: I(15),
+                title: I(15),
+                power_cpu: I(12),
+                power_gpu: I(10),
+                power_ane: I(13),
+                power_other: I(7),
+            },
+            _ => return None,
+        };
+        Some(colors)
+    }
+
+    /// A built-in theme by name, else a theme file at that path: a TOML table
+    /// of roles (as in `[colors]`) layered over the `dark` theme.
+    pub fn load_theme(theme: &str) -> Result<Self> {
+        if let Some(colors) = Self::builtin(theme) {
+            return Ok(colors);
+        }
+        let path = Path::new(theme);
+        if !path.is_file() {
+            return Err(Error::Config(format!(
+                "unknown theme `{theme}`: expected one of {} or a theme file",
+                THEMES.join(", ")
+            )));
+        }
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+        let overrides: ColorOverrides =
+            toml::from_str(&text).map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+        let mut colors = Self::default();
+        colors.apply(&overrides);
+        Ok(colors)
+    }
+
+    /// Replace the roles set in `overrides`.
+    pub fn apply(&mut self, overrides: &ColorOverrides) {
+        let roles = [
+            (&mut self.accent, overrides.accent),
+            (&mut self.gauge_fg, overrides.gauge_fg),
+            (&mut self.gauge_bg, overrides.gauge_bg),
+            (&mut self.history_fg, overrides.history_fg),
+            (&mut self.history_bg, overrides.history_bg),
+            (&mut self.warning, overrides.warning),
+            (&mut self.critical, overrides.critical),
+            (&mut self.border, overrides.border),
+            (&mut self.title, overrides.title),
+            (&mut self.power_cpu, overrides.power_cpu),
+            (&mut self.power_gpu, overrides.power_gpu),
+            (&mut self.power_ane, overrides.power_ane),
+            (&mut self.power_other, overrides.power_other),
+        ];
+        for (role, color) in roles {
+            if let Some(color) = color {
+                *role = color;
+            }
+        }
+    }
+}
+
+/// Per-role color overrides: the `[colors]` section and theme files. Unset
+/// roles keep the underlying theme's color.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorOverrides {
+    /// See [`UiColors::accent`].
+    pub accent: Option<UiColor>,
+    /// See [`UiColors::gauge_fg`].
+    pub gauge_fg: Option<UiColor>,
+    /// See [`UiColors::gauge_bg`].
+    pub gauge_bg: Option<UiColor>,
+    /// See [`UiColors::history_fg`].
+    pub history_fg: Option<UiColor>,
+    /// See [`UiColors::history_bg`].
+    pub history_bg: Option<UiColor>,
+    /// See [`UiColors::warning`].
+    pub warning: Option<UiColor>,
+    /// See [`UiColors::critical`].
+    pub critical: Option<UiColor>,
+    /// See [`UiColors::border`].
+    pub border: Option<UiColor>,
+    /// See [`UiColors::title`].
+    pub title: Option<UiColor>,
+    /// See [`UiColors::power_cpu`].
+    pub power_cpu: Option<UiColor>,
+    /// See [`UiColors::power_gpu`].
+    pub power_gpu: Option<UiColor>,
+    /// See [`UiColors::power_ane`].
+    pub power_ane: Option<UiColor>,
+    /// See [`UiColors::power_other`].
+    pub power_other: Option<UiColor>,
+}
+
+/// Warning and critical levels of one metric family, in percent. A reading at
+/// or above a level takes its color; a level above 100 never triggers.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Levels {
+    /// Start of the warning color.
+    pub warning: f64,
+    /// Start of the critical color.
+    pub critical: f64,
+}
+
+impl Levels {
+    const fn new(warning: f64, critical: f64) -> Self {
+        Self { warning, critical }
+    }
+}
+
+/// Gauge and sparkline color levels per metric family.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Thresholds {
+    /// CPU cluster and core activity.
+    pub cpu: Levels,
+    /// GPU activity.
+    pub gpu: Levels,
+    /// ANE usage.
+    pub ane: Levels,
+    /// RAM usage.
+    pub memory: Levels,
+    /// Swap usage.
+    pub swap: Levels,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            cpu: Levels::new(75.0, 90.0),
+            gpu: Levels::new(75.0, 90.0),
+            ane: Levels::new(75.0, 90.0),
+            memory: Levels::new(80.0, 90.0),
+            swap: Levels::new(50.0, 80.0),
+        }
+    }
+}
+
+/// Power ceilings of one chip: the values of full CPU, GPU and ANE gauges.
+///
+/// An entry applies to chips with its `brand` (e.g. `"Apple M3 Max"`) and any
+/// core counts it gives, so binned variants of one brand can differ.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChipCeilings {
+    /// CPU brand name, as `sysctl machdep.cpu.brand_string` reports it.
+    pub brand: String,
+    /// Total CPU cores, if the entry is for one core count only.
+    pub cpu_cores: Option<u16>,
+    /// Performance cores, likewise.
+    pub performance_cores: Option<u16>,
+    /// Efficiency cores, likewise.
+    pub efficiency_cores: Option<u16>,
+    /// GPU cores, likewise.
+    pub gpu_cores: Option<u16>,
+    /// CPU power ceiling [W].
+    pub cpu_w: f64,
+    /// GPU power ceiling [W].
+    pub gpu_w: f64,
+    /// ANE power ceiling [W].
+    pub ane_w: f64,
+}
+
+/// Contents of the TOML configuration file. Every section is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    /// Theme name or theme file path, unless `--theme` is given.
+    pub theme: Option<String>,
+    /// Color overrides per role, over the theme.
+    pub colors: ColorOverrides,
+    /// Gauge color levels per metric family.
+    pub thresholds: Thresholds,
+    /// Smoothing of the displayed values per metric family.
+    pub smoothing: SmoothingOverrides,
+    /// Key bindings per action, e.g. `next_tab = ["Right", "l"]`. An action
+    /// listed here loses its default bindings; an empty list unbinds it.
+    pub keymap: BTreeMap<String, Vec<String>>,
+    /// Power ceilings per chip, ahead of the built-in table.
+    pub ceilings: Vec<ChipCeilings>,
+}
+
+impl FileConfig {
+    /// The `path` if given (which must exist), else the default location if
+    /// present, else an empty config.
+    fn locate(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::load(path),
+            None => match default_config_path() {
+                Some(path) if path.is_file() => Self::load(&path),
+                _ => Ok(Self::default()),
+            },
+        }
+    }
+
+    /// Read and parse the configuration file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+        Self::parse(&text).map_err(|e| Error::Config(format!("{}: {e}", path.display())))
+    }
+
+    /// Parse the TOML text of a configuration file.
+    pub fn parse(text: &str) -> std::result::Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/pumas/config.toml`, falling back to
+/// `$HOME/.config/pumas/config.toml`.
+fn default_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(base.join("pumas").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_config_sections_are_optional() {
+        let cfg = FileConfig::parse("").expect("empty file");
+        assert!(cfg.keymap.is_empty());
+
+        let cfg = FileConfig::parse(
+            r#"
+            [keymap]
+            quit = ["q", "Ctrl-d"]
+            help = []
+            "#,
+        )
+        .expect("keymap");
+        assert_eq!(cfg.keymap["quit"], ["q", "Ctrl-d"]);
+        assert!(cfg.keymap["help"].is_empty());
+
+        assert!(
+            FileConfig::parse("[keymapp]").is_err(),
+            "typos are rejected"
+        );
+    }
+
+    #[test]
+    fn colors_parse_from_flags_and_toml() {
+        assert_eq!("2".parse(), Ok(UiColor::Indexed(2)));
+        assert_eq!("#268bD2".parse(), Ok(UiColor::Rgb(0x26, 0x8b, 0xd2)));
+        assert_eq!("Default".parse(), Ok(UiColor::Default));
+        for bad in ["256", "#12345", "#12345g", "green", "#ééé"] {
+            assert!(bad.parse::<UiColor>().is_err(), "{bad}");
+        }
+
+        let cfg = FileConfig::parse(
+            r##"
+            theme = "light"
+            [colors]
+            accent = 5
+            border = "#586e75"
+            "##,
+        )
+        .expect("colors");
+        assert_eq!(cfg.theme.as_deref(), Some("light"));
+        assert_eq!(cfg.colors.accent, Some(UiColor::Indexed(5)));
+        assert_eq!(cfg.colors.border, Some(UiColor::Rgb(0x58, 0x6e, 0x75)));
+        assert!(FileConfig::parse("[colors]\naccent = \"#zz0000\"").is_err());
+        assert!(FileConfig::parse("[colors]\nbroder = 1").is_err());
+    }
+
+    #[test]
+    fn status_takes_a_format_and_a_count() {
+        let argv = ["pumas", "status", "-n", "1", "-f", "{gpu_w:unit}"];
+        let Command::Status { args } = Config::parse_from(argv).command else {
+            unreachable!()
+        };
+        assert_eq!(
+            (args.format.as_str(), args.count),
+            ("{gpu_w:unit}", Some(1))
+        );
+        assert!(!args.lines);
+    }
+
+    #[test]
+    fn snapshot_takes_a_tab_size_and_run_options() {
+        let argv = [
+            "pumas", "snapshot", "--tab", "power", "--width", "100", "--format", "svg", "--ascii",
+        ];
+        let Command::Snapshot { args } = Config::parse_from(argv).command else {
+            unreachable!()
+        };
+        assert_eq!((args.tab.index(), args.width, args.height), (5, 100, 40));
+        assert_eq!((args.format, args.samples), (ExportFormat::Svg, 5));
+        assert!(args.run.ascii);
+        assert!(Config::try_parse_from(["pumas", "snapshot", "--width", "40"]).is_err());
+    }
+
+    #[test]
+    fn ranges_parse_by_duration_and_cycle() {
+        let Command::Run { args } = Config::parse_from(["pumas", "run", "--range", "6h"]).command
+        else {
+            unreachable!()
+        };
+        assert_eq!(args.range, TimeRange::SixHours);
+        assert_eq!(args.range.next().label(), Some("24h"));
+        assert_eq!(TimeRange::Day.next(), TimeRange::Recent);
+        assert_eq!(TimeRange::Recent.label(), None);
+    }
+
+    #[test]
+    fn smoothing_parses_and_layers_flag_over_file() {
+        assert_eq!("none".parse(), Ok(Smoothing::None));
+        assert_eq!("ewma:0.3".parse(), Ok(Smoothing::Ewma(0.3)));
+        assert_eq!("mean:5".parse(), Ok(Smoothing::Mean(5)));
+        for bad in ["ewma", "ewma:0", "ewma:1.5", "mean:0", "median:3", "none:1"] {
+            assert!(bad.parse::<Smoothing>().is_err(), "{bad}");
+        }
+
+        let args = |argv: &[&str]| match Config::parse_from(argv).command {
+            Command::Run { args } => args,
+            _ => unreachable!(),
+        };
+        let file =
+            FileConfig::parse("[smoothing]\ndefault = \"mean:3\"\npackage_w = \"none\"").unwrap();
+        let smoothing = args(&["pumas", "run"]).smoothing(&file);
+        assert_eq!(smoothing.cpu_pct, Smoothing::Mean(3));
+        assert_eq!(smoothing.cpu_w, Smoothing::Mean(3));
+        assert_eq!(smoothing.package_w, Smoothing::None);
+
+        // The flag wins over the file, per-metric overrides included.
+        let smoothing = args(&["pumas", "run", "--smoothing", "ewma:0.5"]).smoothing(&file);
+        assert_eq!(smoothing.gpu_pct, Smoothing::Ewma(0.5));
+        assert_eq!(smoothing.package_w, Smoothing::Ewma(0.5));
+        assert_eq!(
+            args(&["pumas", "run"]).smoothing(&FileConfig::default()),
+            MetricSmoothing::default()
+        );
+        assert!(FileConfig::parse("[smoothing]\ncpu_pct = \"fast\"").is_err());
+        assert!(FileConfig::parse("[smoothing]\npower = \"none\"").is_err());
+    }
+
+    #[test]
+    fn colors_layer_theme_then_file_then_flags() {
+        let args = |argv: &[&str]| match Config::parse_from(argv).command {
+            Command::Run { args } => args,
+            _ => unreachable!(),
+        };
+        let file = FileConfig::parse("theme = \"solarized\"\n[colors]\nwarning = 11").unwrap();
+
+        let colors = args(&["pumas", "run"]).colors(&file).unwrap();
+        let solarized = UiColors::builtin("solarized").unwrap();
+        assert_eq!(colors.accent, solarized.accent);
+        assert_eq!(colors.warning, UiColor::Indexed(11));
+
+        let colors = args(&[
+            "pumas",
+            "run",
+            "--theme",
+            "light",
+            "--accent-color",
+            "#ffffff",
+        ])
+        .colors(&file)
+        .unwrap();
+        assert_eq!(colors.accent, UiColor::Rgb(255, 255, 255));
+        assert_eq!(colors.border, UiColors::builtin("light").unwrap().border);
+
+        assert_eq!(
+            args(&["pumas", "run"])
+                .colors(&FileConfig::default())
+                .unwrap(),
+            UiColors::default()
+        );
+        assert!(
+            args(&["pumas", "run", "--theme", "nope"])
+                .colors(&file)
+                .is_err()
+        );
+        for name in THEMES {
+            assert!(UiColors::builtin(name).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    fn monochrome_from_the_flag_or_no_color() {
+        let args = |argv: &[&str]| match Config::parse_from(argv).command {
+            Command::Run { args } => args,
+            _ => unreachable!(),
+        };
+        let plain = args(&["pumas", "run"]);
+        assert!(!plain.is_monochrome(None));
+        assert!(!plain.is_monochrome(Some(OsStr::new(""))));
+        assert!(plain.is_monochrome(Some(OsStr::new("1"))));
+        let flags = args(&["pumas", "run", "--monochrome", "--ascii"]);
+        assert!(flags.is_monochrome(None));
+        assert!(flags.ascii);
+    }
+
+    #[test]
+    fn thresholds_override_whole_families() {
+        let cfg = FileConfig::parse(
+            r#"
+            [thresholds]
+            swap = { warning = 60, critical = 90 }
+            "#,
+        )
+        .expect("thresholds");
+        assert_eq!(cfg.thresholds.swap, Levels::new(60.0, 90.0));
+        assert_eq!(cfg.thresholds.cpu, Thresholds::default().cpu);
+        assert!(FileConfig::parse("[thresholds]\nswap = { warning = 60 }").is_err());
+    }
 }
@@ -0,0 +1,534 @@
+//! Environment diagnostics (`pumas doctor`).
+//!
+//! Each check turns what it finds into a [`Check`]: pass, warn, fail or skip,
+//! with a fix to try when it did not pass. The checks that need the system
+//! (running a binary, reading a file) gather their inputs in [`run`]; deciding
+//! the outcome is a pure function of those inputs, tested below.
+
+use std::{
+    env, fs,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+use crate::{
+    Result,
+    backend::{POWERMETRICS, sample_once},
+    error::Error,
+    info,
+    metrics::Metrics,
+    modules::{soc, vm_stat::VmStats},
+    ui::layout::{MIN_HEIGHT, MIN_WIDTH},
+    units,
+};
+
+/// The sudoers drop-in suggested by the README's Quick Launch section.
+const SUDOERS_DROP_IN: &str = "/etc/sudoers.d/pumas";
+
+/// Sample rate of the dry run.
+const DRY_RUN_RATE: Duration = Duration::from_millis(500);
+
+/// Name column width.
+const NAME_WIDTH: usize = 16;
+
+/// Result of one check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Pass,
+    /// Works, but not as well as it could.
+    Warn,
+    /// pumas cannot run until fixed.
+    Fail,
+    /// Not checked, see the detail.
+    Skip,
+}
+
+impl Outcome {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Warn => "warn",
+            Self::Fail => "FAIL",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+/// One diagnostic.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Check {
+    /// What was checked, e.g. `powermetrics`.
+    pub(crate) name: &'static str,
+    pub(crate) outcome: Outcome,
+    /// What was found.
+    pub(crate) detail: String,
+    /// What to do about it, when the check did not pass.
+    pub(crate) fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome: Outcome::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome: Outcome::Skip,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome: Outcome::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome: Outcome::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run every check, in order.
+pub(crate) fn run() -> Vec<Check> {
+    let is_root = info::is_root();
+    let has_powermetrics = Path::new(POWERMETRICS).exists();
+    let sudoers = Path::new(SUDOERS_DROP_IN)
+        .exists()
+        .then(|| fs::read_to_string(SUDOERS_DROP_IN).ok());
+    let exe = env::current_exe().unwrap_or_else(|_| PathBuf::from("pumas"));
+    let env_var = |name: &str| env::var(name).ok();
+
+    let mut checks = vec![
+        architecture(
+            env::consts::OS,
+            sysctl("hw.optional.arm64").as_deref(),
+            sysctl("sysctl.proc_translated").as_deref(),
+        ),
+        powermetrics(has_powermetrics, is_root),
+        sudoers_drop_in(sudoers, &exe),
+        sysctl_check(),
+        system_profiler_check(),
+        vm_stat_check(),
+    ];
+    checks.extend(terminal(
+        std::io::stdout().is_terminal(),
+        crossterm::terminal::size().ok(),
+        env_var,
+    ));
+    checks.push(if has_powermetrics && is_root {
+        dry_run(sample_once(DRY_RUN_RATE))
+    } else {
+        Check::skip("dry run", "needs powermetrics and sudo")
+    });
+    checks
+}
+
+/// One `sysctl` value, `None` if the kernel lacks the name.
+fn sysctl(name: &str) -> Option<String> {
+    let output = process::Command::new("/usr/sbin/sysctl")
+        .args(["-n", name])
+        .output()
+        .ok()?;
+    let value = String::from_utf8(output.stdout).ok()?;
+    output.status.success().then(|| value.trim().to_string())
+}
+
+/// Apple Silicon, from `hw.optional.arm64`, and not translated by Rosetta.
+fn architecture(os: &str, arm64: Option<&str>, translated: Option<&str>) -> Check {
+    const NAME: &str = "Apple Silicon";
+    if os != "macos" {
+        return Check::fail(
+            NAME,
+            format!("running on {os}"),
+            "pumas reads macOS tools: run it on a Mac with Apple Silicon",
+        );
+    }
+    if arm64 != Some("1") {
+        return Check::fail(
+            NAME,
+            "Intel Mac",
+            "powermetrics reports no Apple Silicon clusters or ANE on Intel Macs",
+        );
+    }
+    if translated == Some("1") {
+        return Check::warn(
+            NAME,
+            "arm64 Mac, but pumas runs under Rosetta",
+            "install the arm64 build of pumas",
+        );
+    }
+    Check::pass(NAME, "arm64")
+}
+
+/// `powermetrics` is present, and pumas can run it.
+fn powermetrics(exists: bool, is_root: bool) -> Check {
+    const NAME: &str = "powermetrics";
+    if !exists {
+        return Check::fail(
+            NAME,
+            format!("{POWERMETRICS} not found"),
+            "powermetrics ships with macOS: check the macOS installation",
+        );
+    }
+    if !is_root {
+        return Check::warn(
+            NAME,
+            "present, but needs superuser privileges",
+            "run `sudo pumas run`, and `sudo pumas doctor` for the dry run",
+        );
+    }
+    Check::pass(NAME, format!("{POWERMETRICS}, running as root"))
+}
+
+/// The README's sudoers drop-in: `None` if absent, `Some(None)` if present but
+/// unreadable (it is root-only), else its contents.
+fn sudoers_drop_in(drop_in: Option<Option<String>>, exe: &Path) -> Check {
+    const NAME: &str = "sudoers drop-in";
+    let rule = format!("<username> ALL=(ALL) NOPASSWD: {}", exe.display());
+    match drop_in {
+        None => Check::warn(
+            NAME,
+            format!("no {SUDOERS_DROP_IN}"),
+            format!(
+                "optional, to skip the password: `sudo visudo -f {SUDOERS_DROP_IN}`, add `{rule}`"
+            ),
+        ),
+        Some(None) => Check::skip(
+            NAME,
+            format!("{SUDOERS_DROP_IN} present, readable with sudo only"),
+        ),
+        Some(Some(contents)) => {
+            let exe = exe.to_string_lossy();
+            let allowed = contents.lines().any(|line| {
+                let line = line.trim();
+                !line.starts_with('#') && line.contains("NOPASSWD") && line.contains(&*exe)
+            });
+            if allowed {
+                Check::pass(NAME, format!("{SUDOERS_DROP_IN} allows {exe}"))
+            } else {
+                Check::warn(
+                    NAME,
+                    format!("{SUDOERS_DROP_IN} has no NOPASSWD rule for {exe}"),
+                    format!(
+                        "`sudo visudo -f {SUDOERS_DROP_IN}`, add `{rule}` (the path moves on reinstall)"
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// `sysctl` runs, and its chip and system values parse.
+fn sysctl_check() -> Check {
+    const NAME: &str = "sysctl";
+    match soc::cpu_info().and_then(|cpu| soc::system_info().map(|_| cpu)) {
+        Ok((brand, cores, performance, efficiency)) => Check::pass(
+            NAME,
+            format!("{brand}, {cores} cores ({performance}P + {efficiency}E)"),
+        ),
+        Err(err) => Check::fail(
+            NAME,
+            err.to_string(),
+            "run `/usr/sbin/sysctl machdep.cpu hw.perflevel0` and report its output",
+        ),
+    }
+}
+
+/// `system_profiler` runs, and its GPU core count parses.
+fn system_profiler_check() -> Check {
+    const NAME: &str = "system_profiler";
+    match soc::gpu_info() {
+        Ok(cores) => Check::pass(NAME, format!("{cores} GPU cores")),
+        Err(err) => Check::fail(
+            NAME,
+            err.to_string(),
+            "run `/usr/sbin/system_profiler SPDisplaysDataType` and report its output",
+        ),
+    }
+}
+
+/// `vm_stat` runs, and its output parses.
+fn vm_stat_check() -> Check {
+    const NAME: &str = "vm_stat";
+    match VmStats::collect() {
+        Ok(stats) => Check::pass(
+            NAME,
+            format!(
+                "{} in {} B pages",
+                units::bibytes1(stats.total_memory() as f64),
+                stats.page_size
+            ),
+        ),
+        Err(err @ Error::VmStatSpawn(_)) => Check::warn(
+            NAME,
+            err.to_string(),
+            "check `vm_stat` is on the PATH; the Memory tab falls back to sysinfo",
+        ),
+        Err(err) => Check::warn(
+            NAME,
+            err.to_string(),
+            "run `vm_stat` and report its output; the Memory tab falls back to sysinfo",
+        ),
+    }
+}
+
+/// The terminal size, colors and Unicode support, for the UI.
+fn terminal(
+    is_tty: bool,
+    size: Option<(u16, u16)>,
+    env_var: impl Fn(&str) -> Option<String>,
+) -> Vec<Check> {
+    let size = match size {
+        _ if !is_tty => Check::skip("terminal size", "stdout is not a terminal"),
+        None => Check::skip("terminal size", "unknown"),
+        Some((w, h)) if usize::from(w) < MIN_WIDTH || usize::from(h) < MIN_HEIGHT => Check::warn(
+            "terminal size",
+            format!("{w} × {h}, the UI needs {MIN_WIDTH} × {MIN_HEIGHT}"),
+            "enlarge the window or shrink the font",
+        ),
+        Some((w, h)) => Check::pass("terminal size", format!("{w} × {h}")),
+    };
+
+    let set = |name: &str| env_var(name).filter(|v| !v.is_empty());
+    let term = set("TERM").unwrap_or_default();
+    let colorterm = set("COLORTERM").unwrap_or_default();
+    let colors = if set("NO_COLOR").is_some() {
+        Check::pass("colors", "none, NO_COLOR is set")
+    } else if term.is_empty() || term == "dumb" {
+        Check::warn(
+            "colors",
+            format!("TERM={term:?}"),
+            "set TERM (e.g. xterm-256color), or run with `--monochrome`",
+        )
+    } else if colorterm == "truecolor" || colorterm == "24bit" {
+        Check::pass("colors", format!("24-bit (COLORTERM={colorterm})"))
+    } else if term.contains("256color") {
+        Check::pass("colors", format!("256 (TERM={term})"))
+    } else {
+        Check::warn(
+            "colors",
+            format!("TERM={term}, without 256 colors"),
+            "use a 256-color TERM, or run with `--monochrome`",
+        )
+    };
+
+    // The first locale variable set decides, as in setlocale(3).
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(|name| set(name).map(|value| (name, value)));
+    let unicode = match locale {
+        Some((name, value))
+            if value.to_ascii_lowercase().contains("utf-8")
+                || value.to_ascii_lowercase().contains("utf8") =>
+        {
+            Check::pass("unicode", format!("{name}={value}"))
+        }
+        locale => Check::warn(
+            "unicode",
+            locale.map_or("no locale set".into(), |(name, value)| {
+                format!("{name}={value}")
+            }),
+            "set LANG=en_US.UTF-8, or run with `--ascii`",
+        ),
+    };
+
+    vec![size, colors, unicode]
+}
+
+/// The one-sample dry run.
+fn dry_run(sample: Result<Option<Metrics>>) -> Check {
+    const NAME: &str = "dry run";
+    match sample {
+        Ok(Some(metrics)) => Check::pass(
+            NAME,
+            format!(
+                "one sample: package {}, thermal pressure {}",
+                units::watts2(f64::from(metrics.consumption.package_w)),
+                metrics.thermal_pressure
+            ),
+        ),
+        Ok(None) => Check::fail(
+            NAME,
+            "powermetrics ended without a complete sample",
+            "run `sudo pumas run --json` and report the error it prints",
+        ),
+        Err(err) => Check::fail(
+            NAME,
+            err.to_string(),
+            "run `sudo powermetrics -n 1 --samplers cpu_power,gpu_power,thermal` and report its output",
+        ),
+    }
+}
+
+/// One line per check, its fix indented below, then the tally.
+pub(crate) fn report(checks: &[Check]) -> String {
+    let mut out = String::new();
+    for check in checks {
+        out += &format!(
+            "{}  {:<NAME_WIDTH$} {}\n",
+            check.outcome.label(),
+            check.name,
+            check.detail
+        );
+        if let Some(fix) = &check.fix {
+            out += &format!("      {:<NAME_WIDTH$} → {fix}\n", "");
+        }
+    }
+    let count = |outcome| checks.iter().filter(|c| c.outcome == outcome).count();
+    out += &format!(
+        "\n{} passed, {} warned, {} failed, {} skipped\n",
+        count(Outcome::Pass),
+        count(Outcome::Warn),
+        count(Outcome::Fail),
+        count(Outcome::Skip)
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn architecture_needs_native_apple_silicon() {
+        let outcome = |os, arm64, translated| architecture(os, arm64, translated).outcome;
+        assert_eq!(outcome("macos", Some("1"), Some("0")), Outcome::Pass);
+        assert_eq!(outcome("macos", Some("1"), None), Outcome::Pass);
+        assert_eq!(outcome("macos", Some("1"), Some("1")), Outcome::Warn);
+        assert_eq!(outcome("macos", Some("0"), None), Outcome::Fail);
+        assert_eq!(outcome("macos", None, None), Outcome::Fail);
+        assert_eq!(outcome("linux", None, None), Outcome::Fail);
+    }
+
+    #[test]
+    fn powermetrics_needs_root() {
+        assert_eq!(powermetrics(true, true).outcome, Outcome::Pass);
+        let check = powermetrics(true, false);
+        assert_eq!(check.outcome, Outcome::Warn);
+        assert!(check.fix.unwrap().contains("sudo pumas run"));
+        assert_eq!(powermetrics(false, true).outcome, Outcome::Fail);
+    }
+
+    #[test]
+    fn sudoers_rule_must_name_this_binary() {
+        let exe = Path::new("/opt/homebrew/bin/pumas");
+        let outcome = |drop_in: Option<Option<&str>>| {
+            sudoers_drop_in(drop_in.map(|d| d.map(String::from)), exe).outcome
+        };
+        assert_eq!(outcome(None), Outcome::Warn);
+        assert_eq!(outcome(Some(None)), Outcome::Skip);
+        assert_eq!(
+            outcome(Some(Some(
+                "alice ALL=(ALL) NOPASSWD: /opt/homebrew/bin/pumas\n"
+            ))),
+            Outcome::Pass
+        );
+        assert_eq!(
+            outcome(Some(Some(
+                "# alice ALL=(ALL) NOPASSWD: /opt/homebrew/bin/pumas\n"
+            ))),
+            Outcome::Warn
+        );
+        assert_eq!(
+            outcome(Some(Some(
+                "alice ALL=(ALL) NOPASSWD: /usr/local/bin/pumas\n"
+            ))),
+            Outcome::Warn
+        );
+        let fix = sudoers_drop_in(None, exe).fix.unwrap();
+        assert!(fix.contains("NOPASSWD: /opt/homebrew/bin/pumas"), "{fix}");
+    }
+
+    #[test]
+    fn terminal_size_colors_and_unicode() {
+        let outcomes = |is_tty, size, vars: &[(&str, &str)]| {
+            terminal(is_tty, size, |name| {
+                vars.iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| (*v).to_string())
+            })
+            .into_iter()
+            .map(|c| c.outcome)
+            .collect::<Vec<_>>()
+        };
+        use Outcome::*;
+        assert_eq!(
+            outcomes(
+                true,
+                Some((120, 40)),
+                &[("TERM", "xterm-256color"), ("LANG", "en_US.UTF-8")]
+            ),
+            [Pass, Pass, Pass]
+        );
+        assert_eq!(
+            outcomes(
+                true,
+                Some((50, 40)),
+                &[
+                    ("TERM", "xterm"),
+                    ("COLORTERM", "truecolor"),
+                    ("LC_ALL", "C.utf8")
+                ]
+            ),
+            [Warn, Pass, Pass]
+        );
+        // LC_ALL wins over LANG, as in setlocale(3).
+        assert_eq!(
+            outcomes(
+                false,
+                None,
+                &[("TERM", "dumb"), ("LC_ALL", "C"), ("LANG", "en_US.UTF-8")]
+            ),
+            [Skip, Warn, Warn]
+        );
+        assert_eq!(
+            outcomes(
+                true,
+                Some((80, 24)),
+                &[("TERM", "vt100"), ("NO_COLOR", "1")]
+            ),
+            [Pass, Pass, Warn]
+        );
+    }
+
+    #[test]
+    fn report_lists_fixes_and_tallies() {
+        let checks = [
+            powermetrics(true, false),
+            Check::pass("vm_stat", "16.0 GiB in 16384 B pages"),
+            dry_run(Ok(None)),
+        ];
+        let report = report(&checks);
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(
+            lines[0],
+            "warn  powermetrics     present, but needs superuser privileges"
+        );
+        assert_eq!(
+            lines[1].trim_start(),
+            "→ run `sudo pumas run`, and `sudo pumas doctor` for the dry run"
+        );
+        assert_eq!(lines[1].find('→'), Some(6 + NAME_WIDTH + 1));
+        assert_eq!(lines[2], "pass  vm_stat          16.0 GiB in 16384 B pages");
+        assert!(lines[3].starts_with("FAIL  dry run "), "{report}");
+        assert_eq!(lines[6], "1 passed, 1 warned, 1 failed, 0 skipped");
+    }
+}
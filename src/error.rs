@@ -44,6 +44,23 @@ pub enum Error {
         source: io::Error,
     },
 
+    /// Error spawning the vm_stat subprocess.
+    #[error("failed to run vm_stat: `{0}`")]
+    VmStatSpawn(io::Error),
+
+    /// A statistic pumas needs is missing from the vm_stat output.
+    #[error("vm_stat output lacks `{0}`")]
+    VmStatMissingField(&'static str),
+
+    /// A statistic of the vm_stat output is not a number.
+    #[error("vm_stat `{field}` is not a number: `{value}`")]
+    VmStatValue {
+        /// Statistic name, e.g. `Pages free`.
+        field: String,
+        /// Its unparsable value.
+        value: String,
+    },
+
     /// Error spawning powermetrics subprocess.
     #[error("failed to spawn powermetrics: `{0}`")]
     PowermetricsSpawn(io::Error),
@@ -59,4 +76,24 @@ pub enum Error {
     /// Error powermetrics exited with non-zero status.
     #[error("powermetrics ({0}), error: `{1}`")]
     PowermetricsNonZeroExit(process::ExitStatus, String),
+
+    /// Invalid configuration file or setting.
+    #[error("config error: `{0}`")]
+    Config(String),
+
+    /// Invalid `pumas status` template.
+    #[error("status template error: {0}")]
+    Template(String),
+
+    /// Unreadable `--history-file`.
+    #[error("history file error: `{0}`")]
+    HistoryFile(String),
+
+    /// Unreadable learned power ceilings file.
+    #[error("ceilings file error: `{0}`")]
+    CeilingsFile(String),
+
+    /// The collector thread panicked.
+    #[error("the collector panicked")]
+    CollectorPanic,
 }
@@ -0,0 +1,240 @@
+//! Hardware and data-source report (`pumas info`), without `powermetrics`.
+//!
+//! Runs the same SoC discovery as the monitor (`sysctl`, `system_profiler`),
+//! then reports the SoC table, the resolved power ceilings and where they come
+//! from, and each data source the monitor reads. Sources are probed, not run:
+//! a binary must exist, and `powermetrics` also needs the superuser. So the
+//! report works without `sudo`, and tells whether `pumas run` would.
+
+use std::{path::Path, process, time::Duration};
+
+use serde::Serialize;
+
+use crate::{
+    backend::{
+        POWERMETRICS, SAMPLERS,
+        frame::{render_ceiling_rows, render_soc_rows},
+    },
+    modules::soc::{Domains, SocInfo},
+    units,
+};
+
+/// Label column width, as on the SoC tab.
+const LABEL_WIDTH: usize = 20;
+
+/// Whether a data source can be read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Availability {
+    /// Present and readable.
+    Available,
+    /// Present, but only readable by the superuser.
+    NeedsRoot,
+    /// Not found.
+    Missing,
+}
+
+impl Availability {
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Available => "available",
+            Self::NeedsRoot => "needs sudo",
+            Self::Missing => "missing",
+        }
+    }
+}
+
+/// One data source of the monitor.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct Source {
+    /// Name, e.g. `vm_stat`.
+    pub(crate) name: &'static str,
+    /// Binary path; `None` for in-process sources.
+    pub(crate) path: Option<&'static str>,
+    /// What pumas reads from it.
+    pub(crate) provides: &'static str,
+    /// Whether it can be read.
+    pub(crate) status: Availability,
+}
+
+/// The external binaries pumas runs, and what each provides.
+const BINARIES: [(&str, &str, &str); 4] = [
+    (
+        "powermetrics",
+        POWERMETRICS,
+        "CPU, GPU and ANE power, frequencies and residency; thermal pressure",
+    ),
+    (
+        "vm_stat",
+        "/usr/bin/vm_stat",
+        "memory page statistics (Memory tab)",
+    ),
+    (
+        "sysctl",
+        "/usr/sbin/sysctl",
+        "chip, core counts, model, OS, memory size and caches",
+    ),
+    (
+        "system_profiler",
+        "/usr/sbin/system_profiler",
+        "GPU core count",
+    ),
+];
+
+/// The data sources, given whether pumas runs as the superuser and which
+/// paths exist.
+pub(crate) fn sources(is_root: bool, exists: impl Fn(&Path) -> bool) -> Vec<Source> {
+    let mut sources: Vec<Source> = BINARIES
+        .into_iter()
+        .map(|(name, path, provides)| {
+            let status = if !exists(Path::new(path)) {
+                Availability::Missing
+            } else if path == POWERMETRICS && !is_root {
+                Availability::NeedsRoot
+            } else {
+                Availability::Available
+            };
+            Source {
+                name,
+                path: Some(path),
+                provides,
+                status,
+            }
+        })
+        .collect();
+    sources.push(Source {
+        name: "sysinfo",
+        path: None,
+        provides: "per-core CPU usage, memory and swap",
+        status: Availability::Available,
+    });
+    sources
+}
+
+/// Whether pumas runs as the superuser, which `powermetrics` requires.
+pub(crate) fn is_root() -> bool {
+    process::Command::new("/usr/bin/id")
+        .arg("-u")
+        .output()
+        .is_ok_and(|out| out.stdout.trim_ascii() == b"0")
+}
+
+/// The domains flagged in `domains`, e.g. `CPU, ANE`.
+fn domain_list(domains: Domains) -> String {
+    [
+        (domains.cpu, "CPU"),
+        (domains.gpu, "GPU"),
+        (domains.ane, "ANE"),
+    ]
+    .into_iter()
+    .filter_map(|(on, name)| on.then_some(name))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// The report as text: the SoC table with the uptime and the ceilings'
+/// origin, then one row per data source.
+pub(crate) fn text(soc: &SocInfo, uptime: Duration, sources: &[Source]) -> String {
+    let mut origin = soc.ceiling_source.describe().to_string();
+    if soc.pinned.any() {
+        origin += &format!("; set by flags: {}", domain_list(soc.pinned));
+    }
+    if soc.calibrated.any() {
+        origin += &format!(
+            "; raised to observed peaks: {}",
+            domain_list(soc.calibrated)
+        );
+    }
+    let mut rows = render_soc_rows(soc).rows;
+    rows.extend(render_ceiling_rows(soc));
+    rows.push(("Uptime:".into(), units::uptime(uptime)));
+    rows.push(("Ceilings from:".into(), origin));
+
+    let mut out = String::new();
+    for (label, value) in rows {
+        out += &format!("{label:<LABEL_WIDTH$} {value}\n");
+    }
+    out += &format!(
+        "\nData sources (powermetrics samplers: {}):\n",
+        SAMPLERS.join(", ")
+    );
+    for source in sources {
+        out += &format!(
+            "{:<LABEL_WIDTH$} {:<11} {}\n",
+            source.name,
+            source.status.describe(),
+            source.provides
+        );
+    }
+    out
+}
+
+/// The report as JSON: the `soc` object of `run --json`, the ceilings'
+/// origin, the `powermetrics` samplers and the data sources.
+pub(crate) fn json(soc: &SocInfo, uptime: Duration, sources: &[Source]) -> serde_json::Value {
+    serde_json::json!({
+        "soc": soc.json(uptime),
+        "ceilings": {
+            "source": soc.ceiling_source,
+            "pinned": soc.pinned,
+            "calibrated": soc.calibrated,
+        },
+        "samplers": SAMPLERS,
+        "sources": sources,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::tests::test_soc;
+
+    #[test]
+    fn sources_need_root_only_for_powermetrics() {
+        let all = sources(false, |_| true);
+        let status: Vec<_> = all.iter().map(|s| (s.name, s.status)).collect();
+        assert_eq!(status[0], ("powermetrics", Availability::NeedsRoot));
+        assert!(all[1..].iter().all(|s| s.status == Availability::Available));
+        assert_eq!(all.last().map(|s| s.name), Some("sysinfo"));
+
+        let root = sources(true, |p| p != Path::new("/usr/bin/vm_stat"));
+        assert_eq!(root[0].status, Availability::Available);
+        assert_eq!(root[1].status, Availability::Missing);
+    }
+
+    #[test]
+    fn text_and_json_reports() {
+        let soc = SocInfo {
+            calibrated: Domains {
+                ane: true,
+                ..Default::default()
+            },
+            ..test_soc()
+        };
+        let uptime = Duration::from_secs(3_720);
+        let sources = sources(false, |_| true);
+
+        let text = text(&soc, uptime, &sources);
+        assert!(
+            text.starts_with("SoC brand name:      Apple M1\n"),
+            "{text}"
+        );
+        assert!(text.contains("Uptime:              1h 2m\n"), "{text}");
+        assert!(
+            text.contains("Ceilings from:       built-in table; raised to observed peaks: ANE\n"),
+            "{text}"
+        );
+        assert!(
+            text.contains("\npowermetrics         needs sudo  CPU, GPU"),
+            "{text}"
+        );
+
+        let json = json(&soc, uptime, &sources);
+        assert_eq!(json["soc"]["uptime_s"], 3_720);
+        assert_eq!(json["ceilings"]["source"], "builtin");
+        assert_eq!(json["ceilings"]["calibrated"]["ane"], true);
+        assert_eq!(json["sources"][0]["status"], "needs_root");
+        assert_eq!(json["sources"][4]["path"], serde_json::Value::Null);
+        assert_eq!(json["samplers"][2], "thermal");
+    }
+}
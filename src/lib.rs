@@ -31,8 +31,91 @@
 //! sudo pumas run
 //! ```
 //!
-//! Use the arrow keys to switch between tabs. Press `Esc`, `q`, `x`, or `Ctrl-C`
-//! to quit.
+//! Use the arrow keys (or `h` / `l`, or `1`–`6`) to switch between tabs. Press
+//! `Esc`, `q`, `x`, or `Ctrl-C` to quit, and `?` for an overlay listing every key
+//! binding.
+//!
+//! Press `p` (or `Space`) to pause the view: sampling continues in the background
+//! but the screen stays frozen. Use `Shift-←` / `Shift-→` to step back and forth
+//! through past samples; the title bar shows how far back you are, e.g.
+//! `PAUSED @ t-42s`. Press `p` again to resume live updates.
+//!
+//! Press `+` / `-` to sample faster / slower (`powermetrics` is restarted
+//! transparently), and `]` / `[` to zoom the sparkline time window in / out. The
+//! current settings are shown on the right of the tab bar.
+//!
+//! Press `c` to switch between block sparklines and braille line charts (or start
+//! with `--chart-style braille`). Line charts have four times the vertical
+//! resolution, a labelled Y axis and time ticks (`t-32s` … `now`), and the GPU
+//! tab gains a history chart of activity and frequency.
+//!
+//! Press `m` on the CPU tab to cycle between the per-core rows and a per-core
+//! heatmap of activity, then of frequency (or start with `--cpu-view activity`).
+//! The heatmap has a row per core, grouped by cluster, and a column per sample
+//! over the whole history, so threads migrating between E and P cores stand
+//! out; `]` / `[` zoom it like the charts.
+//!
+//! By default the charts show the last `--history-size` raw samples (about two
+//! minutes at 1 s). For long sessions, press `r` (or start with `--range`) to
+//! cycle to the last hour, 6 hours or 24 hours: every metric is also rolled up
+//! into 10 s, 1 min and 10 min buckets of min / average / max, and the charts
+//! then draw the bucket averages, colored by each bucket's peak so short bursts
+//! still show. The tab bar names the range (`· 1h`), and pointing at an Overview
+//! sparkline shows the bucket's range, e.g. `42.0 % (10.0 %–80.0 %) @ t-12m`.
+//!
+//! To keep that context across restarts, pass `--history-file <FILE>` (or set
+//! `PUMAS_HISTORY_FILE`): Pumas saves the history, peaks and rollups there on
+//! exit and restores them on the next start, provided the file was recorded on
+//! the same SoC. The time Pumas was not running shows as a gap; the Power tab's
+//! energy and averages still start from zero each session.
+//!
+//! ```console
+//! $ export PUMAS_HISTORY_FILE=~/.local/state/pumas/history.json
+//! $ sudo -E pumas run
+//! ```
+//!
+//! Gauge titles show two peaks: the highest value of the last minute (set the
+//! window with `--peak-window <SECS>`) and the highest of the session, e.g.
+//! `P0-Cluster: 6.6 % @ 1437 MHz (peak: 9.8 %, session: 41.2 %)`, so a startup
+//! spike no longer hides what the machine is doing now. Press `R` to reset both.
+//!
+//! The mouse works too: click a tab to select it, scroll over the tab bar to
+//! cycle tabs or over a tall tab (e.g. the CPU tab on Ultra chips) to scroll it,
+//! and point at a sparkline on the Overview, CPU or GPU tab to see the exact
+//! value and age of that sample, e.g. `42.0 % @ t-12s`.
+//!
+//! On short terminals the Overview shrinks its sparklines, then drops them, to
+//! stay on one screen. Tabs that still don't fit scroll with `↑` / `↓` (or `k` /
+//! `j`) and `PageUp` / `PageDown`, with a scroll indicator on the right edge.
+//! Below 60 × 12 Pumas shows a "Terminal too small" message until the window is
+//! enlarged.
+//!
+//! With `--inline`, Pumas draws a compact Overview (at most 24 rows, shrinking
+//! its sparklines to fit) below the shell prompt instead of taking over the
+//! terminal, and leaves the last frame in the scrollback on exit, which suits CI
+//! logs and split terminals. The keys work as usual, except that the other tabs
+//! are not available; the mouse is left to the terminal, so there are no hover
+//! tooltips.
+//!
+//! For terminals and log viewers that mangle Unicode or colors, `--ascii` draws
+//! everything with plain ASCII (`.,:-=+*#` sparklines, `#` gauges, `=` line
+//! gauges, `+-|` borders and dividers), and `--monochrome` (or any non-empty
+//! `NO_COLOR`) drops every color: unfilled line gauges switch to a thin line and
+//! warning or critical values are shown in bold instead.
+//!
+//! Press `e` to save the screen as it is drawn to `pumas-YYYYMMDD-HHMMSS.svg`
+//! (UTC) in the current directory, with the theme's colors; `--export-format`
+//! saves plain text (`txt`) or ANSI-colored text (`ans`, for `less -R`) instead.
+//!
+//! To share a whole session, `--cast <FILE>` records everything Pumas draws, with
+//! its timing, as an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/)
+//! file, one event per frame. Replay it with `asciinema play`, or in a browser
+//! with the asciinema player:
+//!
+//! ```sh
+//! $ sudo pumas run --cast session.cast
+//! $ asciinema play session.cast
+//! ```
 //!
 //! ### Screenshots
 //!
@@ -67,6 +150,10 @@
 //!
 //! ![SoC](./images/screenshot-soc.png)
 //!
+//! Power Tab: a stacked chart of CPU, GPU, ANE and other power with the SoC's
+//! power ceilings as reference lines, and the current, average and peak power
+//! and the energy used per domain since launch.
+//!
 //! ## Installation Methods
 //!
 //! ### Homebrew Tap
@@ -153,6 +240,10 @@
 //!
 //! Commands:
 //!   run                  Run the power usage monitor
+//!   status               Print a one-line summary per sample, for status bars
+//!   snapshot             Render one frame of a tab after a few samples, without the interactive UI, as text, ANSI or SVG
+//!   info                 Print the detected SoC, its power ceilings and data sources, without sudo
+//!   doctor               Check the Mac, the tools pumas runs and the terminal, with fixes
 //!   generate-completion  Print a shell completion script to stdout
 //!   help                 Print this message or the help of the given subcommand(s)
 //!
@@ -174,18 +265,52 @@
 //!           Update rate (milliseconds): min: 100 [default: 1000]
 //!       --history-size <HISTORY_SIZE>
 //!           History buffer size: default: 128 [default: 128]
+//!       --peak-window <PEAK_WINDOW_S>
+//!           Peak window [s], min=1 [default: 60]
+//!       --smoothing <SMOOTHING>
+//!           Smoothing of the displayed values: `none`, `ewma:<alpha>` (weight of the newest sample, 0 < alpha <= 1) or `mean:<n>` (average of the last n samples). Overrides the configuration file's `[smoothing]` section for every metric; charts and `--json` stay raw
+//!       --theme <THEME>
+//!           Color theme: `dark`, `light`, `solarized`, `high-contrast`, or the path of a theme file. Default: `dark` [env: PUMAS_THEME=]
 //!       --accent-color <ACCENT_COLOR>
-//!           Accent color for labels: ASCII code in 0~255, default: green [default: 2]
+//!           Accent color for labels: 0~255, `#rrggbb` or `default`
 //!       --gauge-fg-color <GAUGE_FG_COLOR>
-//!           Gauge foreground color: ASCII code in 0~255, default: green [default: 2]
+//!           Gauge foreground color: 0~255, `#rrggbb` or `default`
 //!       --gauge-bg-color <GAUGE_BG_COLOR>
-//!           Gauge background color: ASCII code in 0~255, default: white [default: 7]
+//!           Gauge background color: 0~255, `#rrggbb` or `default`
 //!       --history-fg-color <HISTORY_FG_COLOR>
-//!           History foreground color: ASCII code in 0~255, default: blue [default: 4]
+//!           History foreground color: 0~255, `#rrggbb` or `default`
 //!       --history-bg-color <HISTORY_BG_COLOR>
-//!           History background color: ASCII code in 0~255, default: white [default: 7]
+//!           History background color: 0~255, `#rrggbb` or `default`
+//!       --chart-style <CHART_STYLE>
+//!           History chart style on the Overview and GPU tabs: block sparklines or braille line charts with axes (toggle with `c`) [default: sparkline] [possible values: sparkline, braille]
+//!       --cpu-view <CPU_VIEW>
+//!           CPU tab layout: a row per core with short sparklines, or a per-core heatmap of activity or frequency over the whole history (cycle with `m`) [default: rows] [possible values: rows, activity, frequency]
+//!       --range <RANGE>
+//!           Time range of the history charts: the raw recent samples, or the last hour, 6 hours or 24 hours from rolled-up history (cycle with `r`) [default: recent] [possible values: recent, 1h, 6h, 24h]
+//!       --ascii
+//!           Draw with ASCII characters only: no block elements, box-drawing or braille
+//!       --monochrome
+//!           Draw without colors, marking warning and critical state in bold instead. Also enabled by a non-empty `NO_COLOR`
 //!       --json
 //!           Print metrics to stdout as JSON instead of running the UI
+//!       --inline
+//!           Draw a compact Overview below the prompt instead of taking over the terminal; the last frame stays in the scrollback on exit
+//!       --export-format <EXPORT_FORMAT>
+//!           Format of the screen exports saved with `e` [default: svg] [possible values: text, ansi, svg]
+//!       --cast <FILE>
+//!           Record the session to this file as an asciicast v2 recording, for asciinema players
+//!       --history-file <FILE>
+//!           Save the history to this file on exit and restore it on the next start (if recorded on the same SoC), keeping sparklines and peaks across restarts [env: PUMAS_HISTORY_FILE=]
+//!       --max-cpu-w <WATTS>
+//!           CPU power [W] of a full gauge, over the built-in table and the configuration file's `[[ceilings]]`
+//!       --max-gpu-w <WATTS>
+//!           GPU power [W] of a full gauge
+//!       --max-ane-w <WATTS>
+//!           ANE power [W] of a full gauge
+//!       --fixed-ceilings
+//!           Keep the power ceilings as resolved, instead of raising them to the peaks observed in this and earlier sessions
+//!       --config <CONFIG>
+//!           Configuration file (TOML) [env: PUMAS_CONFIG=]
 //!   -h, --help
 //!           Print help (see more with '--help')
 //!   -V, --version
@@ -208,9 +333,219 @@
 //! ^C
 //! ```
 //!
+//! Each line also carries the recent and session peaks of the headline metrics
+//! (power, GPU and ANE activity, memory and swap use) under `peaks`, over the
+//! `--peak-window`:
+//!
+//! ```sh
+//! $ sudo pumas run --json | jq -c '.peaks.package_w'
+//! {"recent":2.97,"session":6.3}
+//! ```
+//!
+//! The `soc` object describes the machine: chip brand, model identifier, macOS
+//! version and build, boot time and `uptime_s`, unified memory size, die count,
+//! core counts (with Super cores on M5 Pro/Max), the L1/L2 cache sizes of each
+//! performance level, and the power ceilings.
+//!
+//! `metrics.memory.vm_stat_error` is `null` while `vm_stat` works. When it fails
+//! to run or its output lacks a statistic, it holds the reason, and `ram_used`
+//! comes from the `sysinfo` crate instead; the Memory tab says so too.
+//!
 //! The JSON schema and an example are available in the [schema](./schema)
 //! directory.
 //!
+//! ### Status Line Mode
+//!
+//! `pumas status` prints a one-line summary per sample, for tmux, SketchyBar or
+//! any other status bar. On a terminal it rewrites a single line in place; piped
+//! (or with `--lines`) it prints one line per sample. `-n 1` prints a single
+//! sample and exits.
+//!
+//! ```sh
+//! $ sudo pumas status -f '{cpu.pct}% {gpu.pct}% {package_w:.1}W {thermal}'
+//! 12% 3% 4.2W Nominal
+//! $ sudo pumas status -n 1 -f 'GPU {gpu.pct:spark} {gpu_w:unit}'
+//! ```
+//!
+//! Placeholders name a metric and an optional format: `{name}` is the bare
+//! number, `{name:.2}` sets the decimals, `{name:unit}` adds the unit as the UI
+//! prints it (`6.48 W`), and `{name:spark}` (or `{name:spark16}`) draws a mini
+//! sparkline of the last 8 (or 16) samples. `{{` and `}}` print braces.
+//!
+//! Metrics: `cpu.pct`, `ecpu.pct`, `pcpu.pct`, `scpu.pct` (mean core activity),
+//! `gpu.pct`, `gpu.mhz`, `ane.pct`, `cpu_w`, `gpu_w`, `ane_w`, `package_w`,
+//! `ram.pct`, `ram.gib`, `swap.pct`, `swap.gib` and `thermal` (the thermal
+//! pressure, text only).
+//!
+//! ### Snapshot Mode
+//!
+//! `pumas snapshot` collects a few samples (`-n`, default 5), renders one frame
+//! of a tab exactly as the UI would draw it, and prints it, or writes it to
+//! `-o <FILE>`, as plain text, ANSI-colored text or SVG. It takes the same
+//! theme and style options as `pumas run`, for docs, bug reports and CI. It
+//! reads a `--history-file` and the learned power ceilings but never writes
+//! them:
+//!
+//! ```sh
+//! $ sudo pumas snapshot --tab overview --width 120 --format svg -o overview.svg
+//! $ sudo pumas snapshot --tab cpu --cpu-view activity -n 30 --format ansi | less -R
+//! ```
+//!
+//! ### Info Mode
+//!
+//! `pumas info` checks what pumas detects without `sudo`: it runs only the SoC
+//! discovery (`sysctl`, `system_profiler`), never `powermetrics`, and prints the
+//! SoC table, the power ceilings and where they come from (built-in table,
+//! configuration file, flags, calibrated peaks), the `powermetrics` samplers, and
+//! whether each data source is available, missing, or needs `sudo`. It takes the
+//! `run` ceiling flags and `--config`; `--json` prints the same report as one JSON
+//! object, its `soc` object as in JSON mode:
+//!
+//! ```sh
+//! $ pumas info
+//! $ pumas info --json | jq .sources
+//! ```
+//!
+//! ### Doctor Mode
+//!
+//! When pumas fails to start, `pumas doctor` prints one pass/warn/fail line per
+//! check, with a fix under each that did not pass:
+//!
+//! - Apple Silicon, and not running under Rosetta
+//! - `powermetrics` present, and pumas running as root
+//! - the [sudoers drop-in](#quick-launch) allowing this `pumas` binary
+//! - `sysctl`, `system_profiler` and `vm_stat` running, and their output parsing
+//! - the terminal size, colors and UTF-8 locale, for the UI
+//! - a dry run taking one sample through `powermetrics`
+//!
+//! Run it with `sudo` for the drop-in's contents and the dry run, which are
+//! skipped otherwise. It exits with status 1 if a check fails, so the output is
+//! worth pasting into a bug report:
+//!
+//! ```sh
+//! $ sudo pumas doctor
+//! ```
+//!
+//! ### Configuration File
+//!
+//! Settings that do not fit on the command line are read from a TOML file:
+//! `--config <FILE>` (or `PUMAS_CONFIG`), else
+//! `$XDG_CONFIG_HOME/pumas/config.toml`, else `~/.config/pumas/config.toml`.
+//! Note that `sudo` may change `HOME`; pass `--config` explicitly if in doubt.
+//!
+//! The `[keymap]` section remaps actions. Listing an action replaces all of its
+//! default keys, and an empty list unbinds it. `Ctrl-c` is not remappable: it
+//! always quits.
+//!
+//! ```toml
+//! [keymap]
+//! quit = ["q", "Ctrl-d"]
+//! next_tab = ["Right", "Tab", "n"]
+//! prev_tab = ["Left", "BackTab", "N"]
+//! zoom_in = []
+//! ```
+//!
+//! Actions: `help`, `quit`, `next_tab`, `prev_tab`, `tab_1` … `tab_6`, `pause`,
+//! `step_back`, `step_forward`, `faster`, `slower`, `zoom_in`, `zoom_out`,
+//! `chart_style`, `cpu_view`, `range`, `reset_peaks`, `scroll_up`, `scroll_down`,
+//! `page_up`, `page_down`, `export`. Keys are a single character or one of `Esc`, `Enter`, `Tab`,
+//! `BackTab`, `Backspace`, `Space`, `Left`, `Right`, `Up`, `Down`, `Home`, `End`,
+//! `PageUp`, `PageDown`, optionally prefixed with `Ctrl-`, `Alt-` or `Shift-`.
+//!
+//! ### Themes
+//!
+//! Pick a built-in theme with `--theme` (or `PUMAS_THEME`, or `theme = "…"` in
+//! the configuration file): `dark` (the default), `light`, `solarized` (truecolor)
+//! or `high-contrast`. Colors are a 256-color index (`0`–`255`), a truecolor
+//! `#rrggbb` value, or `default` for the terminal's own color.
+//!
+//! The `[colors]` section overrides individual roles on top of the theme, and
+//! the `--*-color` flags override both. Besides the gauge and history colors,
+//! the roles include `warning` and `critical` (thermal pressure, the paused
+//! indicator), `border` and `title` (panels), and `power_cpu`, `power_gpu`,
+//! `power_ane` and `power_other` (the Power tab):
+//!
+//! ```toml
+//! theme = "light"
+//!
+//! [colors]
+//! accent = "#268bd2"
+//! border = 244
+//! critical = "default"
+//! ```
+//!
+//! `--theme` also accepts the path of a theme file: a TOML file listing roles the
+//! same way as `[colors]`, at its top level. Roles it omits keep their `dark`
+//! colors.
+//!
+//! ### Thresholds
+//!
+//! Gauges and sparkline bars turn to the `warning` color, then the `critical`
+//! color, as a reading approaches its limit. The `[thresholds]` section sets the
+//! two levels, in percent, per metric family: `cpu`, `gpu`, `ane` (default 75 /
+//! 90), `memory` (80 / 90) and `swap` (50 / 80). A level above 100 never
+//! triggers:
+//!
+//! ```toml
+//! [thresholds]
+//! swap = { warning = 60, critical = 90 }
+//! gpu = { warning = 101, critical = 101 }
+//! ```
+//!
+//! ### Smoothing
+//!
+//! Per-second readings are noisy, so gauges can flicker. `--smoothing` averages
+//! the displayed values (gauges, titles and current readings) over the recent
+//! samples: `ewma:<alpha>` for an exponentially weighted average, where each
+//! sample weighs `alpha` (between 0 and 1), or `mean:<n>` for the average of the
+//! last `n` samples. The charts, peaks, energy totals and `run --json` keep the
+//! raw values. The `[smoothing]` section sets a `default` and per-metric
+//! overrides: `cpu_pct` and `cpu_mhz` (CPU activity and frequency), `gpu_pct`
+//! and `gpu_mhz`, and the `cpu_w`, `gpu_w`, `ane_w` and `package_w` power. A
+//! `--smoothing` flag overrides the whole section:
+//!
+//! ```toml
+//! [smoothing]
+//! default = "ewma:0.3"
+//! package_w = "mean:5"
+//! gpu_pct = "none"
+//! ```
+//!
+//! ### Power Ceilings
+//!
+//! Power gauges and charts are scaled to a ceiling per chip: the CPU, GPU and
+//! ANE power of a full gauge (the Package ceiling is their sum). Pumas ships a
+//! table of approximate ceilings, keyed on the chip's brand and, for binned
+//! variants, its core counts. A chip missing from the table gets generic
+//! ceilings (20 W, 20 W and 8 W), with a warning. `[[ceilings]]` entries in the
+//! configuration file take precedence over the table: each names a `brand` and
+//! optionally `cpu_cores`, `performance_cores`, `efficiency_cores` or
+//! `gpu_cores`, and the entry matching the most core counts wins. The
+//! `--max-cpu-w`, `--max-gpu-w` and `--max-ane-w` flags (on `run`, `status` and
+//! `snapshot`) override both:
+//!
+//! ```toml
+//! [[ceilings]]
+//! brand = "Apple M3 Pro"
+//! performance_cores = 5
+//! gpu_cores = 14
+//! cpu_w = 14.0
+//! gpu_w = 13.0
+//! ane_w = 8.0
+//! ```
+//!
+//! Ceilings also adapt: a sample above a ceiling raises it to the new peak, and
+//! the peaks are saved per chip brand (in `$XDG_STATE_HOME/pumas/ceilings.json`,
+//! else `~/.local/state/pumas/ceilings.json`) when pumas exits, so the next
+//! session starts from them. Under `sudo`, that file and its directories are
+//! given to the invoking user when they are created in a directory that user
+//! owns. Gauges scaled this way are marked `(auto)`: the ANE
+//! gauge, the CPU+GPU+ANE sparkline and the Power tab's ceiling lines. Ceilings
+//! set with a `--max-*-w` flag never move, and `--fixed-ceilings` turns learning
+//! off. A reading above four times the resolved ceiling is taken for a glitch and
+//! ignored. To start over, `pumas info --reset-ceilings` forgets the peaks learned
+//! for this chip.
+//!
 //! ### Quick Launch
 //!
 //! Some users reported they want a shorter way to launch Pumas. A quick way to do
@@ -264,20 +599,23 @@
 //! `sysctl` is used to measure the following:
 //!
 //! - CPU name
-//! - CPU core counts
+//! - CPU core counts and performance levels, with their cache sizes
+//! - Mac model, macOS version, boot time and memory size
 //!
 //! `system_profiler` is used to measure the following:
 //!
 //! - GPU core count
 //!
-//! Some information is guesstimate and hardcoded as there doesn't seem to be a
-//! official source for it on the system:
+//! Some information is guesstimate as there doesn't seem to be a official source
+//! for it on the system:
 //!
-//! - CPU, GPU & ANE max power draw
+//! - CPU, GPU & ANE max power draw: a built-in table per chip, overridable (see
+//!   [Power Ceilings](#power-ceilings)) and raised to the observed peaks
 //!
 //! `vm_stat` is used to measure the following:
 //!
-//! - Memory statistics compatible with Activity Monitor (anonymous, wired, compressed pages)
+//! - Memory statistics compatible with Activity Monitor (anonymous, wired, compressed pages),
+//!   falling back to `sysinfo` when `vm_stat` fails
 //!
 //! ## License
 //!
@@ -294,11 +632,15 @@
 
 mod backend;
 pub mod config;
+mod doctor;
 pub mod error;
+mod info;
 mod metric_key;
 mod metrics;
 mod modules;
 pub mod monitor;
+mod ownership;
+mod status;
 mod ui;
 mod units;
 
@@ -3,13 +3,15 @@
 //! This module provides strongly-typed keys for accessing metrics in the history
 //! HashMap, replacing stringly-typed keys with an enum for compile-time safety.
 
+use serde::{Deserialize, Serialize};
+
 /// Identifies a CPU cluster by its kind and index.
 ///
 /// Apple Silicon chips have efficiency (E) and performance (P) clusters.
 /// Single-die chips (M1, M2, M3) have one of each, while multi-die chips
 /// (M1 Ultra, M2 Ultra) have two of each.
 /// Starting with M5 Pro/Max there are also super (S) clusters above performance.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub(crate) struct ClusterId {
     pub kind: ClusterKind,
     pub index: u8,
@@ -42,7 +44,7 @@ impl ClusterId {
 }
 
 /// The kind of CPU cluster.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub(crate) enum ClusterKind {
     /// Efficiency cores (E-cluster).
     Efficiency,
@@ -52,8 +54,39 @@ pub(crate) enum ClusterKind {
     Super,
 }
 
+/// A slice of package power, as stacked on the Power tab.
+///
+/// CPU, GPU and ANE are measured by `powermetrics`; "other" is whatever the
+/// package reports beyond those three.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub(crate) enum PowerDomain {
+    /// CPU clusters.
+    Cpu,
+    /// GPU.
+    Gpu,
+    /// Apple Neural Engine.
+    Ane,
+    /// Package power not attributed to the CPU, GPU or ANE.
+    Other,
+}
+
+impl PowerDomain {
+    /// Every domain, bottom of the stack first.
+    pub const ALL: [Self; 4] = [Self::Cpu, Self::Gpu, Self::Ane, Self::Other];
+
+    /// Short display name, e.g. `"CPU"`.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Gpu => "GPU",
+            Self::Ane => "ANE",
+            Self::Other => "Other",
+        }
+    }
+}
+
 /// Type-safe key for accessing metrics in the history.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub(crate) enum MetricKey {
     // ─── Cluster metrics ───────────────────────────────────────────────────────
     /// Active ratio for a CPU cluster (0-100%).
@@ -84,6 +117,11 @@ pub(crate) enum MetricKey {
     AnePowerW,
     /// Total package power (CPU + GPU + ANE) in watts.
     PackagePowerW,
+    /// Power of one domain in milliwatts (the watt signals above keep whole
+    /// watts only, too coarse to stack).
+    PowerMilliwatts(PowerDomain),
+    /// Total package power in milliwatts, for exact package readouts.
+    PackageMilliwatts,
 
     // ─── Memory ────────────────────────────────────────────────────────────────
     /// RAM usage in bytes.
@@ -10,7 +10,8 @@ use serde::Serialize;
 use crate::{
     Result,
     error::Error,
-    modules::{powermetrics::plist_parsing, sysinfo},
+    metric_key::PowerDomain,
+    modules::{powermetrics::plist_parsing, sysinfo, vm_stat::VmStats},
 };
 
 /// Reformulated metrics from the output of the `powermetrics` tool and `sysinfo`.
@@ -22,7 +23,7 @@ use crate::{
 /// - Mx Max chips have one E cluster and two P clusters.
 /// - Mx Ultra chips have multiple E clusters and multiple P clusters.
 ///
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub(crate) struct Metrics {
     /// Efficiency Cluster metrics.
     pub(crate) e_clusters: Vec<ClusterMetrics>,
@@ -198,7 +199,7 @@ impl From<plist_parsing::Metrics> for Metrics {
 }
 
 /// Power consumption in W of the CPU, GPU, ANE, and package.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub(crate) struct PowerConsumption {
     /// CPU power consumption in W.
     pub(crate) cpu_w: f32,
@@ -210,8 +211,26 @@ pub(crate) struct PowerConsumption {
     pub(crate) package_w: f32,
 }
 
+impl PowerConsumption {
+    /// Package power not attributed to the CPU, GPU or ANE, in W (never
+    /// negative).
+    pub(crate) fn other_w(&self) -> f32 {
+        (self.package_w - self.cpu_w - self.gpu_w - self.ane_w).max(0.0)
+    }
+
+    /// Power of one domain in W.
+    pub(crate) fn domain_w(&self, domain: PowerDomain) -> f32 {
+        match domain {
+            PowerDomain::Cpu => self.cpu_w,
+            PowerDomain::Gpu => self.gpu_w,
+            PowerDomain::Ane => self.ane_w,
+            PowerDomain::Other => self.other_w(),
+        }
+    }
+}
+
 /// Metrics for a single cluster.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub(crate) struct ClusterMetrics {
     /// Cluster name: e.g. "E-Cluster" or "P-Cluster", or "P0-Cluster", "P1-Cluster", etc.
     pub(crate) name: String,
@@ -242,7 +261,7 @@ impl From<&plist_parsing::ClusterMetrics> for ClusterMetrics {
 }
 
 /// Metrics for a single CPU.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub(crate) struct CpuMetrics {
     /// CPU ID.
     pub(crate) id: u16,
@@ -298,7 +317,7 @@ impl CpuMetrics {
 }
 
 /// Metrics for the GPU.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub(crate) struct GpuMetrics {
     /// GPU frequency in MHz.
     pub(crate) freq_mhz: f64,
@@ -351,7 +370,7 @@ impl GpuMetrics {
 }
 
 /// Frequency ratios (from dynamic voltage and frequency management).
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub(crate) struct DvfmState {
     pub(crate) freq_mhz: u16,
     pub(crate) active_ratio: f64,
@@ -367,12 +386,17 @@ impl From<&plist_parsing::DvfmState> for DvfmState {
 }
 
 /// Memory metrics: RAM and Swap.
-#[derive(Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub(crate) struct MemoryMetrics {
     pub(crate) ram_total: u64,
     pub(crate) ram_used: u64,
     pub(crate) swap_total: u64,
     pub(crate) swap_used: u64,
+    /// The `vm_stat` statistics behind `ram_used`, for the Memory tab.
+    #[serde(skip)]
+    pub(crate) vm_stats: Option<VmStats>,
+    /// Why `vm_stat` failed, `ram_used` then coming from sysinfo.
+    pub(crate) vm_stat_error: Option<String>,
 }
 
 impl MemoryMetrics {
@@ -398,6 +422,8 @@ impl From<sysinfo::MemoryMetrics> for MemoryMetrics {
             ram_used: value.ram_used,
             swap_total: value.swap_total,
             swap_used: value.swap_used,
+            vm_stat_error: value.vm_stats.as_ref().err().cloned(),
+            vm_stats: value.vm_stats.ok(),
         }
     }
 }
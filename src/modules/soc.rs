@@ -1,15 +1,116 @@
 //! System-on-Chip (SoC) information.
+//!
+//! The power ceilings that scale the gauges come from the per-chip table in
+//! `ceilings.toml`, embedded at build time, under the configuration file's
+//! `[[ceilings]]` and the `--max-*-w` flags.
+
+use std::{
+    collections::HashMap,
+    process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    Result,
+    config::{CeilingArgs, ChipCeilings},
+    error::Error,
+};
+use serde::{Deserialize, Serialize};
+
+/// The built-in power ceilings table.
+const BUILTIN_CEILINGS: &str = include_str!("ceilings.toml");
+
+/// Ceilings for chips in neither table.
+const FALLBACK_CEILINGS: (f64, f64, f64) = (20.0, 20.0, 8.0);
+
+/// Where the power ceilings of a [`SocInfo`] were found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CeilingSource {
+    /// The built-in table.
+    #[default]
+    Builtin,
+    /// The configuration file's `[[ceilings]]`.
+    Config,
+    /// Neither: generic fallback values.
+    Unknown,
+}
+
+impl CeilingSource {
+    /// Human-readable description.
+    pub(crate) fn describe(self) -> &'static str {
+        match self {
+            Self::Builtin => "built-in table",
+            Self::Config => "configuration file",
+            Self::Unknown => "unknown chip, generic values",
+        }
+    }
+}
 
-use std::process;
+/// One flag per power domain with a ceiling.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct Domains {
+    pub(crate) cpu: bool,
+    pub(crate) gpu: bool,
+    pub(crate) ane: bool,
+}
 
-use crate::{Result, error::Error};
-use serde::Serialize;
+impl Domains {
+    /// Whether any domain is flagged.
+    pub(crate) fn any(self) -> bool {
+        self.cpu || self.gpu || self.ane
+    }
+}
+
+/// One CPU performance level (a core type) and its caches.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub(crate) struct PerfLevel {
+    /// Name, e.g. "Performance" or "Efficiency".
+    pub(crate) name: String,
+    /// Number of cores.
+    pub(crate) cores: u16,
+    /// L1 instruction cache size per core, in bytes.
+    pub(crate) l1i_bytes: u64,
+    /// L1 data cache size per core, in bytes.
+    pub(crate) l1d_bytes: u64,
+    /// L2 cache size, shared per cluster, in bytes.
+    pub(crate) l2_bytes: u64,
+}
+
+/// What `sysctl` reports beyond the core counts.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct SystemInfo {
+    model: String,
+    os_version: String,
+    os_build: String,
+    boot_time_s: u64,
+    memory_bytes: u64,
+    perf_levels: Vec<PerfLevel>,
+}
 
 #[derive(Debug, Serialize)]
 pub(crate) struct SocInfo {
     /// Brand name of the CPU, e.g. "Apple M1".
     pub(crate) cpu_brand_name: String,
 
+    /// Model identifier, e.g. "Mac14,6".
+    pub(crate) model: String,
+
+    /// macOS version, e.g. "15.1".
+    pub(crate) os_version: String,
+
+    /// macOS build, e.g. "24B83".
+    pub(crate) os_build: String,
+
+    /// Boot time, in seconds since the Unix epoch.
+    pub(crate) boot_time_s: u64,
+
+    /// Unified memory size, in bytes.
+    pub(crate) memory_bytes: u64,
+
+    /// Number of dies: 2 for an Ultra (two fused Max dies), else 1.
+    pub(crate) num_dies: u16,
+
     /// Number of CPU cores.
     pub(crate) num_cpu_cores: u16,
 
@@ -19,6 +120,12 @@ pub(crate) struct SocInfo {
     /// Number of Performance cores.
     pub(crate) num_performance_cores: u16,
 
+    /// Number of Super cores (M5 Pro/Max and above), 0 on other chips.
+    pub(crate) num_super_cores: u16,
+
+    /// CPU performance levels with their caches, fastest first.
+    pub(crate) perf_levels: Vec<PerfLevel>,
+
     /// Number of GPU cores.
     pub(crate) num_gpu_cores: u16,
 
@@ -33,61 +140,194 @@ pub(crate) struct SocInfo {
 
     /// Max Package power consumption.
     pub(crate) max_package_w: f64,
+
+    /// Where the power ceilings were found (before any `--max-*-w` flag).
+    #[serde(skip)]
+    pub(crate) ceiling_source: CeilingSource,
+
+    /// Ceilings set by a `--max-*-w` flag, which observed peaks never raise.
+    #[serde(skip)]
+    pub(crate) pinned: Domains,
+
+    /// Ceilings raised to observed peaks.
+    #[serde(skip)]
+    pub(crate) calibrated: Domains,
 }
 
 impl SocInfo {
-    pub(crate) fn new() -> Result<SocInfo> {
+    /// Query the chip, then resolve its power ceilings from `ceilings` (the
+    /// configuration file's), the built-in table and the `flags`.
+    pub(crate) fn new(ceilings: &[ChipCeilings], flags: CeilingArgs) -> Result<SocInfo> {
         let (cpu_brand_name, num_cpu_cores, num_efficiency_cores, num_performance_cores) =
             cpu_info()?;
 
         let num_gpu_cores = gpu_info()?;
-
-        // Approximate peak power ceilings (watts) for scaling gauges and sparklines — not
-        // measured TDP. Calibrated loosely against public summaries (e.g. MacNerd SoC peak table
-        // citing AnandTech / NotebookCheck / Apple; TechRadar on M4 Max CPU ~48 W) and split
-        // across CPU / GPU / ANE for separate widgets; sums sit near those “chip peak” ballparks.
-        //
-        // M5: base chip CPU ceiling from user testing (~25 W). M5 Pro / M5 Max:
-        // NotebookCheck CPU analysis (~75 W peak CPU for the shared 18-core complex; GPU analysis
-        // ~38 W Pro / ~72 W Max). M5 Ultra: chip not public — values are extrapolation only.
-        let (max_cpu_w, max_gpu_w, max_ane_w) = match cpu_brand_name.as_str() {
-            "Apple M1" => (20.0, 20.0, 8.0),
-            "Apple M1 Pro" => (22.0, 18.0, 8.0),
-            "Apple M1 Max" => (32.0, 52.0, 8.0),
-            "Apple M1 Ultra" => (70.0, 130.0, 15.0),
-            "Apple M2" => (22.0, 22.0, 8.0),
-            "Apple M2 Pro" => (38.0, 52.0, 10.0),
-            "Apple M2 Max" => (45.0, 90.0, 10.0),
-            "Apple M2 Ultra" => (75.0, 200.0, 20.0),
-            "Apple M3" => (14.0, 14.0, 8.0),
-            "Apple M3 Pro" => (16.0, 16.0, 8.0),
-            "Apple M3 Max" => (42.0, 66.0, 10.0),
-            "Apple M3 Ultra" => (65.0, 120.0, 15.0),
-            "Apple M4" => (18.0, 17.0, 8.0),
-            "Apple M4 Pro" => (35.0, 35.0, 10.0),
-            "Apple M4 Max" => (52.0, 83.0, 10.0),
-            "Apple M5" => (25.0, 22.0, 8.0),
-            "Apple M5 Pro" => (78.0, 40.0, 10.0),
-            "Apple M5 Max" => (78.0, 75.0, 12.0),
-            "Apple M5 Ultra" => (95.0, 175.0, 18.0),
-            _ => (20.0, 20.0, 8.0),
+        let system = system_info()?;
+        let num_super_cores = system
+            .perf_levels
+            .iter()
+            .filter(|level| level.name.contains("Super"))
+            .map(|level| level.cores)
+            .sum();
+        // Both dies of an Ultra show up as one package: only the brand tells.
+        let num_dies = if cpu_brand_name.ends_with("Ultra") {
+            2
+        } else {
+            1
         };
 
-        Ok(SocInfo {
+        let mut soc = SocInfo {
             cpu_brand_name,
+            model: system.model,
+            os_version: system.os_version,
+            os_build: system.os_build,
+            boot_time_s: system.boot_time_s,
+            memory_bytes: system.memory_bytes,
+            num_dies,
             num_cpu_cores,
             num_efficiency_cores,
             num_performance_cores,
-            max_cpu_w,
-            max_gpu_w,
-            max_ane_w,
-            max_package_w: max_cpu_w + max_gpu_w + max_ane_w,
+            num_super_cores,
+            perf_levels: system.perf_levels,
             num_gpu_cores,
-        })
+            max_cpu_w: 0.0,
+            max_gpu_w: 0.0,
+            max_ane_w: 0.0,
+            max_package_w: 0.0,
+            ceiling_source: CeilingSource::Unknown,
+            pinned: Domains::default(),
+            calibrated: Domains::default(),
+        };
+        soc.set_ceilings(ceilings, &builtin_ceilings()?, flags);
+        Ok(soc)
+    }
+
+    /// The `soc` object of the JSON outputs: the serialized fields plus the
+    /// `uptime` in whole seconds.
+    pub(crate) fn json(&self, uptime: Duration) -> serde_json::Value {
+        let mut soc = serde_json::json!(self);
+        soc["uptime_s"] = uptime.as_secs().into();
+        soc
+    }
+
+    /// Time since boot at `now` (zero if the clock is behind the boot time).
+    pub(crate) fn uptime(&self, now: SystemTime) -> Duration {
+        let now = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+        now.saturating_sub(Duration::from_secs(self.boot_time_s))
+    }
+
+    /// Set the power ceilings: the most specific matching entry of `config`,
+    /// else of `builtin`, else the fallback, then any `flags` over them.
+    fn set_ceilings(
+        &mut self,
+        config: &[ChipCeilings],
+        builtin: &[ChipCeilings],
+        flags: CeilingArgs,
+    ) {
+        let (source, (cpu, gpu, ane)) = match self.lookup(config) {
+            Some(c) => (CeilingSource::Config, (c.cpu_w, c.gpu_w, c.ane_w)),
+            None => match self.lookup(builtin) {
+                Some(c) => (CeilingSource::Builtin, (c.cpu_w, c.gpu_w, c.ane_w)),
+                None => (CeilingSource::Unknown, FALLBACK_CEILINGS),
+            },
+        };
+        self.ceiling_source = source;
+        self.max_cpu_w = flags.max_cpu_w.unwrap_or(cpu);
+        self.max_gpu_w = flags.max_gpu_w.unwrap_or(gpu);
+        self.max_ane_w = flags.max_ane_w.unwrap_or(ane);
+        self.max_package_w = self.max_cpu_w + self.max_gpu_w + self.max_ane_w;
+        self.pinned = Domains {
+            cpu: flags.max_cpu_w.is_some(),
+            gpu: flags.max_gpu_w.is_some(),
+            ane: flags.max_ane_w.is_some(),
+        };
+    }
+
+    /// Raise every ceiling not pinned by a flag to the observed `(cpu, gpu,
+    /// ane)` peak it falls below, marking it calibrated. Returns whether any
+    /// ceiling rose.
+    pub(crate) fn raise_ceilings(&mut self, (cpu, gpu, ane): (f64, f64, f64)) -> bool {
+        let raise = |max: &mut f64, pinned: bool, peak: f64, calibrated: &mut bool| {
+            let rises = !pinned && peak > *max;
+            if rises {
+                *max = peak;
+                *calibrated = true;
+            }
+            rises
+        };
+        let rose = [
+            raise(
+                &mut self.max_cpu_w,
+                self.pinned.cpu,
+                cpu,
+                &mut self.calibrated.cpu,
+            ),
+            raise(
+                &mut self.max_gpu_w,
+                self.pinned.gpu,
+                gpu,
+                &mut self.calibrated.gpu,
+            ),
+            raise(
+                &mut self.max_ane_w,
+                self.pinned.ane,
+                ane,
+                &mut self.calibrated.ane,
+            ),
+        ];
+        self.max_package_w = self.max_cpu_w + self.max_gpu_w + self.max_ane_w;
+        rose.contains(&true)
+    }
+
+    /// Set the ceilings back to the resolved `(cpu, gpu, ane)` ones, undoing
+    /// any calibration.
+    pub(crate) fn restore_ceilings(&mut self, (cpu, gpu, ane): (f64, f64, f64)) {
+        (self.max_cpu_w, self.max_gpu_w, self.max_ane_w) = (cpu, gpu, ane);
+        self.max_package_w = cpu + gpu + ane;
+        self.calibrated = Domains::default();
+    }
+
+    /// The entry of `table` for this chip: its brand, and every core count it
+    /// gives. The one giving the most core counts wins; the first on a tie.
+    fn lookup<'a>(&self, table: &'a [ChipCeilings]) -> Option<&'a ChipCeilings> {
+        let counts = |c: &ChipCeilings| {
+            [
+                (c.cpu_cores, self.num_cpu_cores),
+                (c.performance_cores, self.num_performance_cores),
+                (c.efficiency_cores, self.num_efficiency_cores),
+                (c.gpu_cores, self.num_gpu_cores),
+            ]
+        };
+        let mut best: Option<(usize, &ChipCeilings)> = None;
+        for entry in table.iter().filter(|c| c.brand == self.cpu_brand_name) {
+            let counts = counts(entry);
+            if counts
+                .iter()
+                .any(|&(want, have)| want.is_some_and(|w| w != have))
+            {
+                continue;
+            }
+            let keys = counts.iter().filter(|(want, _)| want.is_some()).count();
+            if best.is_none_or(|(most, _)| keys > most) {
+                best = Some((keys, entry));
+            }
+        }
+        best.map(|(_, entry)| entry)
     }
 }
 
-fn cpu_info() -> Result<(String, u16, u16, u16)> {
+/// The built-in power ceilings table.
+fn builtin_ceilings() -> Result<Vec<ChipCeilings>> {
+    #[derive(Deserialize)]
+    struct Table {
+        ceilings: Vec<ChipCeilings>,
+    }
+    let table: Table = toml::from_str(BUILTIN_CEILINGS)
+        .map_err(|e| Error::SocInfoParsingError(format!("ceilings.toml: {e}")))?;
+    Ok(table.ceilings)
+}
+
+pub(crate) fn cpu_info() -> Result<(String, u16, u16, u16)> {
     let binary = "/usr/sbin/sysctl";
     let args = &[
         "-n",
@@ -134,7 +374,99 @@ fn parse_cpu_info(buffer: &str) -> Result<(String, u16, u16, u16)> {
     ))
 }
 
-fn gpu_info() -> Result<u16> {
+/// Most Apple Silicon chips have two performance levels; M5 Pro/Max add a
+/// third.
+const MAX_PERF_LEVELS: usize = 3;
+
+pub(crate) fn system_info() -> Result<SystemInfo> {
+    let binary = "/usr/sbin/sysctl";
+    let mut args: Vec<String> = [
+        "hw.model",
+        "kern.osproductversion",
+        "kern.osversion",
+        "kern.boottime",
+        "hw.memsize",
+        "hw.nperflevels",
+    ]
+    .map(String::from)
+    .to_vec();
+    for level in 0..MAX_PERF_LEVELS {
+        for name in [
+            "name",
+            "logicalcpu",
+            "l1icachesize",
+            "l1dcachesize",
+            "l2cachesize",
+        ] {
+            args.push(format!("hw.perflevel{level}.{name}"));
+        }
+    }
+
+    // Names the kernel lacks are reported on stderr and left out, so the exit
+    // status is not checked.
+    let output = process::Command::new(binary).args(&args).output()?;
+    let buffer = String::from_utf8(output.stdout)?;
+
+    parse_system_info(&buffer)
+}
+
+/// Parse `sysctl` output of the `name: value` form. Missing names keep their
+/// defaults.
+fn parse_system_info(buffer: &str) -> Result<SystemInfo> {
+    let values: HashMap<&str, &str> = buffer
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .collect();
+    let text = |name: &str| {
+        values
+            .get(name)
+            .map_or_else(String::new, |v| v.trim().to_string())
+    };
+    let number = |name: &str| -> Result<u64> {
+        match values.get(name) {
+            Some(v) => Ok(v.trim().parse::<u64>()?),
+            None => Ok(0),
+        }
+    };
+
+    // `{ sec = 1729240000, usec = 123456 } Fri Oct 18 10:26:40 2024`
+    let boot_time_s = match values.get("kern.boottime") {
+        Some(v) => v
+            .split_once("sec = ")
+            .and_then(|(_, rest)| rest.split(',').next())
+            .ok_or_else(|| Error::SocInfoParsingError(v.to_string()))?
+            .trim()
+            .parse::<u64>()?,
+        None => 0,
+    };
+
+    let levels = usize::try_from(number("hw.nperflevels")?)
+        .unwrap_or(0)
+        .min(MAX_PERF_LEVELS);
+    let perf_levels = (0..levels)
+        .map(|i| {
+            let key = |name: &str| format!("hw.perflevel{i}.{name}");
+            Ok(PerfLevel {
+                name: text(&key("name")),
+                cores: u16::try_from(number(&key("logicalcpu"))?).unwrap_or(u16::MAX),
+                l1i_bytes: number(&key("l1icachesize"))?,
+                l1d_bytes: number(&key("l1dcachesize"))?,
+                l2_bytes: number(&key("l2cachesize"))?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(SystemInfo {
+        model: text("hw.model"),
+        os_version: text("kern.osproductversion"),
+        os_build: text("kern.osversion"),
+        boot_time_s,
+        memory_bytes: number("hw.memsize")?,
+        perf_levels,
+    })
+}
+
+pub(crate) fn gpu_info() -> Result<u16> {
     let binary = "/usr/sbin/system_profiler";
     let args = &["-detailLevel", "basic", "SPDisplaysDataType"];
 
@@ -174,6 +506,53 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn parse_system_info_ok() {
+        let buffer = "hw.model: Mac14,6
+kern.osproductversion: 15.1
+kern.osversion: 24B83
+kern.boottime: { sec = 1729240000, usec = 123456 } Fri Oct 18 10:26:40 2024
+hw.memsize: 34359738368
+hw.nperflevels: 2
+hw.perflevel0.name: Performance
+hw.perflevel0.logicalcpu: 8
+hw.perflevel0.l1icachesize: 196608
+hw.perflevel0.l1dcachesize: 131072
+hw.perflevel0.l2cachesize: 16777216
+hw.perflevel1.name: Efficiency
+hw.perflevel1.logicalcpu: 4
+hw.perflevel1.l1icachesize: 131072
+hw.perflevel1.l1dcachesize: 65536
+hw.perflevel1.l2cachesize: 4194304
+";
+
+        let actual = parse_system_info(buffer).expect("Parsing system info should succeed");
+
+        assert_eq!(actual.model, "Mac14,6");
+        assert_eq!(
+            (actual.os_version.as_str(), actual.os_build.as_str()),
+            ("15.1", "24B83")
+        );
+        assert_eq!(actual.boot_time_s, 1_729_240_000);
+        assert_eq!(actual.memory_bytes, 32 << 30);
+        assert_eq!(
+            actual.perf_levels[1],
+            PerfLevel {
+                name: "Efficiency".into(),
+                cores: 4,
+                l1i_bytes: 128 << 10,
+                l1d_bytes: 64 << 10,
+                l2_bytes: 4 << 20,
+            }
+        );
+        assert_eq!(actual.perf_levels.len(), 2);
+
+        // Older kernels without perf levels still parse.
+        let minimal = parse_system_info("hw.model: MacBookAir10,1\n").unwrap();
+        assert!(minimal.perf_levels.is_empty());
+        assert!(parse_system_info("kern.boottime: { usec = 1 }\n").is_err());
+    }
+
     #[test]
     fn parse_gpu_info_ok() {
         let buffer = "Graphics/Displays:
@@ -202,4 +581,119 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    fn chip(brand: &str, performance: u16, efficiency: u16, gpu: u16) -> SocInfo {
+        SocInfo {
+            cpu_brand_name: brand.into(),
+            model: String::new(),
+            os_version: String::new(),
+            os_build: String::new(),
+            boot_time_s: 0,
+            memory_bytes: 0,
+            num_dies: 1,
+            num_cpu_cores: performance + efficiency,
+            num_efficiency_cores: efficiency,
+            num_performance_cores: performance,
+            num_super_cores: 0,
+            perf_levels: Vec::new(),
+            num_gpu_cores: gpu,
+            max_cpu_w: 0.0,
+            max_gpu_w: 0.0,
+            max_ane_w: 0.0,
+            max_package_w: 0.0,
+            ceiling_source: CeilingSource::Unknown,
+            pinned: Domains::default(),
+            calibrated: Domains::default(),
+        }
+    }
+
+    fn ceilings(soc: &SocInfo) -> (f64, f64, f64) {
+        (soc.max_cpu_w, soc.max_gpu_w, soc.max_ane_w)
+    }
+
+    #[test]
+    fn builtin_ceilings_key_on_brand_and_core_counts() {
+        let builtin = builtin_ceilings().expect("embedded table parses");
+        let resolve = |mut soc: SocInfo| {
+            soc.set_ceilings(&[], &builtin, CeilingArgs::default());
+            soc
+        };
+
+        // The full M3 Max, and the binned 10P + 4E / 30-core GPU variant.
+        let full = resolve(chip("Apple M3 Max", 12, 4, 40));
+        assert_eq!(ceilings(&full), (42.0, 66.0, 10.0));
+        assert_eq!(full.max_package_w, 118.0);
+        assert_eq!(full.ceiling_source, CeilingSource::Builtin);
+        let binned = resolve(chip("Apple M3 Max", 10, 4, 30));
+        assert_eq!(ceilings(&binned), (35.0, 50.0, 10.0));
+
+        // Partial matches fall back to the brand entry.
+        let odd = resolve(chip("Apple M3 Max", 10, 4, 40));
+        assert_eq!(ceilings(&odd), (42.0, 66.0, 10.0));
+
+        let unknown = resolve(chip("Apple M9", 8, 4, 12));
+        assert_eq!(ceilings(&unknown), FALLBACK_CEILINGS);
+        assert_eq!(unknown.ceiling_source, CeilingSource::Unknown);
+    }
+
+    #[test]
+    fn config_ceilings_and_flags_override_the_table() {
+        let builtin = builtin_ceilings().expect("embedded table parses");
+        let config: Vec<ChipCeilings> = toml::from_str::<crate::config::FileConfig>(
+            r#"
+            [[ceilings]]
+            brand = "Apple M1"
+            cpu_w = 15.0
+            gpu_w = 12.0
+            ane_w = 6.0
+
+            [[ceilings]]
+            brand = "Apple M9"
+            gpu_cores = 12
+            cpu_w = 30.0
+            gpu_w = 40.0
+            ane_w = 9.0
+            "#,
+        )
+        .expect("config parses")
+        .ceilings;
+
+        let mut m1 = chip("Apple M1", 4, 4, 8);
+        m1.set_ceilings(&config, &builtin, CeilingArgs::default());
+        assert_eq!(ceilings(&m1), (15.0, 12.0, 6.0));
+        assert_eq!(m1.ceiling_source, CeilingSource::Config);
+
+        let mut m9 = chip("Apple M9", 8, 4, 12);
+        let flags = CeilingArgs {
+            max_gpu_w: Some(55.0),
+            ..CeilingArgs::default()
+        };
+        m9.set_ceilings(&config, &builtin, flags);
+        assert_eq!(ceilings(&m9), (30.0, 55.0, 9.0));
+        assert_eq!(m9.max_package_w, 94.0);
+    }
+
+    #[test]
+    fn observed_peaks_raise_unpinned_ceilings() {
+        let mut soc = chip("Apple M9", 8, 4, 12);
+        let flags = CeilingArgs {
+            max_ane_w: Some(5.0),
+            ..CeilingArgs::default()
+        };
+        soc.set_ceilings(&[], &[], flags);
+        assert!(!soc.raise_ceilings((12.0, 20.0, 4.0)));
+        assert!(!soc.calibrated.any());
+
+        // CPU rises, GPU stays above the peak, and the pinned ANE holds.
+        assert!(soc.raise_ceilings((26.5, 18.0, 7.0)));
+        assert_eq!(ceilings(&soc), (26.5, 20.0, 5.0));
+        assert_eq!(soc.max_package_w, 51.5);
+        assert_eq!(
+            soc.calibrated,
+            Domains {
+                cpu: true,
+                ..Domains::default()
+            }
+        );
+    }
 }
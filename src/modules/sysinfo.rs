@@ -7,6 +7,8 @@
 
 use sysinfo::{CpuRefreshKind, MemoryRefreshKind, System};
 
+use super::vm_stat::VmStats;
+
 pub(crate) struct CpuMetrics {
     /// CPU ID (0 - ...)
     pub(crate) id: u16,
@@ -19,6 +21,8 @@ pub(crate) struct MemoryMetrics {
     pub(crate) ram_used: u64,
     pub(crate) swap_total: u64,
     pub(crate) swap_used: u64,
+    /// The `vm_stat` statistics, or why they are missing.
+    pub(crate) vm_stats: Result<VmStats, String>,
 }
 
 pub(crate) struct Metrics {
@@ -57,21 +61,16 @@ impl SystemState {
             .collect();
 
         // Use vm_stat for better memory accounting on macOS, fallback to sysinfo if it fails
-        let memory_metrics = if let Ok(vm_stats) = super::vm_stat::VmStats::collect() {
-            MemoryMetrics {
-                ram_total: self.system.total_memory(), // Use sysinfo for total memory (more reliable)
-                ram_used: vm_stats.activity_monitor_memory_used(),
-                swap_total: self.system.total_swap(),
-                swap_used: self.system.used_swap(),
-            }
-        } else {
-            // Fallback to sysinfo if vm_stat fails
-            MemoryMetrics {
-                ram_total: self.system.total_memory(),
-                ram_used: self.system.used_memory(),
-                swap_total: self.system.total_swap(),
-                swap_used: self.system.used_swap(),
-            }
+        let vm_stats = VmStats::collect().map_err(|err| err.to_string());
+        let memory_metrics = MemoryMetrics {
+            ram_total: self.system.total_memory(), // Use sysinfo for total memory (more reliable)
+            ram_used: match &vm_stats {
+                Ok(vm_stats) => vm_stats.activity_monitor_memory_used(),
+                Err(_) => self.system.used_memory(),
+            },
+            swap_total: self.system.total_swap(),
+            swap_used: self.system.used_swap(),
+            vm_stats,
         };
 
         Metrics {
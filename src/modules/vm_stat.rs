@@ -5,7 +5,12 @@
 use std::collections::HashMap;
 use std::process::Command;
 
-#[derive(Debug, Default)]
+use crate::{Result, error::Error};
+
+/// Header line announcing the page size, up to the size itself.
+const PAGE_SIZE_PREFIX: &str = "Mach Virtual Memory Statistics: (page size of ";
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub(crate) struct VmStats {
     pub page_size: u64,
     pub pages_active: u64,
@@ -18,47 +23,42 @@ pub(crate) struct VmStats {
 }
 
 impl VmStats {
-    /// Collect VM statistics by parsing `vm_stat` output.
-    pub fn collect() -> Result<Self, Box<dyn std::error::Error>> {
-        let output = Command::new("vm_stat").output()?;
-        let output_str = String::from_utf8(output.stdout)?;
+    /// Collect VM statistics by running `vm_stat`.
+    pub fn collect() -> Result<Self> {
+        let output = Command::new("vm_stat")
+            .output()
+            .map_err(Error::VmStatSpawn)?;
+        Self::parse(&String::from_utf8(output.stdout)?)
+    }
 
-        let mut stats = VmStats::default();
+    /// Parse the output of `vm_stat`. Every statistic used here must be
+    /// present and a number; the others are ignored.
+    pub(crate) fn parse(output: &str) -> Result<Self> {
+        let mut page_size = None;
         let mut values = HashMap::new();
-
-        // Parse the output
-        for line in output_str.lines() {
-            if let Some(page_size) =
-                line.strip_prefix("Mach Virtual Memory Statistics: (page size of ")
-            {
-                if let Some(size_str) = page_size.strip_suffix(" bytes)") {
-                    stats.page_size = size_str.parse().unwrap_or(4096);
-                }
-            } else if line.contains(':') {
-                let parts: Vec<&str> = line.split(':').collect();
-                if parts.len() == 2 {
-                    let key = parts[0].trim();
-                    let value_str = parts[1].trim().trim_end_matches('.');
-                    if let Ok(value) = value_str.parse::<u64>() {
-                        values.insert(key, value);
-                    }
-                }
+        for line in output.lines() {
+            if let Some(rest) = line.strip_prefix(PAGE_SIZE_PREFIX) {
+                let size = rest.strip_suffix(" bytes)").unwrap_or(rest);
+                page_size = Some(number("page size", size)?);
+            } else if let Some((key, value)) = line.split_once(':') {
+                values.insert(key.trim(), value.trim().trim_end_matches('.'));
             }
         }
 
-        // Extract the values we need
-        stats.pages_active = values.get("Pages active").copied().unwrap_or(0);
-        stats.pages_inactive = values.get("Pages inactive").copied().unwrap_or(0);
-        stats.pages_wired = values.get("Pages wired down").copied().unwrap_or(0);
-        stats.pages_file_backed = values.get("File-backed pages").copied().unwrap_or(0);
-        stats.pages_anonymous = values.get("Anonymous pages").copied().unwrap_or(0);
-        stats.pages_compressed = values
-            .get("Pages occupied by compressor")
-            .copied()
-            .unwrap_or(0);
-        stats.pages_free = values.get("Pages free").copied().unwrap_or(0);
-
-        Ok(stats)
+        let field = |key: &'static str| {
+            let value = values.get(key).ok_or(Error::VmStatMissingField(key))?;
+            number(key, value)
+        };
+        Ok(Self {
+            page_size: page_size.ok_or(Error::VmStatMissingField("page size"))?,
+            pages_active: field("Pages active")?,
+            pages_inactive: field("Pages inactive")?,
+            pages_wired: field("Pages wired down")?,
+            pages_file_backed: field("File-backed pages")?,
+            pages_anonymous: field("Anonymous pages")?,
+            pages_compressed: field("Pages occupied by compressor")?,
+            pages_free: field("Pages free")?,
+        })
     }
 
     /// Calculate memory usage closer to Activity Monitor's calculation.
@@ -80,3 +80,79 @@ impl VmStats {
             * self.page_size
     }
 }
+
+/// One statistic's value.
+fn number(field: &str, value: &str) -> Result<u64> {
+    value.parse().map_err(|_| Error::VmStatValue {
+        field: field.into(),
+        value: value.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(name: &str) -> String {
+        std::fs::read_to_string(format!("./tests/data/{name}")).expect("failed to read the file")
+    }
+
+    #[test]
+    fn parse_apple_silicon_output() {
+        let stats = VmStats::parse(&read("vm_stat-m1.txt")).unwrap();
+        assert_eq!(
+            stats,
+            VmStats {
+                page_size: 16384,
+                pages_active: 401_284,
+                pages_inactive: 379_842,
+                pages_wired: 198_536,
+                pages_file_backed: 254_197,
+                pages_anonymous: 531_546,
+                pages_compressed: 160_071,
+                pages_free: 31_502,
+            }
+        );
+        assert_eq!(stats.total_memory(), 1_011_164 * 16384);
+        assert_eq!(stats.activity_monitor_memory_used(), 890_153 * 16384);
+    }
+
+    #[test]
+    fn parse_intel_output() {
+        let stats = VmStats::parse(&read("vm_stat-intel.txt")).unwrap();
+        assert_eq!(stats.page_size, 4096);
+        assert_eq!(stats.pages_compressed, 298_417);
+        assert_eq!(stats.total_memory(), 3_093_980 * 4096);
+    }
+
+    #[test]
+    fn parse_errors_name_the_field() {
+        let output = read("vm_stat-m1.txt");
+
+        let no_header = output.lines().skip(1).collect::<Vec<_>>().join("\n");
+        let err = VmStats::parse(&no_header).unwrap_err();
+        assert!(
+            matches!(err, Error::VmStatMissingField("page size")),
+            "{err}"
+        );
+
+        let no_wired = output.replace("Pages wired down:", "Pages wired:");
+        let err = VmStats::parse(&no_wired).unwrap_err();
+        assert_eq!(err.to_string(), "vm_stat output lacks `Pages wired down`");
+
+        let garbled = output.replace("31502.", "31,502.");
+        let err = VmStats::parse(&garbled).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "vm_stat `Pages free` is not a number: `31,502`"
+        );
+
+        let bad_size = output.replace("16384 bytes", "16k bytes");
+        assert!(matches!(
+            VmStats::parse(&bad_size),
+            Err(Error::VmStatValue { field, .. }) if field == "page size"
+        ));
+
+        assert!(VmStats::parse("").is_err());
+    }
+}
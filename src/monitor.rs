@@ -2,34 +2,238 @@
 //!
 //! `run` branches on `--json`: the JSON path streams metrics to stdout with no
 //! UI or channel; the UI path spawns the backend collector on its own OS thread
-//! and runs the iocraft [`PumasApp`] on `smol` (no tokio anywhere).
+//! and runs the iocraft [`PumasApp`] on `smol` (no tokio anywhere). `status`
+//! prints a one-line summary per sample, with no UI either, and `snapshot`
+//! draws a single screen from the same collector without a terminal.
 
-use std::{io::Write, thread};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    thread,
+    time::SystemTime,
+};
 
 use iocraft::prelude::*;
 
 use crate::{
     Result,
-    backend::{self, frame::Frame},
-    config::RunConfig,
+    backend::{
+        self,
+        calibrate::{self, Calibration},
+        control::Control,
+        frame::Frame,
+        persist::{self, Restored},
+    },
+    config::{CeilingArgs, FileConfig, InfoConfig, RunConfig, SnapshotConfig, StatusConfig},
+    doctor,
     error::Error as CrateError,
-    modules::soc::SocInfo,
-    ui::{app_root::PumasApp, theme::Theme},
+    info,
+    modules::soc::{CeilingSource, SocInfo},
+    ownership,
+    status::Template,
+    ui::{
+        app_root::PumasApp,
+        cast::Recorder,
+        export,
+        keymap::Keymap,
+        layout::ZOOM_LEVELS,
+        screen::{Screen, screen},
+        theme::Theme,
+    },
 };
 
 /// Launch the monitor.
 ///
 /// In UI mode, build the frame channel, spawn the collector thread, and run the
-/// fullscreen `PumasApp`. In JSON mode, run the exporter loop directly.
+/// fullscreen (or `--inline`) `PumasApp`. In JSON mode, run the exporter loop
+/// directly.
 pub fn run(args: RunConfig) -> Result<()> {
-    let soc_info = SocInfo::new()?;
+    let file_config = args.file_config()?;
+    let soc = soc_info(&file_config, args.ceilings)?;
 
     let result = if args.json {
-        backend::run_exporter(soc_info, args)
+        backend::run_exporter(soc, args)
     } else {
-        run_ui(soc_info, args)
+        run_ui(soc, args, &file_config)
     };
+    report(result);
+
+    Ok(())
+}
+
+/// Print one status line per sample (`pumas status`). An invalid template is
+/// reported before `powermetrics` starts.
+pub fn status(args: StatusConfig) -> Result<()> {
+    let template = Template::parse(&args.format)?;
+    let soc = soc_info(&args.config.file_config()?, args.ceilings)?;
+    report(backend::run_status(soc, args, template));
+    Ok(())
+}
 
+/// Print the SoC, its power ceilings and the data sources (`pumas info`),
+/// without starting `powermetrics`.
+pub fn info(args: InfoConfig) -> Result<()> {
+    let file_config = args.config.file_config()?;
+    let (mut soc_info, mut calibration) = soc_info(&file_config, args.ceilings)?;
+    if args.reset_ceilings {
+        if calibration.reset(&mut soc_info)? {
+            eprintln!(
+                "Forgot the power peaks learned for {}.",
+                soc_info.cpu_brand_name
+            );
+        } else {
+            eprintln!("No power peaks learned for {}.", soc_info.cpu_brand_name);
+        }
+    }
+    let uptime = soc_info.uptime(SystemTime::now());
+    let sources = info::sources(info::is_root(), Path::exists);
+    let mut out = std::io::stdout().lock();
+    if args.json {
+        writeln!(out, "{}", info::json(&soc_info, uptime, &sources))?;
+    } else {
+        write!(out, "{}", info::text(&soc_info, uptime, &sources))?;
+    }
+    Ok(())
+}
+
+/// Print the environment diagnostics (`pumas doctor`). Returns whether no
+/// check failed.
+pub fn doctor() -> Result<bool> {
+    let checks = doctor::run();
+    write!(std::io::stdout().lock(), "{}", doctor::report(&checks))?;
+    Ok(checks
+        .iter()
+        .all(|check| check.outcome != doctor::Outcome::Fail))
+}
+
+/// Render one screen (`pumas snapshot`): run the collector for `samples`
+/// samples, then draw the last frame as the UI would at the given size, and
+/// write it to stdout or `--output`. A snapshot reads the `--history-file` and
+/// the learned power ceilings but writes neither, so it never clobbers the
+/// state of a full run.
+pub fn snapshot(args: SnapshotConfig) -> Result<()> {
+    let file_config = args.run.file_config()?;
+    let theme = theme(&args.run, &file_config)?;
+    let display = (file_config.thresholds, args.run.smoothing(&file_config));
+    let (soc_info, _) = soc_info(&file_config, args.run.ceilings)?;
+    let restored = restore(&args.run, &soc_info)?;
+    let header = backend::frame::render_header(&soc_info);
+    let soc_rows = backend::frame::render_soc_rows(&soc_info);
+    let (chart, cpu_view) = (args.run.chart_style, args.run.cpu_view);
+    let sample_rate_ms = args.run.sample_rate_ms;
+
+    let (tx, rx) = smol::channel::bounded::<Frame>(4);
+    let (_control_tx, control_rx) = smol::channel::unbounded::<Control>();
+    let run = RunConfig {
+        history_file: None,
+        ..args.run
+    };
+    let collector = thread::spawn(move || {
+        backend::run_collector(
+            (soc_info, Calibration::off()),
+            run,
+            display,
+            restored,
+            tx,
+            control_rx,
+        )
+    });
+    let mut last = None;
+    for _ in 0..args.samples {
+        match rx.recv_blocking() {
+            Ok(frame) => last = Some(frame),
+            Err(_) => break,
+        }
+    }
+    // Closing the channel stops the collector at its next sample.
+    drop(rx);
+    let collected = collector.join().map_err(|_| CrateError::CollectorPanic)?;
+
+    if let Some(frame) = last {
+        let (width, height) = (usize::from(args.width), usize::from(args.height));
+        let s = Screen {
+            frame: &frame,
+            header: &header,
+            soc_rows: &soc_rows,
+            status: None,
+            tab: args.tab.index(),
+            size: (width, height),
+            per_col: ZOOM_LEVELS[0],
+            chart,
+            cpu_view,
+            sample_rate_ms,
+            scroll: 0,
+            pointer: None,
+            help: None,
+            inline: false,
+            theme,
+        };
+        let bytes = export::encode(&screen(&s).render(Some(width)), args.format);
+        match &args.output {
+            Some(path) => {
+                std::fs::write(path, bytes)?;
+                ownership::hand_over(path, path, &[])?;
+            }
+            None => std::io::stdout().lock().write_all(&bytes)?,
+        }
+    }
+    report(collected);
+    Ok(())
+}
+
+/// The theme from the resolved colors, then `--monochrome` / `NO_COLOR` and
+/// `--ascii`.
+fn theme(args: &RunConfig, file_config: &FileConfig) -> Result<Theme> {
+    let mut theme = Theme::from(&args.colors(file_config)?);
+    if args.is_monochrome(std::env::var_os("NO_COLOR").as_deref()) {
+        theme = theme.monochrome();
+    }
+    if args.ascii {
+        theme = theme.ascii();
+    }
+    Ok(theme)
+}
+
+/// Query the SoC and resolve its power ceilings, raised to the peaks learned
+/// in earlier sessions unless `--fixed-ceilings`. Warns when the chip is in no
+/// ceilings table and the flags leave the gauges on generic scales.
+fn soc_info(file_config: &FileConfig, flags: CeilingArgs) -> Result<(SocInfo, Calibration)> {
+    let mut soc = SocInfo::new(&file_config.ceilings, flags)?;
+    let calibration = match calibrate::default_path() {
+        Some(path) if !flags.fixed_ceilings => Calibration::load(path, &mut soc)?,
+        _ => Calibration::off(),
+    };
+    if soc.ceiling_source == CeilingSource::Unknown && !flags.is_complete() {
+        let scales = if flags.fixed_ceilings {
+            "gauges use generic scales"
+        } else {
+            "gauges start from generic scales and adapt to observed peaks"
+        };
+        eprintln!(
+            "warning: no power ceilings known for {} ({} P + {} E cores, {} GPU cores); \
+             {scales}. Add a `[[ceilings]]` entry to the configuration file, or pass \
+             --max-cpu-w, --max-gpu-w and --max-ane-w.",
+            soc.cpu_brand_name,
+            soc.num_performance_cores,
+            soc.num_efficiency_cores,
+            soc.num_gpu_cores
+        );
+    }
+    Ok((soc, calibration))
+}
+
+/// The history saved in the `--history-file`, if any was recorded on this SoC.
+fn restore(args: &RunConfig, soc_info: &SocInfo) -> Result<Option<Restored>> {
+    match &args.history_file {
+        Some(path) => persist::load(path, soc_info),
+        None => Ok(None),
+    }
+}
+
+/// Print a collector error, with the `sudo` hint when `powermetrics` was
+/// refused superuser access.
+fn report(result: Result<()>) {
     if let Err(err) = result {
         eprintln!("{err}");
         if let CrateError::PowermetricsNonZeroExit(status, msg) = &err
@@ -41,44 +245,100 @@ pub fn run(args: RunConfig) -> Result<()> {
             );
         }
     }
-
-    Ok(())
 }
 
-/// Run the iocraft UI: spawn the collector, render `PumasApp` fullscreen, then
-/// surface the collector's result (so the sudo hint still prints when
-/// powermetrics exits non-zero before any frame arrives).
-fn run_ui(soc_info: SocInfo, args: RunConfig) -> Result<()> {
+/// Run the iocraft UI: spawn the collector, render `PumasApp` fullscreen (or
+/// inline), then surface the collector's result (so the sudo hint still prints
+/// when powermetrics exits non-zero before any frame arrives).
+fn run_ui(
+    (soc_info, calibration): (SocInfo, Calibration),
+    args: RunConfig,
+    file_config: &FileConfig,
+) -> Result<()> {
+    // Configuration errors surface here, before the terminal goes fullscreen.
+    let keymap = Keymap::with_overrides(&file_config.keymap)?;
+    let theme = theme(&args, file_config)?;
+    let display = (file_config.thresholds, args.smoothing(file_config));
+    let restored = restore(&args, &soc_info)?;
+
     install_panic_hook();
 
-    let theme = Theme::from(&args.colors());
     let header = backend::frame::render_header(&soc_info);
     let soc_rows = backend::frame::render_soc_rows(&soc_info);
+    let history_size = args.history_size;
+    let sample_rate_ms = args.sample_rate_ms;
+    let chart_style = args.chart_style;
+    let cpu_view = args.cpu_view;
+    let range = args.range;
+    let inline = args.inline;
+    let export_format = args.export_format;
+
+    // The cast file is created up front, so a bad path fails before the
+    // terminal goes fullscreen.
+    let mut recorder = match &args.cast {
+        Some(path) => {
+            let file = File::create(path)?;
+            ownership::hand_over(path, path, &[])?;
+            Some(Recorder::new(std::io::stdout(), BufWriter::new(file))?)
+        }
+        None => None,
+    };
 
     // Bounded(4): event-gated repaint, no free-running animation loop.
     let (tx, rx) = smol::channel::bounded::<Frame>(4);
+    // The reverse direction only carries rare, tiny settings changes.
+    let (control_tx, control_rx) = smol::channel::unbounded::<Control>();
 
-    let collector = thread::spawn(move || backend::run_collector(soc_info, args, tx));
+    let collector = thread::spawn(move || {
+        backend::run_collector(
+            (soc_info, calibration),
+            args,
+            display,
+            restored,
+            tx,
+            control_rx,
+        )
+    });
 
-    smol::block_on(
-        element! {
-            PumasApp(
-                rx: Some(rx),
-                header: Some(header),
-                soc_rows: Some(soc_rows),
-                theme: theme,
-            )
-        }
-        .fullscreen(),
-    )?;
+    let mut app = element! {
+        PumasApp(
+            rx: Some(rx),
+            control: Some(control_tx),
+            header: Some(header),
+            soc_rows: Some(soc_rows),
+            theme: theme,
+            keymap: Some(keymap),
+            history_size: history_size,
+            sample_rate_ms: sample_rate_ms,
+            chart_style: chart_style,
+            cpu_view: cpu_view,
+            range: range,
+            inline: inline,
+            export_format: export_format,
+        )
+    };
+    // Inline rendering leaves the last frame in the scrollback on exit.
+    let mut render = if inline {
+        app.render_loop()
+    } else {
+        app.fullscreen()
+    };
+    // With `--cast`, iocraft writes through the recorder, which passes
+    // everything on to stdout.
+    if let Some(recorder) = &mut recorder {
+        render = render.stdout(recorder);
+    }
+    smol::block_on(render)?;
+    let recorded = recorder.map_or(Ok(()), Recorder::finish);
 
     // The UI has exited (user quit, or the collector closed the channel).
     // Joining yields the collector's `Result`; an `Err` here drives the
     // post-run sudo-hint handling in `run`.
-    match collector.join() {
-        Ok(res) => res,
-        Err(_) => Ok(()), // collector panicked; the panic hook already logged it
+    // A collector panic was already logged by the panic hook.
+    if let Ok(res) = collector.join() {
+        res?;
     }
+    Ok(recorded?)
 }
 
 /// Install a panic hook that appends to a log file, since the fullscreen TUI
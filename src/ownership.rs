@@ -0,0 +1,97 @@
+//! Ownership of the files pumas writes.
+//!
+//! pumas runs under `sudo`, which keeps the user's `HOME` and working
+//! directory. The files it writes there (the learned power ceilings, the
+//! `--history-file`, screen exports, snapshots and cast recordings) and the
+//! directories created for them are handed to the `SUDO_UID` / `SUDO_GID` user
+//! when they land in a tree that user owns, so they stay writable without
+//! root.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Create the directories missing above `path`, returning the ones created.
+pub(crate) fn create_parent(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let created = dir
+        .ancestors()
+        .take_while(|d| !d.as_os_str().is_empty() && !d.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    fs::create_dir_all(dir)?;
+    Ok(created)
+}
+
+/// Hand `written` (the file at `path`, or the temporary one renamed to it) and
+/// the `created` directories to the `sudo` user, if `path` lands in their
+/// tree. Without `sudo`, there is nothing to do.
+pub(crate) fn hand_over(path: &Path, written: &Path, created: &[PathBuf]) -> io::Result<()> {
+    let owner = sudo_owner(
+        std::env::var("SUDO_UID").ok().as_deref(),
+        std::env::var("SUDO_GID").ok().as_deref(),
+    );
+    if let Some((uid, gid)) = owner.filter(|&(uid, _)| owned_by(path, uid, created)) {
+        for entry in created.iter().map(PathBuf::as_path).chain([written]) {
+            std::os::unix::fs::chown(entry, Some(uid), Some(gid))?;
+        }
+    }
+    Ok(())
+}
+
+/// The user and group that ran `sudo pumas`, from `SUDO_UID` / `SUDO_GID`.
+fn sudo_owner(uid: Option<&str>, gid: Option<&str>) -> Option<(u32, u32)> {
+    Some((uid?.parse().ok()?, gid?.parse().ok()?))
+}
+
+/// Whether the closest directory above `path` that pumas did not create is
+/// owned by `uid`, i.e. the file lands in that user's tree. A bare file name
+/// lands in the working directory.
+fn owned_by(path: &Path, uid: u32, created: &[PathBuf]) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    path.ancestors()
+        .skip(1)
+        .map(|d| {
+            if d.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                d
+            }
+        })
+        .find(|d| !created.iter().any(|c| c == d))
+        .and_then(|d| fs::metadata(d).ok())
+        .is_some_and(|meta| meta.uid() == uid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_go_to_the_sudo_user_in_their_tree() {
+        use std::os::unix::fs::MetadataExt;
+
+        assert_eq!(sudo_owner(Some("501"), Some("20")), Some((501, 20)));
+        assert_eq!(sudo_owner(Some("501"), None), None);
+        assert_eq!(sudo_owner(Some("x"), Some("20")), None);
+
+        let root = std::env::temp_dir().join(format!("pumas-{}-owner", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir(&root).unwrap();
+        let uid = fs::metadata(&root).unwrap().uid();
+        let file = root.join("state/pumas/ceilings.json");
+        let created = create_parent(&file).unwrap();
+        assert_eq!(created, [root.join("state/pumas"), root.join("state")]);
+        assert!(owned_by(&file, uid, &created));
+        assert!(!owned_by(&file, uid + 1, &created));
+        assert!(owned_by(&root.join("ceilings.json"), uid, &[]));
+        assert!(create_parent(&file).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&root);
+
+        let cwd = fs::metadata(".").unwrap().uid();
+        assert!(owned_by(Path::new("pumas.svg"), cwd, &[]));
+    }
+}
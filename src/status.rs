@@ -0,0 +1,370 @@
+//! One-line status output (`pumas status`) for tmux, SketchyBar and other
+//! status bars.
+//!
+//! A [`Template`] is parsed once from the `--format` string and rendered per
+//! sample. Literal text is copied as-is (`{{` and `}}` escape braces); a
+//! placeholder names a metric and an optional format:
+//!
+//! - `{cpu.pct}` — the bare number in its natural precision (`42`);
+//! - `{package_w:.2}` — the bare number with 2 decimals (`6.48`);
+//! - `{package_w:unit}` — the number and its unit, as the UI prints it
+//!   (`6.48 W`);
+//! - `{cpu.pct:spark}` — a mini sparkline of the last 8 samples (`▂▃▅▇`),
+//!   or of the last `N` with `spark16`.
+//!
+//! Sparkline history lives in the template, one ring per placeholder, so a
+//! template rendered once per sample needs no other state.
+
+use std::collections::VecDeque;
+
+use crate::{
+    Result,
+    error::Error,
+    metrics::{ClusterMetrics, Metrics},
+    modules::soc::SocInfo,
+    units,
+};
+
+/// Sparkline levels: index 0 = zero, 1..=8 = increasing block heights.
+const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Samples in a `spark` placeholder without an explicit length.
+const SPARK_LENGTH: usize = 8;
+
+/// What a metric measures, which picks its natural precision, unit formatter
+/// and sparkline scale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    /// Percent, `0..=100`.
+    Percent,
+    /// Watts, scaled to the SoC ceiling.
+    Watts,
+    /// MHz, scaled to the maximum DVFM frequency.
+    Megahertz,
+    /// GiB, scaled to the total.
+    Gibibytes,
+    /// Free text (no number, no sparkline).
+    Text,
+}
+
+/// A metric a placeholder can name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Metric {
+    CpuPct,
+    EcpuPct,
+    PcpuPct,
+    ScpuPct,
+    GpuPct,
+    GpuMhz,
+    AnePct,
+    CpuW,
+    GpuW,
+    AneW,
+    PackageW,
+    RamPct,
+    RamGib,
+    SwapPct,
+    SwapGib,
+    Thermal,
+}
+
+/// Every metric, by placeholder name.
+const METRICS: [(&str, Metric); 16] = [
+    ("cpu.pct", Metric::CpuPct),
+    ("ecpu.pct", Metric::EcpuPct),
+    ("pcpu.pct", Metric::PcpuPct),
+    ("scpu.pct", Metric::ScpuPct),
+    ("gpu.pct", Metric::GpuPct),
+    ("gpu.mhz", Metric::GpuMhz),
+    ("ane.pct", Metric::AnePct),
+    ("cpu_w", Metric::CpuW),
+    ("gpu_w", Metric::GpuW),
+    ("ane_w", Metric::AneW),
+    ("package_w", Metric::PackageW),
+    ("ram.pct", Metric::RamPct),
+    ("ram.gib", Metric::RamGib),
+    ("swap.pct", Metric::SwapPct),
+    ("swap.gib", Metric::SwapGib),
+    ("thermal", Metric::Thermal),
+];
+
+/// Bytes per GiB.
+const GIB: f64 = (1u64 << 30) as f64;
+
+/// Mean active ratio of every core in `clusters`, in percent (0 without
+/// cores).
+fn cores_pct<'a>(clusters: impl Iterator<Item = &'a ClusterMetrics>) -> f64 {
+    let (sum, n) = clusters
+        .flat_map(|c| &c.cpus)
+        .fold((0.0, 0usize), |(sum, n), cpu| {
+            (sum + cpu.active_ratio, n + 1)
+        });
+    if n == 0 { 0.0 } else { 100.0 * sum / n as f64 }
+}
+
+impl Metric {
+    fn parse(name: &str) -> Result<Self> {
+        METRICS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, m)| *m)
+            .ok_or_else(|| {
+                let names: Vec<&str> = METRICS.iter().map(|(n, _)| *n).collect();
+                Error::Template(format!(
+                    "unknown metric `{name}`, expected one of: {}",
+                    names.join(", ")
+                ))
+            })
+    }
+
+    fn kind(self) -> Kind {
+        match self {
+            Self::CpuPct
+            | Self::EcpuPct
+            | Self::PcpuPct
+            | Self::ScpuPct
+            | Self::GpuPct
+            | Self::AnePct
+            | Self::RamPct
+            | Self::SwapPct => Kind::Percent,
+            Self::GpuMhz => Kind::Megahertz,
+            Self::CpuW | Self::GpuW | Self::AneW | Self::PackageW => Kind::Watts,
+            Self::RamGib | Self::SwapGib => Kind::Gibibytes,
+            Self::Thermal => Kind::Text,
+        }
+    }
+
+    /// The current value and the top of its sparkline scale.
+    fn value(self, m: &Metrics, soc: &SocInfo) -> (f64, f64) {
+        let c = &m.consumption;
+        let mem = &m.memory;
+        match self {
+            Self::CpuPct => (
+                cores_pct(
+                    m.e_clusters
+                        .iter()
+                        .chain(&m.p_clusters)
+                        .chain(&m.s_clusters),
+                ),
+                100.0,
+            ),
+            Self::EcpuPct => (cores_pct(m.e_clusters.iter()), 100.0),
+            Self::PcpuPct => (cores_pct(m.p_clusters.iter()), 100.0),
+            Self::ScpuPct => (cores_pct(m.s_clusters.iter()), 100.0),
+            Self::GpuPct => (100.0 * m.gpu.active_ratio, 100.0),
+            Self::GpuMhz => (m.gpu.freq_mhz, f64::from(m.gpu.max_frequency())),
+            Self::AnePct => (100.0 * f64::from(c.ane_w) / soc.max_ane_w, 100.0),
+            Self::CpuW => (f64::from(c.cpu_w), soc.max_cpu_w),
+            Self::GpuW => (f64::from(c.gpu_w), soc.max_gpu_w),
+            Self::AneW => (f64::from(c.ane_w), soc.max_ane_w),
+            Self::PackageW => (f64::from(c.package_w), soc.max_package_w),
+            Self::RamPct => (100.0 * mem.ram_usage_ratio(), 100.0),
+            Self::RamGib => (mem.ram_used as f64 / GIB, mem.ram_total as f64 / GIB),
+            Self::SwapPct => (100.0 * mem.swap_usage_ratio(), 100.0),
+            Self::SwapGib => (mem.swap_used as f64 / GIB, mem.swap_total as f64 / GIB),
+            Self::Thermal => (0.0, 0.0),
+        }
+    }
+}
+
+/// How a placeholder prints its metric.
+#[derive(Clone, Debug, PartialEq)]
+enum Format {
+    /// The bare number in the metric's natural precision.
+    Default,
+    /// The bare number with this many decimals.
+    Precision(usize),
+    /// The number and its unit, via [`units`].
+    Unit,
+    /// A sparkline of the last `len` samples.
+    Spark { len: usize, samples: VecDeque<f64> },
+}
+
+impl Format {
+    fn parse(spec: &str, metric: Metric) -> Result<Self> {
+        let bad = |why: &str| Error::Template(format!("`{spec}`: {why}"));
+        let format = if spec.is_empty() {
+            Self::Default
+        } else if spec == "unit" {
+            Self::Unit
+        } else if let Some(digits) = spec.strip_prefix('.') {
+            Self::Precision(digits.parse().map_err(|_| bad("expected `.N`"))?)
+        } else if let Some(len) = spec.strip_prefix("spark") {
+            let len = match len {
+                "" => SPARK_LENGTH,
+                len => len
+                    .parse()
+                    .map_err(|_| bad("expected `spark` or `sparkN`"))?,
+            };
+            if len == 0 {
+                return Err(bad("a sparkline needs at least one sample"));
+            }
+            Self::Spark {
+                len,
+                samples: VecDeque::with_capacity(len),
+            }
+        } else {
+            return Err(bad("expected `.N`, `unit`, `spark` or `sparkN`"));
+        };
+        if metric.kind() == Kind::Text && format != Self::Default {
+            return Err(bad("text metrics take no format"));
+        }
+        Ok(format)
+    }
+}
+
+/// One piece of a template.
+#[derive(Clone, Debug, PartialEq)]
+enum Part {
+    Text(String),
+    Field { metric: Metric, format: Format },
+}
+
+/// A parsed `--format` template.
+#[derive(Clone, Debug)]
+pub(crate) struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Parse `source`, e.g. `"{cpu.pct}% {package_w:.1}W {thermal}"`.
+    pub(crate) fn parse(source: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut field = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        field.push(c);
+                    }
+                    if !closed {
+                        return Err(Error::Template(format!("unclosed `{{{field}`")));
+                    }
+                    let (name, spec) = field.split_once(':').unwrap_or((&field, ""));
+                    let metric = Metric::parse(name.trim())?;
+                    let format = Format::parse(spec.trim(), metric)?;
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                    parts.push(Part::Field { metric, format });
+                }
+                '}' => return Err(Error::Template("unmatched `}`, write `}}`".to_string())),
+                ch => text.push(ch),
+            }
+        }
+        parts.push(Part::Text(text));
+        parts.retain(|p| *p != Part::Text(String::new()));
+        Ok(Self { parts })
+    }
+
+    /// Render one sample, recording it into every sparkline.
+    pub(crate) fn render(&mut self, metrics: &Metrics, soc: &SocInfo) -> String {
+        let mut line = String::new();
+        for part in &mut self.parts {
+            match part {
+                Part::Text(text) => line.push_str(text),
+                Part::Field { metric, format } => {
+                    line.push_str(&field(*metric, format, metrics, soc));
+                }
+            }
+        }
+        line
+    }
+}
+
+/// Format one placeholder.
+fn field(metric: Metric, format: &mut Format, metrics: &Metrics, soc: &SocInfo) -> String {
+    let kind = metric.kind();
+    if kind == Kind::Text {
+        return metrics.thermal_pressure.clone();
+    }
+    let (value, max) = metric.value(metrics, soc);
+    match format {
+        Format::Default => {
+            let precision = match kind {
+                Kind::Watts | Kind::Gibibytes => 1,
+                _ => 0,
+            };
+            format!("{value:.precision$}")
+        }
+        Format::Precision(precision) => format!("{value:.*}", *precision),
+        Format::Unit => match kind {
+            Kind::Percent => units::percent1(value),
+            Kind::Watts => units::watts2(value),
+            Kind::Megahertz => units::mhz(value),
+            Kind::Gibibytes => units::bibytes1(value * GIB),
+            Kind::Text => unreachable!("text metrics return before formatting"),
+        },
+        Format::Spark { len, samples } => {
+            if samples.len() == *len {
+                samples.pop_front();
+            }
+            samples.push_back(value);
+            samples.iter().map(|&v| bar(v, max)).collect()
+        }
+    }
+}
+
+/// The sparkline glyph of `value` on a `0..=max` scale.
+fn bar(value: f64, max: f64) -> char {
+    if max <= 0.0 {
+        return BARS[0];
+    }
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let level = ((value / max).clamp(0.0, 1.0) * 8.0).round() as usize;
+    BARS[level]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::tests::{m1_metrics, test_soc};
+
+    #[test]
+    fn renders_numbers_units_and_text() {
+        let (soc, mut m) = (test_soc(), m1_metrics());
+        m.consumption.package_w = 6.482;
+        m.gpu.active_ratio = 0.421;
+        let mut t = Template::parse("{gpu.pct}% {package_w:.2}W {{{package_w:unit}}} {thermal}")
+            .expect("valid template");
+        assert_eq!(
+            t.render(&m, &soc),
+            format!("42% 6.48W {{6.48 W}} {}", m.thermal_pressure)
+        );
+    }
+
+    #[test]
+    fn sparklines_keep_the_last_samples() {
+        let (soc, mut m) = (test_soc(), m1_metrics());
+        let mut t = Template::parse("[{gpu.pct:spark3}]").expect("valid template");
+        let mut line = String::new();
+        for ratio in [0.0, 0.5, 1.0, 0.25] {
+            m.gpu.active_ratio = ratio;
+            line = t.render(&m, &soc);
+        }
+        assert_eq!(line, "[▄█▂]");
+    }
+
+    #[test]
+    fn parse_errors_name_the_problem() {
+        let err = |s: &str| Template::parse(s).expect_err(s).to_string();
+        assert!(err("{cpu.watts}").contains("unknown metric `cpu.watts`"));
+        assert!(err("{cpu.pct:.x}").contains("expected `.N`"));
+        assert!(err("{thermal:spark}").contains("text metrics take no format"));
+        assert!(err("{cpu.pct").contains("unclosed"));
+        assert!(err("100}%").contains("unmatched `}`"));
+    }
+}
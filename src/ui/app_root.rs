@@ -1,37 +1,83 @@
 //! Root iocraft component: the one-directional data plane's frontend half.
 //!
-//! `PumasApp` owns the UI-side state (current [`Frame`], selected tab, exit
+//! `PumasApp` owns the UI-side state (frame [`Scrollback`], selected tab, exit
 //! flag), drains the backend channel in a single `use_future`, and handles the
-//! keyboard. While no frame has arrived it shows the splash; once frames flow it
-//! renders the title bar, the tab bar, and the selected tab's view
-//!. All five tabs (Overview, CPU, GPU, Memory,
-//! SoC) are wired to their real views.
+//! keyboard through the [`Keymap`] and the mouse: clicking a tab label selects
+//! it, the wheel cycles tabs over the tab bar and scrolls the tab body
+//! elsewhere, and pointing at a sparkline on the Overview, CPU or GPU tab shows
+//! that sample's value and age in a tooltip. While no frame has arrived it
+//! shows the splash; once frames flow it renders the title bar, the tab bar,
+//! and the selected tab's view
+//!. All six tabs (Overview, CPU, GPU, Memory,
+//! SoC, Power) are wired to their real views. The composition itself is
+//! [`screen`], so the export key saves exactly what is on screen.
+//!
+//! Inline (`--inline`) it draws below the prompt instead: the title bar and
+//! the Overview only, at most [`INLINE_MAX_HEIGHT`] rows and no taller than
+//! the Overview needs, so the last frame stays in the scrollback on exit. The
+//! mouse is left to the terminal there.
+
+use std::time::SystemTime;
 
 use iocraft::prelude::*;
-use smol::channel::Receiver;
+use smol::channel::{Receiver, Sender};
 
 use crate::{
-    backend::frame::{Frame, RenderedHeader, SocRows},
+    backend::{
+        control::{Control, step_sample_rate},
+        frame::{Frame, RenderedHeader, SocRows},
+    },
+    config::{ChartStyle, CpuView, ExportFormat, TimeRange},
     ui::{
-        components::{tab_bar::tab_bar, title_bar::title_bar},
+        components::tab_bar::TAB_TITLES,
+        export,
+        keymap::{Action, Keymap},
+        layout::{
+            BODY_TOP, INLINE_MAX_HEIGHT, MIN_HEIGHT, MIN_WIDTH, TAB_BAR_ROWS, ZOOM_LEVELS, tab_at,
+        },
+        screen::{Screen, content_height, screen},
+        scrollback::Scrollback,
         theme::Theme,
-        views::{cpu::cpu, gpu::gpu, memory::memory, overview::overview, soc::soc, splash::splash},
+        views::{splash::splash, too_small::too_small},
     },
 };
 
-/// Number of tabs (Overview, CPU, GPU, Memory, SoC).
-const NUM_TABS: usize = 5;
+/// Number of tabs (Overview, CPU, GPU, Memory, SoC, Power).
+const NUM_TABS: usize = TAB_TITLES.len();
+
+/// Rows scrolled per mouse-wheel notch.
+const SCROLL_STEP: usize = 3;
 
 #[derive(Default, Props)]
 pub(crate) struct PumasAppProps {
     /// Backend frame stream. Taken once into the draining `use_future`.
     pub rx: Option<Receiver<Frame>>,
+    /// Control requests to the collector (sample rate, history size, time
+    /// range).
+    pub control: Option<Sender<Control>>,
     /// Session-static title-bar strings.
     pub header: Option<RenderedHeader>,
     /// Session-static SoC-tab rows (built once, taken into state on first render).
     pub soc_rows: Option<SocRows>,
     /// Resolved theme colors.
     pub theme: Theme,
+    /// Key bindings (defaults plus configured overrides), taken once into state.
+    pub keymap: Option<Keymap>,
+    /// Startup `--history-size`: the scrollback depth and the minimum history
+    /// the collector keeps when zooming.
+    pub history_size: usize,
+    /// Startup `--sample-rate` (ms).
+    pub sample_rate_ms: u16,
+    /// Startup `--chart-style`.
+    pub chart_style: ChartStyle,
+    /// Startup `--cpu-view`.
+    pub cpu_view: CpuView,
+    /// Startup `--range`.
+    pub range: TimeRange,
+    /// `--inline`: render the Overview below the prompt, not fullscreen.
+    pub inline: bool,
+    /// `--export-format`: how the export key saves the screen.
+    pub export_format: ExportFormat,
 }
 
 #[component]
@@ -39,11 +85,34 @@ pub(crate) fn PumasApp(
     mut hooks: Hooks,
     props: &mut PumasAppProps,
 ) -> impl Into<AnyElement<'static>> {
-    let (width, height) = hooks.use_terminal_size();
+    let (width, term_height) = hooks.use_terminal_size();
+    let inline = props.inline;
+    // Inline, keep a row for the prompt the output scrolls up from.
+    let height = if inline {
+        term_height.saturating_sub(1).min(INLINE_MAX_HEIGHT as u16)
+    } else {
+        term_height
+    };
     let theme = props.theme;
+    let history_size = props.history_size;
+    let initial_rate = props.sample_rate_ms;
+    let initial_chart = props.chart_style;
+    let initial_cpu_view = props.cpu_view;
+    let initial_range = props.range;
+    let export_format = props.export_format;
 
-    let mut frame_state = hooks.use_state(|| Option::<Frame>::None);
+    let mut frames = hooks.use_state(move || Scrollback::new(history_size));
     let mut tab = hooks.use_state(|| 0usize);
+    let mut sample_rate = hooks.use_state(move || initial_rate);
+    let mut zoom = hooks.use_state(|| 0usize);
+    let mut chart = hooks.use_state(move || initial_chart);
+    let mut cpu_view = hooks.use_state(move || initial_cpu_view);
+    let mut range = hooks.use_state(move || initial_range);
+    let mut show_help = hooks.use_state(|| false);
+    let mut scroll = hooks.use_state(|| 0usize);
+    let mut pointer = hooks.use_state(|| None::<(usize, usize)>);
+    let mut export = hooks.use_state(|| false);
+    let mut notice = hooks.use_state(|| None::<String>);
     let mut should_exit = hooks.use_state(|| false);
     let mut system = hooks.use_context_mut::<SystemContext>();
 
@@ -55,46 +124,161 @@ pub(crate) fn PumasApp(
     let soc_rows = props.soc_rows.take();
     let soc_rows = hooks.use_state(move || soc_rows.unwrap_or_default());
 
+    let keymap = props.keymap.take();
+    let keymap = hooks.use_state(move || keymap.unwrap_or_default());
+
+    // The control sender is needed by every event closure: keep it in state.
+    let control = props.control.take();
+    let control = hooks.use_state(move || control);
+    let send = move |request: Control| {
+        if let Some(tx) = control.read().as_ref() {
+            // Unbounded: only fails once the collector is gone, and then the
+            // frame channel closing drives the exit anyway.
+            let _ = tx.try_send(request);
+        }
+    };
+
     // Drain the backend channel. `use_future` spawns exactly once, so taking
     // the receiver out of props here is safe (it is `Some` only on first
     // render). When the collector drops its sender (error or shutdown),
-    // `recv()` errors, we fall through and request exit.
+    // `recv()` errors, we fall through and request exit. Frames keep flowing
+    // into the scrollback while paused; only the rendered one is frozen.
     let rx = props.rx.take();
     hooks.use_future(async move {
         if let Some(rx) = rx {
             while let Ok(frame) = rx.recv().await {
-                frame_state.set(Some(frame));
+                frames.write().push(frame);
             }
         }
         should_exit.set(true);
     });
 
-    // Keyboard: quit + tab navigation.
+    // The frame to render, and how far its tab body can scroll.
+    let (current, status) = {
+        let frames = frames.read();
+        (frames.current().cloned(), frames.status())
+    };
+    let w = usize::from(width);
+    // Inline there is no tab bar: the body starts under the title bar.
+    let body_top = if inline { 1 } else { BODY_TOP };
+    let body_h = usize::from(height).saturating_sub(body_top);
+    let content_h = current.as_ref().map_or(0, |f| {
+        content_height(
+            tab.get(),
+            (f, &soc_rows.read()),
+            (w, body_h),
+            (chart.get(), cpu_view.get()),
+        )
+    });
+    let max_scroll = content_h.saturating_sub(body_h);
+    let page = body_h.saturating_sub(1).max(1);
+
+    // Input. The mouse selects/cycles tabs, scrolls the body, and moves the
+    // hover pointer. Every key goes through the keymap; while the help overlay
+    // is open, `Esc` closes it instead of quitting.
     hooks.use_terminal_events(move |event| {
-        if let TerminalEvent::Key(KeyEvent {
-            code,
-            kind,
-            modifiers,
-            ..
-        }) = event
-        {
-            if kind == KeyEventKind::Release {
+        let mut select_tab = move |i: usize| {
+            if inline {
                 return;
             }
-            match code {
-                KeyCode::Char('q') | KeyCode::Char('x') | KeyCode::Esc => should_exit.set(true),
-                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
-                    should_exit.set(true);
-                }
-                KeyCode::Left | KeyCode::BackTab => {
-                    let i = tab.get();
-                    tab.set(if i == 0 { NUM_TABS - 1 } else { i - 1 });
+            tab.set(i);
+            scroll.set(0);
+            pointer.set(None);
+        };
+        let key = match event {
+            TerminalEvent::Key(key) => key,
+            // Inline, the mouse is not captured, and its rows would not map
+            // onto the screen below the prompt anyway.
+            TerminalEvent::FullscreenMouse(_) if inline => return,
+            TerminalEvent::FullscreenMouse(FullscreenMouseEvent {
+                kind, column, row, ..
+            }) => {
+                let (column, row) = (usize::from(column), usize::from(row));
+                match kind {
+                    MouseEventKind::Down(_) => match tab_at(column, row) {
+                        Some(i) => select_tab(i),
+                        None => pointer.set(Some((column, row))),
+                    },
+                    MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+                        pointer.set(Some((column, row)));
+                    }
+                    MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                        let down = kind == MouseEventKind::ScrollDown;
+                        if TAB_BAR_ROWS.contains(&row) {
+                            let i = tab.get();
+                            select_tab(if down {
+                                (i + 1) % NUM_TABS
+                            } else {
+                                (i + NUM_TABS - 1) % NUM_TABS
+                            });
+                        } else if down {
+                            scroll.set((scroll.get() + SCROLL_STEP).min(max_scroll));
+                        } else {
+                            scroll.set(scroll.get().min(max_scroll).saturating_sub(SCROLL_STEP));
+                        }
+                    }
+                    _ => {}
                 }
-                KeyCode::Right | KeyCode::Tab => {
-                    tab.set((tab.get() + 1) % NUM_TABS);
+                return;
+            }
+            _ => return,
+        };
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+        // An export notice stays up until the next key.
+        if notice.read().is_some() {
+            notice.set(None);
+        }
+        if show_help.get() && key.code == KeyCode::Esc {
+            show_help.set(false);
+            return;
+        }
+        let Some(action) = keymap.read().action(&key) else {
+            return;
+        };
+        match action {
+            Action::Quit => should_exit.set(true),
+            Action::Help => show_help.set(!show_help.get()),
+            Action::Pause => frames.write().toggle_pause(),
+            Action::StepBack => frames.write().step_back(),
+            Action::StepForward => frames.write().step_forward(),
+            Action::Faster | Action::Slower => {
+                let rate = step_sample_rate(sample_rate.get(), action == Action::Faster);
+                if rate != sample_rate.get() {
+                    sample_rate.set(rate);
+                    send(Control::SampleRate(rate));
                 }
-                _ => {}
             }
+            Action::ZoomIn | Action::ZoomOut => {
+                let level = if action == Action::ZoomOut {
+                    (zoom.get() + 1).min(ZOOM_LEVELS.len() - 1)
+                } else {
+                    zoom.get().saturating_sub(1)
+                };
+                zoom.set(level);
+                // Ship enough history to fill a full-width sparkline.
+                let per_col = ZOOM_LEVELS[level];
+                send(Control::Zoom {
+                    per_col,
+                    span: history_size.max(per_col * w),
+                });
+            }
+            Action::ChartStyle => chart.set(chart.get().toggled()),
+            Action::CpuView => cpu_view.set(cpu_view.get().next()),
+            Action::Range => {
+                range.set(range.get().next());
+                send(Control::Range(range.get()));
+            }
+            Action::ResetPeaks => send(Control::ResetPeaks),
+            Action::Export => export.set(true),
+            Action::PrevTab => select_tab((tab.get() + NUM_TABS - 1) % NUM_TABS),
+            Action::NextTab => select_tab((tab.get() + 1) % NUM_TABS),
+            Action::Tab(i) => select_tab(i),
+            Action::ScrollUp => scroll.set(scroll.get().min(max_scroll).saturating_sub(1)),
+            Action::ScrollDown => scroll.set((scroll.get() + 1).min(max_scroll)),
+            Action::PageUp => scroll.set(scroll.get().min(max_scroll).saturating_sub(page)),
+            Action::PageDown => scroll.set((scroll.get() + page).min(max_scroll)),
         }
     });
 
@@ -104,13 +288,25 @@ pub(crate) fn PumasApp(
     }
 
     let header = header.read();
-    let program_name = header.program_name.clone();
-    let machine_desc = header.machine_desc.clone();
-    let w = usize::from(width);
+
+    if w < MIN_WIDTH || (!inline && usize::from(height) < MIN_HEIGHT) {
+        return element! {
+            View(width: u32::from(width), height: u32::from(height)) {
+                #(vec![too_small(w, usize::from(height))])
+            }
+        }
+        .into_any();
+    }
 
     // Splash full-screen until the first frame arrives (mirrors the original
     // startup screen, which replaces the whole UI — no title/tab bar).
-    let Some(frame) = frame_state.read().clone() else {
+    let Some(frame) = current else {
+        if inline {
+            return element! {
+                Text(content: "Starting up...", color: theme.accent, wrap: TextWrap::NoWrap)
+            }
+            .into_any();
+        }
         return element! {
             View(width: u32::from(width), height: u32::from(height)) {
                 #(vec![splash(w, usize::from(height))])
@@ -119,30 +315,36 @@ pub(crate) fn PumasApp(
         .into_any();
     };
 
-    let active = tab.get();
-    let body: AnyElement<'static> = match active {
-        0 => overview(&frame.overview, w, theme),
-        1 => cpu(&frame.cpu, w, theme),
-        2 => gpu(&frame.gpu, w, theme),
-        3 => memory(&frame.memory, w, theme),
-        4 => soc(&soc_rows.read(), w, theme),
-        _ => overview(&frame.overview, w, theme),
+    let help_rows = show_help.get().then(|| keymap.read().help_rows());
+    let soc_rows = soc_rows.read();
+    let s = Screen {
+        frame: &frame,
+        header: &header,
+        soc_rows: &soc_rows,
+        status: status.or_else(|| notice.read().clone()),
+        tab: tab.get(),
+        size: (w, usize::from(height)),
+        per_col: ZOOM_LEVELS[zoom.get()],
+        chart: chart.get(),
+        cpu_view: cpu_view.get(),
+        sample_rate_ms: sample_rate.get(),
+        scroll: scroll.get(),
+        pointer: pointer.get(),
+        help: help_rows,
+        inline,
+        theme,
     };
 
-    let chrome = vec![
-        title_bar(program_name, machine_desc, theme.accent, w),
-        tab_bar(active, theme.accent, w),
-        body,
-    ];
-
-    element! {
-        View(
-            flex_direction: FlexDirection::Column,
-            width: u32::from(width),
-            height: u32::from(height),
-        ) {
-            #(chrome)
-        }
+    // Save the screen as drawn, at render time like the exit.
+    if export.get() {
+        export.set(false);
+        let canvas = screen(&s).render(Some(w));
+        notice.set(Some(
+            match export::save(&canvas, export_format, SystemTime::now()) {
+                Ok(path) => format!("saved {}", path.display()),
+                Err(err) => format!("export failed: {err}"),
+            },
+        ));
     }
-    .into_any()
+    screen(&s)
 }
@@ -0,0 +1,184 @@
+//! `--cast` recording: the UI's terminal output as an [asciicast v2] file.
+//!
+//! A [`Recorder`] sits between iocraft and stdout. Everything iocraft writes
+//! (alternate screen, cursor moves, the ANSI rows of each frame) goes to the
+//! terminal unchanged and is also buffered; iocraft flushes once per frame, at
+//! the end of its synchronized update, so each flush becomes one `"o"` event
+//! stamped with the time since the recording started. A terminal resize
+//! between frames is recorded as an `"r"` event first, so players resize
+//! before drawing.
+//!
+//! Recording never breaks the UI: the first error writing the cast file stops
+//! the recording and is returned by [`Recorder::finish`] once the UI exits.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Terminal size assumed when it cannot be queried.
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Tees terminal output into an asciicast v2 stream.
+pub(crate) struct Recorder<W, C> {
+    /// The terminal.
+    out: W,
+    /// The cast file, or `None` once writing it failed.
+    cast: Option<C>,
+    /// Output since the last flush.
+    pending: Vec<u8>,
+    /// Time zero of the event stream.
+    start: Instant,
+    /// Terminal size the player currently has.
+    size: (u16, u16),
+    /// First error writing the cast.
+    error: Option<io::Error>,
+}
+
+/// The current terminal size, as (columns, rows).
+fn terminal_size() -> Option<(u16, u16)> {
+    crossterm::terminal::size().ok()
+}
+
+impl<W: Write, C: Write> Recorder<W, C> {
+    /// Start a recording into `cast`, writing its header for the current
+    /// terminal size.
+    pub(crate) fn new(out: W, cast: C) -> io::Result<Self> {
+        let size = terminal_size().unwrap_or(DEFAULT_SIZE);
+        Self::with_size(out, cast, size, SystemTime::now())
+    }
+
+    fn with_size(out: W, mut cast: C, size: (u16, u16), now: SystemTime) -> io::Result<Self> {
+        let timestamp = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+        let header = serde_json::json!({
+            "version": 2,
+            "width": size.0,
+            "height": size.1,
+            "timestamp": timestamp,
+            "title": "pumas",
+            "env": { "TERM": term },
+        });
+        writeln!(cast, "{header}")?;
+        Ok(Self {
+            out,
+            cast: Some(cast),
+            pending: Vec::new(),
+            start: Instant::now(),
+            size,
+            error: None,
+        })
+    }
+
+    /// Append the pending output as an event at `time`, preceded by a resize
+    /// event if `size` changed. A trailing incomplete UTF-8 sequence waits for
+    /// the next event.
+    fn record(&mut self, time: Duration, size: Option<(u16, u16)>) {
+        let Some(cast) = &mut self.cast else {
+            self.pending.clear();
+            return;
+        };
+        let mut lines = String::new();
+        let t = time.as_secs_f64();
+        if let Some(size) = size.filter(|&s| s != self.size) {
+            self.size = size;
+            let dims = format!("{}x{}", size.0, size.1);
+            lines += &format!("[{t:.6}, \"r\", {}]\n", serde_json::Value::from(dims));
+        }
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if complete > 0 {
+            let data = String::from_utf8_lossy(&self.pending[..complete]);
+            lines += &format!("[{t:.6}, \"o\", {}]\n", serde_json::Value::from(data));
+            self.pending.drain(..complete);
+        }
+        if let Err(e) = cast.write_all(lines.as_bytes()) {
+            self.cast = None;
+            self.error = Some(e);
+        }
+    }
+
+    /// Record any output not yet flushed, then flush the cast, returning the
+    /// first error writing it.
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        self.record(self.start.elapsed(), None);
+        if let Some(cast) = &mut self.cast
+            && let Err(e) = cast.flush()
+        {
+            self.error.get_or_insert(e);
+        }
+        self.error.map_or(Ok(()), Err)
+    }
+}
+
+impl<W: Write, C: Write> Write for Recorder<W, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.out.write(buf)?;
+        self.pending.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()?;
+        if !self.pending.is_empty() {
+            self.record(self.start.elapsed(), terminal_size());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder() -> Recorder<Vec<u8>, Vec<u8>> {
+        let now = UNIX_EPOCH + Duration::from_secs(1_792_333_530);
+        Recorder::with_size(Vec::new(), Vec::new(), (120, 40), now).unwrap()
+    }
+
+    fn lines(r: &Recorder<Vec<u8>, Vec<u8>>) -> Vec<serde_json::Value> {
+        let cast = std::str::from_utf8(r.cast.as_ref().unwrap()).unwrap();
+        cast.lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn header_then_one_event_per_flush() {
+        let mut r = recorder();
+        r.write_all(b"\x1b[?1049h").unwrap();
+        r.write_all("a \"b\"\n".as_bytes()).unwrap();
+        r.record(Duration::from_millis(1500), Some((120, 40)));
+        r.record(Duration::from_secs(2), None);
+        assert_eq!(r.out, b"\x1b[?1049ha \"b\"\n");
+        let lines = lines(&r);
+        assert_eq!(lines.len(), 2, "{lines:?}");
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 120);
+        assert_eq!(lines[0]["height"], 40);
+        assert_eq!(lines[0]["timestamp"], 1_792_333_530);
+        assert_eq!(
+            lines[1],
+            serde_json::json!([1.5, "o", "\u{1b}[?1049ha \"b\"\n"])
+        );
+    }
+
+    #[test]
+    fn resizes_and_split_characters() {
+        let mut r = recorder();
+        let bar = "█".as_bytes();
+        r.write_all(&bar[..1]).unwrap();
+        r.record(Duration::from_secs(1), Some((100, 30)));
+        r.write_all(&bar[1..]).unwrap();
+        r.record(Duration::from_secs(2), Some((100, 30)));
+        let lines = lines(&r);
+        assert_eq!(lines[1], serde_json::json!([1.0, "r", "100x30"]));
+        assert_eq!(lines[2], serde_json::json!([2.0, "o", "█"]));
+        assert_eq!(lines.len(), 3, "{lines:?}");
+    }
+}
@@ -7,13 +7,15 @@
 //!   single bar row (`GAUGE_HEIGHT = 2` = title row + 1 bar row). The Overview
 //!   view therefore renders the title as a separate `Text` and drives the gauge
 //!   at `height = 1`.
-//! - `end = round(ratio * width)` columns are filled with `█` (full block),
+//! - `end = round(ratio * width)` columns are filled with `█` (full block, or
+//!   the ASCII set's `#`),
 //!   foreground `gauge_fg` on background `gauge_bg`; the remaining columns are
 //!   spaces showing `gauge_bg`.
 //! - The label `"{NN}%"` (integer percent) is centered horizontally at
 //!   `label_col = (width - label_width) / 2` and vertically on row
 //!   `height / 2` (the single row when height is 1, the bottom row for height
-//!   2), overlaid on the bar.
+//!   2), overlaid on the bar; bold when the gauge is in a warning/critical
+//!   state in monochrome.
 //!
 //! Width is an explicit prop: iocraft computes flex sizes only *after* element
 //! construction, so a component cannot know its allocated width at build time.
@@ -22,6 +24,7 @@
 use iocraft::prelude::*;
 
 use super::{Cell, render_grid};
+use crate::ui::theme::Glyphs;
 
 /// Fully-owned gauge inputs (no borrows — `'static` element output).
 #[derive(Clone, Debug)]
@@ -36,6 +39,10 @@ pub(crate) struct RenderedGauge {
     pub fg: Color,
     /// Background of the whole bar (gauge_bg).
     pub bg: Color,
+    /// Bold label (monochrome state mark).
+    pub bold: bool,
+    /// Glyph set (`block`).
+    pub glyphs: Glyphs,
 }
 
 impl RenderedGauge {
@@ -65,9 +72,9 @@ impl RenderedGauge {
                             // Label glyph: default fg (matches the original label
                             // span style), background follows filled/unfilled.
                             let bg = if x < end { self.fg } else { self.bg };
-                            Cell::new(label[x - label_col], Color::Reset, bg)
+                            Cell::new(label[x - label_col], Color::Reset, bg).bold(self.bold)
                         } else if x < end {
-                            Cell::new('█', self.fg, self.bg)
+                            Cell::new(self.glyphs.block, self.fg, self.bg)
                         } else {
                             Cell::new(' ', self.fg, self.bg)
                         }
@@ -0,0 +1,162 @@
+//! Heatmap: one labelled row per series, one cell column per sample, shaded
+//! by value, with a time-tick row underneath.
+//!
+//! A cell shows its sample as one of four shades (`░▒▓█`, or the ASCII set's
+//! `.:+#`, each a quarter of `max`); zero stays blank so idle stretches read
+//! as gaps. Each cell takes its sample's color (and bold mark), so threshold
+//! roles still show through:
+//!
+//! ```text
+//! E    0 ░░▒░  ░▓█▓▒░
+//!      1    ░░▒▓████▓░
+//! P0   2 ████▓▒░   ░▒
+//!                 now
+//! ```
+//!
+//! Row data arrives already fitted to the plot (one value per cell column,
+//! newest last); the tick row is the [`LineChart`]'s.
+//!
+//! [`LineChart`]: super::line_chart::LineChart
+
+use iocraft::prelude::*;
+
+use super::{Cell, line_chart::tick_row, render_grid};
+use crate::ui::theme::Glyphs;
+
+/// One row of the heatmap.
+#[derive(Clone, Debug)]
+pub(crate) struct HeatRow {
+    /// Left label, padded to the label column.
+    pub label: String,
+    /// One value per cell column, newest last. Shorter data is right-aligned.
+    pub data: Vec<u64>,
+    /// Color of each `data` cell; missing entries use the heatmap's `fg`.
+    pub colors: Vec<Color>,
+    /// Bold mark of each `data` cell; missing entries are not bold.
+    pub bold: Vec<bool>,
+}
+
+/// Fully-owned heatmap inputs.
+#[derive(Clone, Debug)]
+pub(crate) struct RenderedHeatmap {
+    /// Rows, top first.
+    pub rows: Vec<HeatRow>,
+    /// Value of a full (`█`) cell.
+    pub max: u64,
+    /// Total width, label column included.
+    pub width: usize,
+    /// Label column width (the label plus its gap).
+    pub label_w: usize,
+    /// X axis ticks: (plot column, label), the label ending at its column.
+    pub x_ticks: Vec<(usize, String)>,
+    /// Default cell color.
+    pub fg: Color,
+    /// Row label color.
+    pub label_color: Color,
+    /// Tick label color.
+    pub axis: Color,
+    /// Glyph set (`shades`).
+    pub glyphs: Glyphs,
+}
+
+/// Width of the plot area of a `width`-column heatmap with a `label_w` label
+/// column, in cells.
+pub(crate) fn heat_width(width: usize, label_w: usize) -> usize {
+    width.saturating_sub(label_w)
+}
+
+impl RenderedHeatmap {
+    /// Shade index of `value`: any non-zero value gets at least `░`.
+    fn level(&self, value: u64) -> usize {
+        if value == 0 {
+            return 0;
+        }
+        let quarters = (value.min(self.max) * 4).div_ceil(self.max.max(1));
+        #[expect(clippy::cast_possible_truncation)]
+        let level = quarters.max(1) as usize;
+        level
+    }
+
+    /// Build the `(rows + 1) × width` cell grid: heatmap rows, then the tick
+    /// row.
+    fn cells(&self) -> Vec<Vec<Cell>> {
+        let plot_w = heat_width(self.width, self.label_w);
+        let mut rows: Vec<Vec<Cell>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let label: String = row.label.chars().take(self.label_w).collect();
+                let mut cells: Vec<Cell> = format!("{label:<w$}", w = self.label_w)
+                    .chars()
+                    .map(|ch| Cell::new(ch, self.label_color, Color::Reset))
+                    .collect();
+                let skip = row.data.len().saturating_sub(plot_w);
+                let data = &row.data[skip..];
+                cells.extend(
+                    (0..plot_w - data.len()).map(|_| Cell::new(' ', Color::Reset, Color::Reset)),
+                );
+                cells.extend(data.iter().enumerate().map(|(i, &v)| {
+                    let color = row.colors.get(skip + i).copied().unwrap_or(self.fg);
+                    let bold = row.bold.get(skip + i).copied().unwrap_or(false);
+                    Cell::new(self.glyphs.shades[self.level(v)], color, Color::Reset).bold(bold)
+                }));
+                cells
+            })
+            .collect();
+        rows.push(tick_row(self.width, self.label_w, &self.x_ticks, self.axis));
+        rows
+    }
+}
+
+#[derive(Default, Props)]
+pub(crate) struct HeatmapProps {
+    pub heatmap: Option<RenderedHeatmap>,
+}
+
+#[component]
+pub(crate) fn Heatmap(props: &mut HeatmapProps) -> impl Into<AnyElement<'static>> {
+    let Some(h) = props.heatmap.take() else {
+        return element! { View }.into_any();
+    };
+    render_grid(h.cells())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_shade_by_quarter_and_right_align() {
+        let h = RenderedHeatmap {
+            rows: vec![HeatRow {
+                label: "E    0".to_string(),
+                data: vec![0, 1, 25, 26, 75, 100, 250],
+                colors: vec![Color::Red],
+                bold: Vec::new(),
+            }],
+            max: 100,
+            width: 16,
+            label_w: 7,
+            x_ticks: vec![(8, "now".to_string())],
+            fg: Color::Blue,
+            label_color: Color::Reset,
+            axis: Color::Reset,
+            glyphs: Glyphs::UNICODE,
+        };
+        let rows = h.cells();
+        let text: Vec<String> = rows
+            .iter()
+            .map(|r| r.iter().map(|c| c.ch).collect())
+            .collect();
+        assert_eq!(text, ["E    0    ░░▒▓██", "             now"]);
+        // The first sample's explicit color, then the default.
+        assert_eq!(rows[0][9].fg, Color::Red);
+        assert_eq!(rows[0][10].fg, Color::Blue);
+        let ascii = RenderedHeatmap {
+            glyphs: Glyphs::ASCII,
+            ..h
+        };
+        let row: String = ascii.cells()[0].iter().map(|c| c.ch).collect();
+        assert_eq!(row, "E    0    ..:+##");
+    }
+}
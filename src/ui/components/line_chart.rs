@@ -0,0 +1,298 @@
+//! Braille line chart with a labelled Y axis and time ticks.
+//!
+//! Each cell packs a 2×4 grid of braille dots (`U+2800`–`U+28FF`), so a plot
+//! `w` cells wide and `h` rows tall has `2w` sample columns and `4h` levels —
+//! four times the vertical resolution of the block [`Sparkline`]. Every series
+//! is drawn as a line: each sample's dot, joined vertically to the previous
+//! sample's so steep changes stay connected. Series are drawn in order, and a
+//! cell takes the color of the last series (or per-sample color) that set a
+//! dot in it.
+//!
+//! Layout, `width` columns overall:
+//!
+//! ```text
+//!  100.0 %┤⠀⠀⠀⠀⣀⠤⠒⠉⠉⠒⠤⣀⠀⠀
+//!         │⠀⣀⠤⠊⠀⠀⠀⠀⠀⠀⠀⠀⠈⠑
+//!    0.0 %┤⠊⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+//!              t-14s      now
+//! ```
+//!
+//! With the ASCII glyph set there is no braille: a cell shows `'`, `.` or `:`
+//! for dots in its upper half, lower half or both, and the axis is drawn with
+//! `|` and `+`.
+//!
+//! The axis labels and tick labels arrive formatted; series data arrives
+//! already fitted to the plot (one value per dot column, newest last). The
+//! axis helpers are shared with the [`StackedChart`].
+//!
+//! [`Sparkline`]: super::sparkline::Sparkline
+//! [`StackedChart`]: super::stacked_chart::StackedChart
+
+use iocraft::prelude::*;
+
+use super::{Cell, render_grid};
+use crate::ui::theme::Glyphs;
+
+/// First braille pattern (no dots).
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Dot bit for (dot column `0..2`, dot row `0..4`, top first) of a braille
+/// cell.
+const DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Dots in the upper two rows of a braille cell.
+const UPPER_DOTS: u8 = 0x01 | 0x02 | 0x08 | 0x10;
+
+/// The cell glyph for a set of braille dots.
+fn dots_glyph(bits: u8, glyphs: &Glyphs) -> char {
+    if glyphs.braille {
+        return char::from_u32(BRAILLE_BLANK + u32::from(bits)).unwrap_or(' ');
+    }
+    match (bits & UPPER_DOTS != 0, bits & !UPPER_DOTS != 0) {
+        (false, false) => ' ',
+        (true, false) => '\'',
+        (false, true) => '.',
+        (true, true) => ':',
+    }
+}
+
+/// One line of the chart.
+#[derive(Clone, Debug)]
+pub(crate) struct ChartSeries {
+    /// One value per dot column (`2 ×` the plot width at most), newest last.
+    /// Shorter data is right-aligned.
+    pub data: Vec<u64>,
+    /// Line color.
+    pub color: Color,
+    /// Per-sample colors (e.g. warning / critical samples); samples past its
+    /// end use `color`.
+    pub colors: Vec<Color>,
+}
+
+/// Fully-owned line-chart inputs.
+#[derive(Clone, Debug)]
+pub(crate) struct RenderedLineChart {
+    /// Lines, drawn in order (later ones on top).
+    pub series: Vec<ChartSeries>,
+    /// Value at the top of the plot; the bottom is 0.
+    pub max: u64,
+    /// Total width, Y axis included.
+    pub width: usize,
+    /// Plot rows, not counting the tick row.
+    pub height: usize,
+    /// Y axis labels for the top (`max`) and bottom (0) rows.
+    pub y_labels: (String, String),
+    /// X axis ticks: (plot column, label), the label ending at its column.
+    pub x_ticks: Vec<(usize, String)>,
+    /// Axis and label color.
+    pub axis: Color,
+    /// Glyph set (axis, braille or not).
+    pub glyphs: Glyphs,
+}
+
+/// Width of a Y axis: the longer label plus the axis line.
+pub(crate) fn axis_width(y_labels: &(String, String)) -> usize {
+    y_labels.0.chars().count().max(y_labels.1.chars().count()) + 1
+}
+
+/// Width of the plot area of a `width`-column chart, in cells.
+pub(crate) fn plot_width(width: usize, y_labels: &(String, String)) -> usize {
+    width.saturating_sub(axis_width(y_labels))
+}
+
+/// The Y axis cells of plot row `row` of `height`: the top label and `┤` on the
+/// first row, the bottom label and `┤` on the last, `│` in between (in the
+/// glyph set's own characters).
+pub(crate) fn y_axis(
+    y_labels: &(String, String),
+    (row, height): (usize, usize),
+    color: Color,
+    glyphs: &Glyphs,
+) -> Vec<Cell> {
+    let label_w = axis_width(y_labels) - 1;
+    let (label, tick) = match row {
+        0 => (y_labels.0.as_str(), glyphs.axis_tick),
+        r if r == height - 1 => (y_labels.1.as_str(), glyphs.axis_tick),
+        _ => ("", glyphs.axis),
+    };
+    format!("{label:>label_w$}{tick}")
+        .chars()
+        .map(|ch| Cell::new(ch, color, Color::Reset))
+        .collect()
+}
+
+/// The tick row under a plot starting at column `axis_w`: each label ends at
+/// its plot column; one that would overlap the previous label (or the axis)
+/// is dropped.
+pub(crate) fn tick_row(
+    width: usize,
+    axis_w: usize,
+    x_ticks: &[(usize, String)],
+    color: Color,
+) -> Vec<Cell> {
+    let mut ticks = vec![' '; width];
+    let mut free_from = axis_w;
+    let mut sorted = x_ticks.to_vec();
+    sorted.sort_by_key(|(col, _)| *col);
+    for (col, label) in sorted {
+        let end = axis_w + col + 1;
+        let len = label.chars().count();
+        if end > width || end < free_from + len {
+            continue;
+        }
+        for (i, ch) in label.chars().enumerate() {
+            ticks[end - len + i] = ch;
+        }
+        free_from = end + 1;
+    }
+    ticks
+        .into_iter()
+        .map(|ch| Cell::new(ch, color, Color::Reset))
+        .collect()
+}
+
+impl RenderedLineChart {
+    /// The plot's braille dots and cell colors, `height` rows of `plot_w`.
+    fn plot(&self, plot_w: usize) -> Vec<Vec<(u8, Option<Color>)>> {
+        let height = self.height.max(1);
+        let levels = height * 4;
+        let max = self.max.max(1) as f64;
+        let mut grid = vec![vec![(0u8, None); plot_w]; height];
+
+        // Dot row (0 = top) of a value.
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let dot_row = |v: u64| {
+            let level = (v.min(self.max) as f64 / max * (levels - 1) as f64).round() as usize;
+            levels - 1 - level
+        };
+
+        for s in &self.series {
+            let skip = s.data.len().saturating_sub(plot_w * 2);
+            let data = &s.data[skip..];
+            let start = plot_w * 2 - data.len();
+            let mut prev: Option<usize> = None;
+            for (i, &v) in data.iter().enumerate() {
+                let x = start + i;
+                let y = dot_row(v);
+                let color = s.colors.get(skip + i).copied().unwrap_or(s.color);
+                let (lo, hi) = match prev {
+                    Some(p) => (p.min(y), p.max(y)),
+                    None => (y, y),
+                };
+                for dot in lo..=hi {
+                    let cell = &mut grid[dot / 4][x / 2];
+                    cell.0 |= DOTS[x % 2][dot % 4];
+                    cell.1 = Some(color);
+                }
+                prev = Some(y);
+            }
+        }
+        grid
+    }
+
+    /// Build the `(height + 1) × width` cell grid: plot rows, then the tick row.
+    fn cells(&self) -> Vec<Vec<Cell>> {
+        let height = self.height.max(1);
+        let plot_w = plot_width(self.width, &self.y_labels);
+        let plot = self.plot(plot_w);
+
+        let mut rows: Vec<Vec<Cell>> = plot
+            .into_iter()
+            .enumerate()
+            .map(|(r, dots)| {
+                let mut row = y_axis(&self.y_labels, (r, height), self.axis, &self.glyphs);
+                row.extend(dots.into_iter().map(|(bits, color)| {
+                    let ch = dots_glyph(bits, &self.glyphs);
+                    Cell::new(ch, color.unwrap_or(Color::Reset), Color::Reset)
+                }));
+                row
+            })
+            .collect();
+
+        let axis_w = axis_width(&self.y_labels);
+        rows.push(tick_row(self.width, axis_w, &self.x_ticks, self.axis));
+        rows
+    }
+}
+
+#[derive(Default, Props)]
+pub(crate) struct LineChartProps {
+    pub chart: Option<RenderedLineChart>,
+}
+
+#[component]
+pub(crate) fn LineChart(props: &mut LineChartProps) -> impl Into<AnyElement<'static>> {
+    let Some(c) = props.chart.take() else {
+        return element! { View }.into_any();
+    };
+    render_grid(c.cells())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(rows: &[Vec<Cell>]) -> Vec<String> {
+        rows.iter()
+            .map(|r| r.iter().map(|c| c.ch).collect())
+            .collect()
+    }
+
+    #[test]
+    fn lines_join_consecutive_samples() {
+        let chart = RenderedLineChart {
+            series: vec![ChartSeries {
+                data: vec![0, 100, 100, 0],
+                color: Color::Green,
+                colors: Vec::new(),
+            }],
+            max: 100,
+            width: 6,
+            height: 1,
+            y_labels: ("9".to_string(), "0".to_string()),
+            x_ticks: vec![(3, "now".to_string())],
+            axis: Color::Reset,
+            glyphs: Glyphs::UNICODE,
+        };
+        // Axis "9┤", two leading empty cells, then a rise and a fall.
+        assert_eq!(text(&chart.cells()), ["9┤⠀⠀\u{28f8}\u{28b9}", "   now"]);
+        let ascii = RenderedLineChart {
+            glyphs: Glyphs::ASCII,
+            ..chart
+        };
+        assert_eq!(text(&ascii.cells()), ["9+  ::", "   now"]);
+    }
+
+    #[test]
+    fn later_series_and_sample_colors_win() {
+        let chart = RenderedLineChart {
+            series: vec![
+                ChartSeries {
+                    data: vec![50, 50],
+                    color: Color::Green,
+                    colors: Vec::new(),
+                },
+                ChartSeries {
+                    data: vec![50, 50],
+                    color: Color::Blue,
+                    colors: vec![Color::Red],
+                },
+            ],
+            max: 100,
+            width: 3,
+            height: 2,
+            y_labels: ("1".to_string(), "0".to_string()),
+            x_ticks: Vec::new(),
+            axis: Color::Reset,
+            glyphs: Glyphs::UNICODE,
+        };
+        let rows = chart.cells();
+        assert_eq!(rows.len(), 3, "two plot rows and the tick row");
+        let cell = rows
+            .iter()
+            .take(2)
+            .find_map(|r| (r[2].ch != '⠀').then(|| &r[2]));
+        // The second series' second sample has no per-sample color: blue.
+        assert_eq!(cell.map(|c| c.fg), Some(Color::Blue));
+    }
+}
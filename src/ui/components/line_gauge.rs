@@ -4,25 +4,26 @@
 //! left label, then a one-column gap, then the bar drawn with the THICK
 //! horizontal symbol. Filled columns use `gauge_fg` as the glyph foreground,
 //! unfilled columns use `gauge_bg`; there is no background fill (unlike the
-//! block gauge). Fill count is `floor(ratio * width)`.
+//! block gauge). Fill count is `floor(ratio * width)`. The glyphs come from
+//! the theme's set: `━` throughout in color; in monochrome the unfilled part
+//! is a thin `─`, and in ASCII `=` / `-`.
 //!
-//! Activity rows pass a `"{:.1}%"` label; frequency rows pass `None` (just the
-//! leading gap + line). Width is the number of `━` cells, an explicit prop for
-//! the same reason as the block gauge.
+//! Activity rows pass a `"{:.1}%"` label (bold for a warning/critical row in
+//! monochrome); frequency rows pass `None` (just the leading gap + line).
+//! Width is the number of bar cells, an explicit prop for the same reason as
+//! the block gauge.
 
 use iocraft::prelude::*;
 
 use super::{Cell, render_grid};
-
-/// THICK horizontal line symbol (`━`).
-const LINE: char = '━';
+use crate::ui::theme::Glyphs;
 
 /// Fully-owned line-gauge inputs.
 #[derive(Clone, Debug)]
 pub(crate) struct RenderedLineGauge {
     /// Fill ratio in `0.0..=1.0`.
     pub ratio: f64,
-    /// Bar width in columns (number of `━` cells).
+    /// Bar width in columns.
     pub width: usize,
     /// Optional left label (activity rows); `None` => frequency row.
     pub label: Option<String>,
@@ -30,6 +31,10 @@ pub(crate) struct RenderedLineGauge {
     pub fg: Color,
     /// Unfilled-bar foreground (gauge_bg).
     pub bg: Color,
+    /// Bold label (monochrome state mark).
+    pub bold: bool,
+    /// Glyph set (`line`, `line_empty`).
+    pub glyphs: Glyphs,
 }
 
 impl RenderedLineGauge {
@@ -42,14 +47,18 @@ impl RenderedLineGauge {
         // Optional label, then the one-column gap the original always inserts.
         if let Some(label) = &self.label {
             for ch in label.chars() {
-                row.push(Cell::new(ch, Color::Reset, Color::Reset));
+                row.push(Cell::new(ch, Color::Reset, Color::Reset).bold(self.bold));
             }
         }
         row.push(Cell::new(' ', Color::Reset, Color::Reset));
         // The bar.
         for x in 0..self.width {
-            let fg = if x < filled { self.fg } else { self.bg };
-            row.push(Cell::new(LINE, fg, Color::Reset));
+            let (ch, fg) = if x < filled {
+                (self.glyphs.line, self.fg)
+            } else {
+                (self.glyphs.line_empty, self.bg)
+            };
+            row.push(Cell::new(ch, fg, Color::Reset));
         }
         vec![row]
     }
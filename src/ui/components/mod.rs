@@ -7,15 +7,21 @@
 //! values into glyphs.
 
 pub(crate) mod gauge;
+pub(crate) mod heatmap;
+pub(crate) mod line_chart;
 pub(crate) mod line_gauge;
 pub(crate) mod panel;
+pub(crate) mod scrollbar;
 pub(crate) mod sparkline;
+pub(crate) mod stacked_chart;
 pub(crate) mod tab_bar;
 pub(crate) mod title_bar;
+pub(crate) mod tooltip;
 
 use iocraft::prelude::*;
 
-/// A single rendered terminal cell: one character plus its fg/bg colors.
+/// A single rendered terminal cell: one character plus its fg/bg colors, and
+/// whether it is bold (state marks in monochrome).
 ///
 /// Components build a `Vec<Vec<Cell>>` (rows of cells) and hand it to
 /// [`render_grid`], which merges same-colored runs into compact `Text` spans.
@@ -24,19 +30,31 @@ pub(crate) struct Cell {
     pub ch: char,
     pub fg: Color,
     pub bg: Color,
+    pub bold: bool,
 }
 
 impl Cell {
     pub(crate) fn new(ch: char, fg: Color, bg: Color) -> Self {
-        Self { ch, fg, bg }
+        Self {
+            ch,
+            fg,
+            bg,
+            bold: false,
+        }
+    }
+
+    /// The same cell, bold if `bold`.
+    pub(crate) fn bold(self, bold: bool) -> Self {
+        Self { bold, ..self }
     }
 }
 
-/// A run of consecutive cells sharing the same fg/bg, collapsed to one string.
+/// A run of consecutive cells sharing the same style, collapsed to one string.
 struct Segment {
     text: String,
     fg: Color,
     bg: Color,
+    bold: bool,
 }
 
 /// Collapse a row of cells into the minimal set of same-colored runs.
@@ -44,11 +62,14 @@ fn merge_runs(cells: &[Cell]) -> Vec<Segment> {
     let mut segments: Vec<Segment> = Vec::new();
     for cell in cells {
         match segments.last_mut() {
-            Some(seg) if seg.fg == cell.fg && seg.bg == cell.bg => seg.text.push(cell.ch),
+            Some(seg) if seg.fg == cell.fg && seg.bg == cell.bg && seg.bold == cell.bold => {
+                seg.text.push(cell.ch);
+            }
             _ => segments.push(Segment {
                 text: cell.ch.to_string(),
                 fg: cell.fg,
                 bg: cell.bg,
+                bold: cell.bold,
             }),
         }
     }
@@ -56,7 +77,7 @@ fn merge_runs(cells: &[Cell]) -> Vec<Segment> {
 }
 
 /// Render a rectangular grid of cells as a `Column` of `Row`s, merging
-/// same-colored runs within each row into single `Text` spans. The background
+/// same-styled runs within each row into single `Text` spans. The background
 /// color of each run is painted via the wrapping `View` (iocraft `Text` has no
 /// background prop).
 pub(crate) fn render_grid(rows: Vec<Vec<Cell>>) -> AnyElement<'static> {
@@ -67,7 +88,12 @@ pub(crate) fn render_grid(rows: Vec<Vec<Cell>>) -> AnyElement<'static> {
                 View(key: ri, flex_direction: FlexDirection::Row) {
                     #(segs.into_iter().enumerate().map(|(si, seg)| element! {
                         View(key: si, background_color: seg.bg) {
-                            Text(content: seg.text, color: seg.fg, wrap: TextWrap::NoWrap)
+                            Text(
+                                content: seg.text,
+                                color: seg.fg,
+                                weight: if seg.bold { Weight::Bold } else { Weight::Normal },
+                                wrap: TextWrap::NoWrap,
+                            )
                         }
                     }))
                 }
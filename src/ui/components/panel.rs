@@ -2,53 +2,72 @@
 //!
 //! iocraft `View` has native border props but no `Block::title` equivalent, so
 //! the title is composed manually: the panel is a `Column`
-//! whose first row is the top-border line (`┌` + title + `─`-fill + `┐`) and
+//! whose first row is the top-border line (`┌` + title + `─`-fill + `┐`, the
+//! title in its own color) and
 //! whose second child is a `View` with native borders on every edge *except*
 //! Top. The left/right `│` and the bottom `└──┘` are drawn natively; only the
 //! titled top line is hand-composed. This is NOT a border drawer (DO-NOT #1) —
 //! all box-drawing except the title line comes from `View`'s own border props.
 //!
+//! The corners and edges follow the theme's glyph set: `+`, `-` and `|` with
+//! `--ascii`.
+//!
 //! `width` is the total outer width including the two corner columns; the body
 //! must be sized to `width - 2` by the caller.
 
 use iocraft::prelude::*;
 
-/// Compose the titled top-border line, exactly `width` columns wide:
-/// `┌` + title (truncated to fit) + `─` filler + `┐`.
-fn top_border_line(title: &str, width: usize) -> String {
+use crate::ui::theme::Theme;
+
+/// Split the titled top-border line, exactly `width` columns wide, into the
+/// title (truncated to fit) and the rest: `┌` + title + `─` filler + `┐`,
+/// given the `(┌, ─, ┐)` characters.
+fn top_border_line(
+    title: &str,
+    width: usize,
+    (_, top, right): (char, char, char),
+) -> (String, String) {
     let inner = width.saturating_sub(2); // columns between the two corners
     let title_w = title.chars().count().min(inner);
-    let mut s = String::with_capacity(width * 3);
-    s.push('┌');
-    s.extend(title.chars().take(title_w));
+    let title = title.chars().take(title_w).collect();
+    let mut fill = String::with_capacity((inner - title_w + 1) * 3);
     for _ in 0..(inner - title_w) {
-        s.push('─');
+        fill.push(top);
     }
-    s.push('┐');
-    s
+    fill.push(right);
+    (title, fill)
 }
 
 /// Render a bordered panel with an inline title and the given body.
 ///
 /// The body is any owned `'static` element (typically a `Column` of gauges and
-/// sparklines). `border_color` colors the box-drawing (and, for parity with
-/// the original default styling, the title text — both are `Color::Reset` today).
+/// sparklines). The box-drawing takes the theme's `border` color and the title
+/// text its `title` color (both the terminal default in the `dark` theme).
 pub(crate) fn panel(
     title: &str,
     width: usize,
-    border_color: Color,
+    theme: Theme,
     body: AnyElement<'static>,
 ) -> AnyElement<'static> {
-    let top = top_border_line(title, width);
+    let corners = theme.glyphs.top_border();
+    let (title, fill) = top_border_line(title, width, corners);
+    let border_color = theme.border;
     #[expect(clippy::cast_possible_truncation)]
     let w = width as u32;
     element! {
         View(flex_direction: FlexDirection::Column, width: w) {
-            Text(content: top, color: border_color, wrap: TextWrap::NoWrap)
+            MixedText(
+                wrap: TextWrap::NoWrap,
+                contents: vec![
+                    MixedTextContent::new(corners.0.to_string()).color(border_color),
+                    MixedTextContent::new(title).color(theme.title),
+                    MixedTextContent::new(fill).color(border_color),
+                ],
+            )
             View(
                 flex_direction: FlexDirection::Column,
                 width: w,
-                border_style: BorderStyle::Single,
+                border_style: theme.glyphs.border,
                 border_edges: Edges::Left | Edges::Right | Edges::Bottom,
                 border_color: border_color,
             ) {
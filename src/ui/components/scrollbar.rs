@@ -0,0 +1,20 @@
+//! Scroll indicator.
+//!
+//! A column of `┃` (`#` in ASCII) marking the visible part of a scrolled view, drawn over the
+//! body's right border at the rows [`scroll_thumb`] computes. Only the thumb
+//! is drawn; the border underneath stands in for the track.
+//!
+//! [`scroll_thumb`]: crate::ui::layout::scroll_thumb
+
+use iocraft::prelude::*;
+
+use crate::ui::theme::Theme;
+
+/// Render a `len`-row thumb in the accent color.
+pub(crate) fn scrollbar(len: usize, theme: Theme) -> AnyElement<'static> {
+    let thumb = vec![theme.glyphs.thumb.to_string(); len].join("\n");
+    element! {
+        Text(content: thumb, color: theme.accent, weight: Weight::Bold, wrap: TextWrap::NoWrap)
+    }
+    .into_any()
+}
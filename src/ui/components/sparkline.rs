@@ -4,7 +4,9 @@
 //! value `v` (clamped to `max`), the total bar height in eighths is
 //! `e = round(v / max * height * 8)`. For row `r` (0 = top), the cell shows the
 //! glyph for `clamp(e - 8*(height-1-r), 0, 8)`, where level 0 is a space and
-//! levels 1..=8 map to `▁▂▃▄▅▆▇█`. Color: `history_fg` on `history_bg`.
+//! levels 1..=8 map to `▁▂▃▄▅▆▇█` (or the ASCII set's `.,:-=+*#`). Color:
+//! `history_fg` on `history_bg`, unless a column has its own foreground (a
+//! warning/critical sample); in monochrome those columns are bold instead.
 //!
 //! The single-row case (`height == 1`, used by CPU/GPU per-core rows) falls out
 //! naturally as `clamp(round(v/max*8), 0, 8)`.
@@ -12,9 +14,7 @@
 use iocraft::prelude::*;
 
 use super::{Cell, render_grid};
-
-/// Levels: index 0 = empty, 1..=8 = increasing block heights.
-const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+use crate::ui::theme::Glyphs;
 
 /// Fully-owned sparkline inputs (owned `Vec<u64>`, no borrows).
 #[derive(Clone, Debug)]
@@ -27,8 +27,14 @@ pub(crate) struct RenderedSparkline {
     pub height: usize,
     /// Bar foreground (history_fg).
     pub fg: Color,
+    /// Per-column bar foregrounds; columns past its end use `fg`.
+    pub colors: Vec<Color>,
+    /// Per-column bold marks; columns past its end are not bold.
+    pub bold: Vec<bool>,
     /// Background (history_bg).
     pub bg: Color,
+    /// Glyph set (`bars`).
+    pub glyphs: Glyphs,
 }
 
 impl RenderedSparkline {
@@ -53,9 +59,12 @@ impl RenderedSparkline {
             .map(|row| {
                 eighths
                     .iter()
-                    .map(|&e| {
+                    .enumerate()
+                    .map(|(col, &e)| {
                         let level = (e - 8 * (height - 1 - row) as i64).clamp(0, 8) as usize;
-                        Cell::new(BARS[level], self.fg, self.bg)
+                        let fg = self.colors.get(col).copied().unwrap_or(self.fg);
+                        let bold = self.bold.get(col).copied().unwrap_or(false);
+                        Cell::new(self.glyphs.bars[level], fg, self.bg).bold(bold)
                     })
                     .collect()
             })
@@ -0,0 +1,268 @@
+//! Stacked area chart with a labelled Y axis, time ticks and reference lines.
+//!
+//! One cell column per sample, layers stacked bottom-up. Each cell row covers
+//! eight levels, drawn with the [`Sparkline`]'s `▁▂▃▄▅▆▇█` blocks: a cell
+//! inside a layer is a full block in its color, and a cell where a layer ends
+//! shows the rest of that layer as a partial block, its background taking the
+//! color of the next layer up (or staying empty). Every boundary thus keeps
+//! eighth-row resolution.
+//!
+//! The blocks, reference line and axis come from the theme's glyph set, so
+//! the ASCII set draws `.,:-=+*#` levels and `-` lines.
+//!
+//! Reference lines (`╌`, their label at the left end) are drawn over the empty
+//! part of the plot only, so they never hide the stack:
+//!
+//! ```text
+//!   52 W┤╌CPU max 52 W╌╌╌╌╌╌╌╌╌╌╌╌╌
+//!       │               ▂▃▅▆█▇▅▃▂
+//!       │   ▁▂▃▄▅▆▇████████████████
+//!    0 W┤▆▆██████████████████████████
+//!                  t-32s         now
+//! ```
+//!
+//! The Y axis and tick row are shared with the [`LineChart`]. Layer data
+//! arrives already fitted to the plot (one value per cell column, newest
+//! last).
+//!
+//! [`Sparkline`]: super::sparkline::Sparkline
+//! [`LineChart`]: super::line_chart::LineChart
+
+use iocraft::prelude::*;
+
+use super::{
+    Cell,
+    line_chart::{axis_width, plot_width, tick_row, y_axis},
+    render_grid,
+};
+use crate::ui::theme::Glyphs;
+
+/// One layer of the stack.
+#[derive(Clone, Debug)]
+pub(crate) struct StackLayer {
+    /// One value per cell column, newest last. Shorter data is right-aligned.
+    pub data: Vec<u64>,
+    /// Layer color.
+    pub color: Color,
+}
+
+/// A horizontal line at a fixed value.
+#[derive(Clone, Debug)]
+pub(crate) struct ReferenceLine {
+    /// Value the line marks; lines above the chart's `max` are not drawn.
+    pub value: u64,
+    /// Label drawn at the left end of the line.
+    pub label: String,
+    /// Line and label color.
+    pub color: Color,
+}
+
+/// Fully-owned stacked-chart inputs.
+#[derive(Clone, Debug)]
+pub(crate) struct RenderedStackedChart {
+    /// Layers, bottom first.
+    pub layers: Vec<StackLayer>,
+    /// Value at the top of the plot; the bottom is 0.
+    pub max: u64,
+    /// Total width, Y axis included.
+    pub width: usize,
+    /// Plot rows, not counting the tick row.
+    pub height: usize,
+    /// Y axis labels for the top (`max`) and bottom (0) rows.
+    pub y_labels: (String, String),
+    /// X axis ticks: (plot column, label), the label ending at its column.
+    pub x_ticks: Vec<(usize, String)>,
+    /// Reference lines; where two share a row, the first one is drawn.
+    pub references: Vec<ReferenceLine>,
+    /// Axis and label color.
+    pub axis: Color,
+    /// Glyph set (`bars`, `dash`, axis).
+    pub glyphs: Glyphs,
+}
+
+impl RenderedStackedChart {
+    /// Level (in eighths of a row, `0..=levels`) of `value`.
+    fn level(&self, value: u64, levels: usize) -> usize {
+        let max = self.max.max(1) as f64;
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let level = (value.min(self.max) as f64 / max * levels as f64).round() as usize;
+        level
+    }
+
+    /// The top level and color of each layer in every plot column, bottom
+    /// first.
+    fn stacks(&self, plot_w: usize, levels: usize) -> Vec<Vec<(usize, Color)>> {
+        let mut stacks = vec![Vec::new(); plot_w];
+        let mut totals = vec![0u64; plot_w];
+        for layer in &self.layers {
+            let skip = layer.data.len().saturating_sub(plot_w);
+            let data = &layer.data[skip..];
+            let start = plot_w - data.len();
+            for (i, &v) in data.iter().enumerate() {
+                totals[start + i] += v;
+                let top = self.level(totals[start + i], levels);
+                stacks[start + i].push((top, layer.color));
+            }
+        }
+        stacks
+    }
+
+    /// Build the `(height + 1) × width` cell grid: plot rows, then the tick row.
+    fn cells(&self) -> Vec<Vec<Cell>> {
+        let height = self.height.max(1);
+        let levels = height * 8;
+        let plot_w = plot_width(self.width, &self.y_labels);
+        let stacks = self.stacks(plot_w, levels);
+
+        // The layer color at level `e` of a column (none above the stack).
+        let owner = |stack: &[(usize, Color)], e: usize| {
+            stack.iter().find(|(top, _)| *top > e).map(|(_, c)| *c)
+        };
+
+        let mut plot: Vec<Vec<Option<Cell>>> = (0..height)
+            .map(|r| {
+                let bottom = (height - 1 - r) * 8;
+                stacks
+                    .iter()
+                    .map(|stack| {
+                        let &(top, color) = stack.iter().find(|(top, _)| *top > bottom)?;
+                        let n = (top - bottom).min(8);
+                        let above = match n {
+                            8 => None,
+                            n => owner(stack, bottom + n),
+                        };
+                        Some(Cell::new(
+                            self.glyphs.bars[n],
+                            color,
+                            above.unwrap_or(Color::Reset),
+                        ))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for line in self.references.iter().filter(|l| l.value <= self.max) {
+            let level = self.level(line.value, levels);
+            let r = height - 1 - level.saturating_sub(1) / 8;
+            let row = &mut plot[r];
+            let label: Vec<char> = line.label.chars().collect();
+            let label_fits =
+                label.len() + 2 <= plot_w && row[1..=label.len()].iter().all(Option::is_none);
+            for (col, cell) in row.iter_mut().enumerate() {
+                if cell.is_some() {
+                    continue;
+                }
+                let ch = match col.checked_sub(1) {
+                    Some(i) if label_fits && i < label.len() => label[i],
+                    _ => self.glyphs.dash,
+                };
+                *cell = Some(Cell::new(ch, line.color, Color::Reset));
+            }
+        }
+
+        let mut rows: Vec<Vec<Cell>> = plot
+            .into_iter()
+            .enumerate()
+            .map(|(r, cells)| {
+                let mut row = y_axis(&self.y_labels, (r, height), self.axis, &self.glyphs);
+                row.extend(
+                    cells
+                        .into_iter()
+                        .map(|c| c.unwrap_or_else(|| Cell::new(' ', Color::Reset, Color::Reset))),
+                );
+                row
+            })
+            .collect();
+        let axis_w = axis_width(&self.y_labels);
+        rows.push(tick_row(self.width, axis_w, &self.x_ticks, self.axis));
+        rows
+    }
+}
+
+#[derive(Default, Props)]
+pub(crate) struct StackedChartProps {
+    pub chart: Option<RenderedStackedChart>,
+}
+
+#[component]
+pub(crate) fn StackedChart(props: &mut StackedChartProps) -> impl Into<AnyElement<'static>> {
+    let Some(c) = props.chart.take() else {
+        return element! { View }.into_any();
+    };
+    render_grid(c.cells())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chart(layers: Vec<StackLayer>, references: Vec<ReferenceLine>) -> RenderedStackedChart {
+        RenderedStackedChart {
+            layers,
+            max: 16,
+            width: 16,
+            height: 2,
+            y_labels: ("9".to_string(), "0".to_string()),
+            x_ticks: Vec::new(),
+            references,
+            axis: Color::Reset,
+            glyphs: Glyphs::UNICODE,
+        }
+    }
+
+    fn text(rows: &[Vec<Cell>]) -> Vec<String> {
+        rows.iter()
+            .map(|r| r.iter().map(|c| c.ch).collect())
+            .collect()
+    }
+
+    #[test]
+    fn layer_boundaries_share_a_cell() {
+        // 14 plot columns, 16 levels: one level per unit.
+        let c = chart(
+            vec![
+                StackLayer {
+                    data: vec![3, 8],
+                    color: Color::Blue,
+                },
+                StackLayer {
+                    data: vec![2, 4],
+                    color: Color::Green,
+                },
+            ],
+            Vec::new(),
+        );
+        let rows = c.cells();
+        assert_eq!(
+            text(&rows),
+            [
+                format!("9┤{}▄", " ".repeat(13)),
+                format!("0┤{}▃█", " ".repeat(12)),
+                " ".repeat(16),
+            ]
+        );
+        // Blue ends 3/8 up the cell, green stacks on top of it.
+        let cell = &rows[1][14];
+        assert_eq!((cell.fg, cell.bg), (Color::Blue, Color::Green));
+        // Green ends mid-row with nothing above.
+        let cell = &rows[0][15];
+        assert_eq!((cell.fg, cell.bg), (Color::Green, Color::Reset));
+    }
+
+    #[test]
+    fn reference_lines_skip_the_stack() {
+        let c = chart(
+            vec![StackLayer {
+                data: vec![16; 4],
+                color: Color::Blue,
+            }],
+            vec![ReferenceLine {
+                value: 16,
+                label: "max".to_string(),
+                color: Color::Red,
+            }],
+        );
+        let rows = text(&c.cells());
+        assert_eq!(rows[0], format!("9┤╌max{}████", "╌".repeat(6)));
+    }
+}
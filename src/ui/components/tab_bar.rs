@@ -1,17 +1,22 @@
 //! Tab bar.
 //!
 //! A `View` bordered on all edges (3 rows) whose single inner row holds the
-//! five tab labels. Each tab is
+//! six tab labels. Each tab is
 //! `padding_left` +
 //! title + `padding_right` (both a single space), tabs separated by the
-//! `│` (U+2502) divider. The active title is accent + bold;
+//! `│` (U+2502) divider (`|` in ASCII). The active title is accent + bold;
 //! the surrounding padding/divider stay default. The net inner string is
-//! `" Overview │ CPU │ GPU │ Memory │ SoC "`.
+//! `" Overview │ CPU │ GPU │ Memory │ SoC │ Power "`.
+//!
+//! An optional `info` string (the live sampling settings) is right-aligned on
+//! the same row, and left out when the terminal is too narrow for both.
 
 use iocraft::prelude::*;
 
-/// The five tab titles, in order.
-pub(crate) const TAB_TITLES: [&str; 5] = ["Overview", "CPU", "GPU", "Memory", "SoC"];
+use crate::ui::theme::Theme;
+
+/// The six tab titles, in order.
+pub(crate) const TAB_TITLES: [&str; 6] = ["Overview", "CPU", "GPU", "Memory", "SoC", "Power"];
 
 /// Render one tab label, accent + bold when it is the active tab.
 fn tab_label(title: &'static str, active: bool, accent: Color) -> AnyElement<'static> {
@@ -26,7 +31,12 @@ fn tab_label(title: &'static str, active: bool, accent: Color) -> AnyElement<'st
 }
 
 /// Render the bordered tab bar at an explicit `width`, highlighting `active`.
-pub(crate) fn tab_bar(active: usize, accent: Color, width: usize) -> AnyElement<'static> {
+pub(crate) fn tab_bar(
+    active: usize,
+    info: Option<String>,
+    theme: Theme,
+    width: usize,
+) -> AnyElement<'static> {
     #[expect(clippy::cast_possible_truncation)]
     let w = width as u32;
 
@@ -35,19 +45,42 @@ pub(crate) fn tab_bar(active: usize, accent: Color, width: usize) -> AnyElement<
     let mut segments: Vec<AnyElement<'static>> = Vec::new();
     segments.push(element! { Text(content: " ", wrap: TextWrap::NoWrap) }.into_any());
     for (i, title) in TAB_TITLES.iter().enumerate() {
-        segments.push(tab_label(title, i == active, accent));
-        let sep = if i == last { " " } else { " │ " };
+        segments.push(tab_label(title, i == active, theme.accent));
+        let sep = if i == last {
+            " ".to_string()
+        } else {
+            format!(" {} ", theme.glyphs.divider)
+        };
         segments.push(element! { Text(content: sep, wrap: TextWrap::NoWrap) }.into_any());
     }
+    // ` title ` per tab plus the dividers, and the info's trailing space.
+    let labels_w = TAB_TITLES
+        .iter()
+        .map(|t| t.chars().count() + 3)
+        .sum::<usize>()
+        - 1;
+    let info: Vec<AnyElement<'static>> = info
+        .into_iter()
+        .filter(|s| labels_w + s.chars().count() < width.saturating_sub(2))
+        .map(|s| element! { Text(content: format!("{s} "), wrap: TextWrap::NoWrap) }.into_any())
+        .collect();
 
     element! {
         View(
             width: w,
-            border_style: BorderStyle::Single,
+            border_style: theme.glyphs.border,
+            border_color: theme.border,
             border_edges: Edges::all(),
         ) {
-            View(flex_direction: FlexDirection::Row) {
-                #(segments)
+            View(
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                flex_grow: 1.0,
+            ) {
+                View(flex_direction: FlexDirection::Row) {
+                    #(segments)
+                }
+                #(info)
             }
         }
     }
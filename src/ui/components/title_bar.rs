@@ -1,30 +1,53 @@
 //! Top title bar.
 //!
 //! A single row: the program name (`Pumas v{version}`) on the left in the
-//! default color, and the machine description (`{brand} (cores: …)`) on the
+//! title color, and the machine description (`{brand} (cores: …)`) on the
 //! right in the accent color. The original overlays a left-aligned and a
 //! right-aligned paragraph on the same row; iocraft expresses this as one
 //! `Row` with `justify_content: SpaceBetween`.
+//!
+//! An optional status (e.g. `"PAUSED @ t-42s"`) follows the program name,
+//! bold in the warning color, while the view is frozen.
 
 use iocraft::prelude::*;
 
+use crate::ui::theme::Theme;
+
 /// Render the title bar at an explicit `width`.
 pub(crate) fn title_bar(
     program_name: String,
     machine_desc: String,
-    accent: Color,
+    status: Option<String>,
+    theme: Theme,
     width: usize,
 ) -> AnyElement<'static> {
     #[expect(clippy::cast_possible_truncation)]
     let w = width as u32;
+    let status: Vec<AnyElement<'static>> = status
+        .into_iter()
+        .map(|s| {
+            element! {
+                Text(
+                    content: format!("  {s}"),
+                    color: theme.warning,
+                    weight: Weight::Bold,
+                    wrap: TextWrap::NoWrap,
+                )
+            }
+            .into_any()
+        })
+        .collect();
     element! {
         View(
             flex_direction: FlexDirection::Row,
             justify_content: JustifyContent::SpaceBetween,
             width: w,
         ) {
-            Text(content: program_name, wrap: TextWrap::NoWrap)
-            Text(content: machine_desc, color: accent, wrap: TextWrap::NoWrap)
+            View(flex_direction: FlexDirection::Row) {
+                Text(content: program_name, color: theme.title, wrap: TextWrap::NoWrap)
+                #(status)
+            }
+            Text(content: machine_desc, color: theme.accent, wrap: TextWrap::NoWrap)
         }
     }
     .into_any()
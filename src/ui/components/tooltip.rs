@@ -0,0 +1,34 @@
+//! Tooltip: a one-line bordered box with an opaque background.
+//!
+//! `│ text │` — `text + 4` columns wide and [`TOOLTIP_HEIGHT`] rows tall. The
+//! caller positions it absolutely (see
+//! [`tooltip_origin`](crate::ui::layout::tooltip_origin)); the opaque
+//! background hides whatever is drawn underneath.
+
+use iocraft::prelude::*;
+
+use crate::ui::theme::Theme;
+
+/// Rows taken by a tooltip (its text row plus the top/bottom border).
+pub(crate) const TOOLTIP_HEIGHT: usize = 3;
+
+/// Columns taken by a tooltip showing `text`.
+pub(crate) fn tooltip_width(text: &str) -> usize {
+    text.chars().count() + 4
+}
+
+/// Render `text` in a bordered box, the border in the accent color.
+pub(crate) fn tooltip(text: String, theme: Theme) -> AnyElement<'static> {
+    element! {
+        View(
+            background_color: Color::Reset,
+            border_style: theme.glyphs.border,
+            border_color: theme.accent,
+            padding_left: 1u32,
+            padding_right: 1u32,
+        ) {
+            Text(content: text, wrap: TextWrap::NoWrap)
+        }
+    }
+    .into_any()
+}
@@ -0,0 +1,305 @@
+//! Screen export: a rendered [`Canvas`] as plain text, ANSI-colored text or an
+//! SVG image.
+//!
+//! Text and ANSI come straight from iocraft's own canvas writers, the same
+//! ones the terminal output uses. The SVG lays the canvas out on a monospace
+//! grid: per row, a rectangle for each stretch of background color and a text
+//! run for each stretch of same-styled cells, every run stretched to its exact
+//! cell width so box-drawing lines stay joined. Palette colors resolve to the
+//! xterm palette, and the terminal's default colors to light grey on black.
+
+use std::{
+    fmt::Write as _,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use iocraft::prelude::*;
+
+use crate::{config::ExportFormat, ownership};
+
+/// SVG cell size in pixels, for a 14 px monospace font.
+const CELL_W: f64 = 8.4;
+const CELL_H: f64 = 17.0;
+const FONT_SIZE: f64 = 14.0;
+
+/// The terminal's default foreground and background, in the SVG.
+const DEFAULT_FG: (u8, u8, u8) = (0xe5, 0xe5, 0xe5);
+const DEFAULT_BG: (u8, u8, u8) = (0x00, 0x00, 0x00);
+
+/// The 16 standard colors, as xterm draws them.
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// `canvas` in `format`.
+pub(crate) fn encode(canvas: &Canvas, format: ExportFormat) -> Vec<u8> {
+    let mut out = Vec::new();
+    match format {
+        // Writing to a `Vec` cannot fail.
+        ExportFormat::Text => canvas.write(&mut out).unwrap_or_default(),
+        ExportFormat::Ansi => canvas.write_ansi(&mut out).unwrap_or_default(),
+        ExportFormat::Svg => out = svg(canvas).into_bytes(),
+    }
+    out
+}
+
+/// Save `canvas` in `format` to the current directory as
+/// `pumas-YYYYMMDD-HHMMSS.<ext>` (UTC time `now`), returning the path. Under
+/// `sudo`, the file goes to the invoking user.
+pub(crate) fn save(
+    canvas: &Canvas,
+    format: ExportFormat,
+    now: SystemTime,
+) -> std::io::Result<PathBuf> {
+    let path = PathBuf::from(file_name(now, format));
+    std::fs::write(&path, encode(canvas, format))?;
+    ownership::hand_over(&path, &path, &[])?;
+    Ok(path)
+}
+
+/// `pumas-YYYYMMDD-HHMMSS.<ext>` for the UTC time `now`.
+fn file_name(now: SystemTime, format: ExportFormat) -> String {
+    let secs = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_date(days);
+    format!(
+        "pumas-{year:04}{month:02}{day:02}-{:02}{:02}{:02}.{}",
+        rest / 3600,
+        rest / 60 % 60,
+        rest % 60,
+        format.extension()
+    )
+}
+
+/// The proleptic Gregorian (year, month, day) `days` after 1970-01-01
+/// (Howard Hinnant's `civil_from_days`).
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The RGB value of a terminal color; `Reset` is `default`.
+fn rgb(color: Color, default: (u8, u8, u8)) -> (u8, u8, u8) {
+    match color {
+        Color::Reset => default,
+        Color::Black => ANSI_16[0],
+        Color::DarkRed => ANSI_16[1],
+        Color::DarkGreen => ANSI_16[2],
+        Color::DarkYellow => ANSI_16[3],
+        Color::DarkBlue => ANSI_16[4],
+        Color::DarkMagenta => ANSI_16[5],
+        Color::DarkCyan => ANSI_16[6],
+        Color::Grey => ANSI_16[7],
+        Color::DarkGrey => ANSI_16[8],
+        Color::Red => ANSI_16[9],
+        Color::Green => ANSI_16[10],
+        Color::Yellow => ANSI_16[11],
+        Color::Blue => ANSI_16[12],
+        Color::Magenta => ANSI_16[13],
+        Color::Cyan => ANSI_16[14],
+        Color::White => ANSI_16[15],
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::AnsiValue(i) => palette(i),
+    }
+}
+
+/// xterm's 256-color palette: the 16 standard colors, a 6×6×6 cube, then 24
+/// greys.
+fn palette(i: u8) -> (u8, u8, u8) {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match i {
+        0..=15 => ANSI_16[usize::from(i)],
+        16..=231 => {
+            let i = usize::from(i - 16);
+            (LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6])
+        }
+        _ => {
+            let grey = 8 + (i - 232) * 10;
+            (grey, grey, grey)
+        }
+    }
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Escape `text` for SVG character data.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+/// One cell's look: character, foreground, background and weight.
+fn cell_style(canvas: &Canvas, x: usize, y: usize) -> (String, Color, Option<Color>, bool) {
+    let cell = canvas.cell(x, y);
+    let bg = cell.and_then(|c| c.background_color);
+    let style = cell.and_then(|c| c.text_style());
+    let text = cell
+        .and_then(|c| c.text())
+        .filter(|t| !t.is_empty())
+        .unwrap_or(" ");
+    let fg = style.and_then(|s| s.color).unwrap_or(Color::Reset);
+    let bold = style.is_some_and(|s| s.weight == Weight::Bold);
+    (text.to_string(), fg, bg, bold)
+}
+
+/// `canvas` as an SVG document.
+fn svg(canvas: &Canvas) -> String {
+    let (cols, rows) = (canvas.width(), canvas.height());
+    #[expect(clippy::cast_precision_loss)]
+    let (width, height) = (cols as f64 * CELL_W, rows as f64 * CELL_H);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(DEFAULT_BG)
+    );
+    let _ = writeln!(
+        out,
+        r#"<g font-family="Menlo, Monaco, 'DejaVu Sans Mono', monospace" font-size="{FONT_SIZE}" xml:space="preserve">"#
+    );
+    for y in 0..rows {
+        #[expect(clippy::cast_precision_loss)]
+        let top = y as f64 * CELL_H;
+        let baseline = top + CELL_H * 0.78;
+        let cells: Vec<_> = (0..cols).map(|x| cell_style(canvas, x, y)).collect();
+
+        // Backgrounds.
+        let mut x = 0;
+        while x < cols {
+            let bg = cells[x].2;
+            let run = cells[x..].iter().take_while(|c| c.2 == bg).count();
+            if let Some(bg) = bg.filter(|&c| c != Color::Reset) {
+                #[expect(clippy::cast_precision_loss)]
+                let (left, w) = (x as f64 * CELL_W, run as f64 * CELL_W);
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{left:.1}" y="{top:.1}" width="{w:.1}" height="{CELL_H}" fill="{}"/>"#,
+                    hex(rgb(bg, DEFAULT_BG))
+                );
+            }
+            x += run;
+        }
+
+        // Text runs; blank stretches are left out.
+        let mut x = 0;
+        while x < cols {
+            let (fg, bold) = (cells[x].1, cells[x].3);
+            let run = cells[x..]
+                .iter()
+                .take_while(|c| c.1 == fg && c.3 == bold)
+                .count();
+            let text: String = cells[x..x + run].iter().map(|c| c.0.as_str()).collect();
+            if !text.trim().is_empty() {
+                #[expect(clippy::cast_precision_loss)]
+                let (left, w) = (x as f64 * CELL_W, run as f64 * CELL_W);
+                let weight = if bold { r#" font-weight="bold""# } else { "" };
+                let _ = writeln!(
+                    out,
+                    r#"<text x="{left:.1}" y="{baseline:.1}" fill="{}"{weight} textLength="{w:.1}" lengthAdjust="spacingAndGlyphs">{}</text>"#,
+                    hex(rgb(fg, DEFAULT_FG)),
+                    escape(&text)
+                );
+            }
+            x += run;
+        }
+    }
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn canvas() -> Canvas {
+        let mut el = element! {
+            View(flex_direction: FlexDirection::Row) {
+                View(background_color: Color::AnsiValue(4)) {
+                    Text(content: "a<b", color: Color::AnsiValue(2), weight: Weight::Bold)
+                }
+                Text(content: " x", color: Color::Rgb { r: 1, g: 2, b: 3 })
+            }
+        };
+        el.render(Some(6))
+    }
+
+    #[test]
+    fn text_and_ansi_come_from_the_canvas() {
+        let canvas = canvas();
+        let text = String::from_utf8(encode(&canvas, ExportFormat::Text)).unwrap();
+        assert_eq!(text, "a<b x\n");
+        let ansi = String::from_utf8(encode(&canvas, ExportFormat::Ansi)).unwrap();
+        assert!(ansi.contains("\x1b[38;5;2m"), "{ansi:?}");
+    }
+
+    #[test]
+    fn svg_keeps_colors_weight_and_escapes() {
+        let svg = String::from_utf8(encode(&canvas(), ExportFormat::Svg)).unwrap();
+        assert!(svg.starts_with("<svg "));
+        // Palette 4 background behind the first three cells.
+        assert!(
+            svg.contains(r##"width="25.2" height="17" fill="#0000ee""##),
+            "{svg}"
+        );
+        // Palette 2, bold, escaped.
+        assert!(
+            svg.contains(r##"fill="#00cd00" font-weight="bold""##),
+            "{svg}"
+        );
+        assert!(svg.contains(">a&lt;b</text>"), "{svg}");
+        assert!(svg.contains(r##"fill="#010203""##), "{svg}");
+    }
+
+    #[test]
+    fn palette_and_file_names() {
+        assert_eq!(palette(196), (255, 0, 0));
+        assert_eq!(palette(244), (128, 128, 128));
+        let now = UNIX_EPOCH + Duration::from_secs(1_792_333_530);
+        assert_eq!(
+            file_name(now, ExportFormat::Svg),
+            "pumas-20261018-142530.svg"
+        );
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+    }
+}
@@ -0,0 +1,100 @@
+//! Test-only frame fixtures shared by the UI modules' tests.
+
+use std::time::Duration;
+
+use crate::backend::frame::{
+    ColorRole, CpuFrame, Frame, FreqTable, GpuFrame, MemoryFrame, Meter, OverviewFrame, PowerFrame,
+    SparkText, SparkUnit, Thermals,
+};
+use crate::config::TimeRange;
+
+fn meter() -> Meter {
+    Meter {
+        title: String::new(),
+        ratio: 0.0,
+        role: ColorRole::GaugeFg,
+        spark: Vec::new(),
+        spark_span: Vec::new(),
+        spark_roles: Vec::new(),
+        spark_max: 0,
+        unit: SparkUnit::Percent,
+    }
+}
+
+fn thermals() -> Thermals {
+    Thermals {
+        pressure: "Nominal".to_string(),
+        role: ColorRole::Accent,
+    }
+}
+
+/// A minimal frame whose only distinguishing field is its timestamp.
+pub(crate) fn frame_at(secs: u64) -> Frame {
+    Frame {
+        elapsed: Duration::from_secs(secs),
+        range: TimeRange::Recent,
+        timeline: Vec::new(),
+        overview: overview_shape(0, 0, 0),
+        cpu: CpuFrame {
+            clusters: Vec::new(),
+            freq_table: FreqTable { rows: Vec::new() },
+        },
+        gpu: GpuFrame {
+            act_ratio: 0.0,
+            act_role: ColorRole::GaugeFg,
+            act_label: String::new(),
+            act_spark: Vec::new(),
+            act_spark_roles: Vec::new(),
+            act_spark_max: 0,
+            freq_value: String::new(),
+            freq_ratio: 0.0,
+            freq_label: String::new(),
+            freq_spark: Vec::new(),
+            freq_spark_max: 0,
+            power_value: String::new(),
+            power_spark: Vec::new(),
+            power_spark_max: 0,
+            act_history: Vec::new(),
+            act_history_roles: Vec::new(),
+            freq_history: Vec::new(),
+            peak_text: String::new(),
+            thermals: thermals(),
+            freq_table: FreqTable { rows: Vec::new() },
+        },
+        memory: MemoryFrame {
+            vm_lines: Vec::new(),
+            sysinfo_lines: Vec::new(),
+        },
+        power: PowerFrame {
+            layers: Vec::new(),
+            chart_max: 0,
+            unit: SparkUnit::Milliwatts,
+            ceilings: Vec::new(),
+            rows: Vec::new(),
+        },
+        uptime: String::new(),
+        soc_ceilings: Vec::new(),
+    }
+}
+
+/// An Overview frame with empty meters, only its cluster counts matter.
+pub(crate) fn overview_shape(n_e: usize, n_p: usize, n_s: usize) -> OverviewFrame {
+    OverviewFrame {
+        cpu_clusters_title: String::new(),
+        e_meters: (0..n_e).map(|_| meter()).collect(),
+        p_meters: (0..n_p).map(|_| meter()).collect(),
+        s_meters: (0..n_s).map(|_| meter()).collect(),
+        gpu: meter(),
+        ane: meter(),
+        package: SparkText {
+            title: String::new(),
+            spark: Vec::new(),
+            spark_span: Vec::new(),
+            spark_max: 0,
+            unit: SparkUnit::Milliwatts,
+        },
+        thermals: thermals(),
+        ram: meter(),
+        swap: meter(),
+    }
+}
@@ -0,0 +1,204 @@
+//! Sparkline hover details.
+//!
+//! Resolves a pointer position on the Overview, CPU or GPU tab to the history
+//! sample drawn under it and labels it with its value and age, e.g.
+//! `"42.0 % @ t-12s"`. A rolled-up Overview point also shows its bucket's
+//! range, e.g. `"42.0 % (10.0 %–80.0 %) @ t-12m"`.
+//! Sparklines only: line charts and heatmaps carry their own axes.
+//! The geometry comes from the layouts the views draw with
+//! ([`OverviewLayout`], [`CpuRowLayout`] and [`GpuLayout`]) and
+//! [`zoomed_source`]; the value is formatted by the sample's
+//! [`SparkUnit`], and its time comes from the frame's `timeline`.
+
+use std::time::Duration;
+
+use crate::{
+    backend::frame::{Frame, SparkUnit},
+    config::{ChartStyle, CpuView},
+    ui::layout::{CpuRowLayout, GpuLayout, OverviewLayout, SparkId, SparkRegion, zoomed_source},
+};
+
+/// The age of a sample, e.g. `"t-12s"`, or in minutes (`"t-12m"`) and hours
+/// (`"t-6h"`) from two of each.
+pub(crate) fn age_label(ago: Duration) -> String {
+    let secs = ago.as_secs_f64();
+    if secs < 120.0 {
+        format!("t-{}s", secs.round())
+    } else if secs < 7200.0 {
+        format!("t-{}m", (secs / 60.0).round())
+    } else {
+        format!("t-{}h", (secs / 3600.0).round())
+    }
+}
+
+/// What a sparkline is drawn from.
+struct SparkSource<'a> {
+    data: &'a [u64],
+    /// Bucket `(min, max)` per `data` point, when rolled up.
+    span: &'a [(u64, u64)],
+    unit: SparkUnit,
+}
+
+/// The sparkline data, bucket spans and sample unit behind `id`.
+fn spark_data(frame: &Frame, id: SparkId) -> Option<SparkSource<'_>> {
+    let f = &frame.overview;
+    let row = |data, unit| {
+        Some(SparkSource {
+            data,
+            span: &[],
+            unit,
+        })
+    };
+    let core = |i| frame.cpu.clusters.iter().flat_map(|c| &c.cpus).nth(i);
+    let meter = match id {
+        SparkId::Cluster(i) => f
+            .e_meters
+            .iter()
+            .chain(&f.p_meters)
+            .chain(&f.s_meters)
+            .nth(i)?,
+        SparkId::Gpu => &f.gpu,
+        SparkId::Ane => &f.ane,
+        SparkId::Package => {
+            return Some(SparkSource {
+                data: &f.package.spark,
+                span: &f.package.spark_span,
+                unit: f.package.unit,
+            });
+        }
+        SparkId::Ram => &f.ram,
+        SparkId::Swap => &f.swap,
+        SparkId::CoreActivity(i) => return row(&core(i)?.act_spark, SparkUnit::Percent),
+        SparkId::CoreFrequency(i) => return row(&core(i)?.freq_spark, SparkUnit::Percent),
+        SparkId::GpuActivity => return row(&frame.gpu.act_spark, SparkUnit::Percent),
+        SparkId::GpuFrequency => return row(&frame.gpu.freq_spark, SparkUnit::Percent),
+        SparkId::GpuPower => return row(&frame.gpu.power_spark, SparkUnit::Milliwatts),
+    };
+    Some(SparkSource {
+        data: &meter.spark,
+        span: &meter.spark_span,
+        unit: meter.unit,
+    })
+}
+
+/// The sparklines of `tab` as drawn at `width` × `height` in the given chart
+/// style and CPU view.
+pub(crate) fn spark_regions(
+    tab: usize,
+    frame: &Frame,
+    (width, height): (usize, usize),
+    (chart, cpu_view): (ChartStyle, CpuView),
+) -> Vec<SparkRegion> {
+    match tab {
+        0 if chart == ChartStyle::Sparkline => OverviewLayout::for_frame(width, &frame.overview)
+            .fit_height(height)
+            .spark_regions(&frame.overview),
+        1 if cpu_view == CpuView::Rows => CpuRowLayout::new(width).spark_regions(&frame.cpu),
+        2 => GpuLayout::new(width).spark_regions().to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+/// Label the sample of `tab` under tab-body position (`x`, `y`), for a tab
+/// `width` × `height` in the given chart style and CPU view, at `per_col`
+/// samples per column. `None` when the pointer is not over a drawn sparkline
+/// column.
+pub(crate) fn spark_detail(
+    tab: usize,
+    frame: &Frame,
+    size: (usize, usize),
+    (chart, cpu_view, per_col): (ChartStyle, CpuView, usize),
+    (x, y): (usize, usize),
+) -> Option<String> {
+    let (region, column) = spark_regions(tab, frame, size, (chart, cpu_view))
+        .into_iter()
+        .find_map(|r| Some((r, r.column_at(x, y)?)))?;
+    let SparkSource { data, span, unit } = spark_data(frame, region.id)?;
+    let index = zoomed_source(data, region.width, per_col, column)?;
+    let mut value = unit.format_exact(data[index]);
+    if let Some(&(min, max)) = span.get(index) {
+        value = format!(
+            "{value} ({}–{})",
+            unit.format_exact(min),
+            unit.format_exact(max)
+        );
+    }
+
+    // Every signal ends at the newest sample, as does the timeline.
+    let time = (frame.timeline.len() + index)
+        .checked_sub(data.len())
+        .and_then(|i| frame.timeline.get(i));
+    Some(match time {
+        Some(t) => format!("{value} @ {}", age_label(frame.elapsed.saturating_sub(*t))),
+        None => value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::fixtures::{frame_at, overview_shape};
+
+    /// The Overview detail at (`x`, `y`) of a 120 × 60 body.
+    fn overview_at(frame: &Frame, per_col: usize, at: (usize, usize)) -> Option<String> {
+        let style = (ChartStyle::Sparkline, CpuView::Rows, per_col);
+        spark_detail(0, frame, (120, 60), style, at)
+    }
+
+    #[test]
+    fn detail_names_the_sample_under_the_pointer() {
+        let mut overview = overview_shape(0, 2, 1);
+        overview.s_meters[0].spark = vec![10, 20, 30];
+        overview.package.spark = vec![4_200, 5_130];
+        let mut frame = frame_at(3);
+        frame.overview = overview;
+        frame.timeline = (0..=3).map(Duration::from_secs).collect();
+
+        // S-cluster sparkline: body row 9, from column 1.
+        assert_eq!(
+            overview_at(&frame, 1, (1, 9)).as_deref(),
+            Some("10.0 % @ t-2s")
+        );
+        assert_eq!(
+            overview_at(&frame, 1, (3, 11)).as_deref(),
+            Some("30.0 % @ t-0s")
+        );
+        // Past the last drawn column, or on a title row.
+        assert_eq!(overview_at(&frame, 1, (4, 9)), None);
+        assert_eq!(overview_at(&frame, 1, (1, 8)), None);
+        // Package (milliwatts), two samples at zoom 2 -> one column, the max,
+        // read to the hundredth of a watt.
+        assert_eq!(
+            overview_at(&frame, 2, (1, 22)).as_deref(),
+            Some("5.13 W @ t-0s")
+        );
+        // Rolled-up points add their bucket's range.
+        frame.overview.s_meters[0].spark_span = vec![(5, 15), (10, 40), (30, 30)];
+        assert_eq!(
+            overview_at(&frame, 1, (2, 9)).as_deref(),
+            Some("20.0 % (10.0 %–40.0 %) @ t-1s")
+        );
+    }
+
+    #[test]
+    fn gpu_rows_have_details_too() {
+        let mut frame = frame_at(3);
+        frame.gpu.power_spark = vec![1_250, 2_000, 3_000];
+        frame.timeline = (0..=3).map(Duration::from_secs).collect();
+        let style = (ChartStyle::Braille, CpuView::Rows, 1);
+
+        // Power sparkline: the bottom row of the GPU block, from column 1.
+        assert_eq!(
+            spark_detail(2, &frame, (120, 60), style, (1, 2)).as_deref(),
+            Some("1.25 W @ t-2s")
+        );
+        assert_eq!(spark_detail(2, &frame, (120, 60), style, (4, 2)), None);
+    }
+
+    #[test]
+    fn ages_switch_to_minutes_then_hours() {
+        assert_eq!(age_label(Duration::from_secs(119)), "t-119s");
+        assert_eq!(age_label(Duration::from_secs(750)), "t-13m");
+        assert_eq!(age_label(Duration::from_secs(6 * 3600)), "t-6h");
+    }
+}
@@ -0,0 +1,446 @@
+//! Key bindings: which key triggers which [`Action`].
+//!
+//! `PumasApp` never matches on raw key codes; it asks the [`Keymap`] for the
+//! action bound to each key event. The defaults below can be overridden per
+//! action from the `[keymap]` section of the configuration file, and the help
+//! overlay (`?`) lists whatever is currently bound. `Ctrl-c` is not part of
+//! the map: it always quits, so no configuration can trap the user in the
+//! fullscreen UI.
+//!
+//! Bindings are written as an optional `Ctrl-` / `Alt-` / `Shift-` prefix
+//! followed by a single character (`q`, `?`, `1`) or a key name (`Esc`,
+//! `Left`, `Tab`, `Space`, ...).
+
+use std::collections::BTreeMap;
+
+use iocraft::prelude::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{Result, error::Error, ui::components::tab_bar::TAB_TITLES};
+
+/// Something the user can ask the UI to do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Action {
+    Quit,
+    Help,
+    NextTab,
+    PrevTab,
+    /// Jump to the tab at this index.
+    Tab(usize),
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    Pause,
+    StepBack,
+    StepForward,
+    Faster,
+    Slower,
+    ZoomIn,
+    ZoomOut,
+    ChartStyle,
+    CpuView,
+    /// Cycle the history charts' time range.
+    Range,
+    /// Restart the recent and session peaks.
+    ResetPeaks,
+    /// Save the screen to a file.
+    Export,
+}
+
+/// Every action, in help-overlay order.
+const ACTIONS: [Action; 26] = [
+    Action::Help,
+    Action::Quit,
+    Action::NextTab,
+    Action::PrevTab,
+    Action::Tab(0),
+    Action::Tab(1),
+    Action::Tab(2),
+    Action::Tab(3),
+    Action::Tab(4),
+    Action::Tab(5),
+    Action::ScrollUp,
+    Action::ScrollDown,
+    Action::PageUp,
+    Action::PageDown,
+    Action::Pause,
+    Action::StepBack,
+    Action::StepForward,
+    Action::Faster,
+    Action::Slower,
+    Action::ZoomIn,
+    Action::ZoomOut,
+    Action::ChartStyle,
+    Action::CpuView,
+    Action::Range,
+    Action::ResetPeaks,
+    Action::Export,
+];
+
+impl Action {
+    /// The name used in the `[keymap]` configuration section.
+    pub(crate) fn name(self) -> String {
+        match self {
+            Action::Quit => "quit".to_string(),
+            Action::Help => "help".to_string(),
+            Action::NextTab => "next_tab".to_string(),
+            Action::PrevTab => "prev_tab".to_string(),
+            Action::Tab(i) => format!("tab_{}", i + 1),
+            Action::ScrollUp => "scroll_up".to_string(),
+            Action::ScrollDown => "scroll_down".to_string(),
+            Action::PageUp => "page_up".to_string(),
+            Action::PageDown => "page_down".to_string(),
+            Action::Pause => "pause".to_string(),
+            Action::StepBack => "step_back".to_string(),
+            Action::StepForward => "step_forward".to_string(),
+            Action::Faster => "faster".to_string(),
+            Action::Slower => "slower".to_string(),
+            Action::ZoomIn => "zoom_in".to_string(),
+            Action::ZoomOut => "zoom_out".to_string(),
+            Action::ChartStyle => "chart_style".to_string(),
+            Action::CpuView => "cpu_view".to_string(),
+            Action::Range => "range".to_string(),
+            Action::ResetPeaks => "reset_peaks".to_string(),
+            Action::Export => "export".to_string(),
+        }
+    }
+
+    /// One-line description for the help overlay.
+    pub(crate) fn description(self) -> String {
+        match self {
+            Action::Quit => "Quit".to_string(),
+            Action::Help => "Show / hide this help".to_string(),
+            Action::NextTab => "Next tab".to_string(),
+            Action::PrevTab => "Previous tab".to_string(),
+            Action::Tab(i) => format!("{} tab", TAB_TITLES[i]),
+            Action::ScrollUp => "Scroll up".to_string(),
+            Action::ScrollDown => "Scroll down".to_string(),
+            Action::PageUp => "Scroll up one page".to_string(),
+            Action::PageDown => "Scroll down one page".to_string(),
+            Action::Pause => "Pause / resume live updates".to_string(),
+            Action::StepBack => "Step back one sample (pauses)".to_string(),
+            Action::StepForward => "Step forward one sample".to_string(),
+            Action::Faster => "Sample faster".to_string(),
+            Action::Slower => "Sample slower".to_string(),
+            Action::ZoomIn => "Zoom sparklines in (shorter window)".to_string(),
+            Action::ZoomOut => "Zoom sparklines out (longer window)".to_string(),
+            Action::ChartStyle => "Switch sparklines / line charts".to_string(),
+            Action::CpuView => "Cycle CPU rows / activity / frequency heatmap".to_string(),
+            Action::Range => "Cycle chart range: recent / 1h / 6h / 24h".to_string(),
+            Action::ResetPeaks => "Reset peaks".to_string(),
+            Action::Export => "Save the screen to a file (--export-format)".to_string(),
+        }
+    }
+
+    /// The built-in bindings.
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "x", "Esc"],
+            Action::Help => &["?"],
+            Action::NextTab => &["Right", "Tab", "l"],
+            Action::PrevTab => &["Left", "BackTab", "h"],
+            Action::Tab(0) => &["1"],
+            Action::Tab(1) => &["2"],
+            Action::Tab(2) => &["3"],
+            Action::Tab(3) => &["4"],
+            Action::Tab(4) => &["5"],
+            Action::Tab(_) => &["6"],
+            Action::ScrollUp => &["Up", "k"],
+            Action::ScrollDown => &["Down", "j"],
+            Action::PageUp => &["PageUp"],
+            Action::PageDown => &["PageDown"],
+            Action::Pause => &["p", "Space"],
+            Action::StepBack => &["Shift-Left"],
+            Action::StepForward => &["Shift-Right"],
+            Action::Faster => &["+", "="],
+            Action::Slower => &["-"],
+            Action::ZoomIn => &["]"],
+            Action::ZoomOut => &["["],
+            Action::ChartStyle => &["c"],
+            Action::CpuView => &["m"],
+            Action::Range => &["r"],
+            Action::ResetPeaks => &["R"],
+            Action::Export => &["e"],
+        }
+    }
+}
+
+/// A key plus the modifiers that must be held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Binding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Binding {
+    /// Parse `"q"`, `"Ctrl-c"`, `"Shift-Left"`, `"-"`, ...
+    fn parse(s: &str) -> Option<Self> {
+        let mut rest = s;
+        let mut modifiers = KeyModifiers::NONE;
+        // A lone `-` (or `Ctrl--`) is the key itself, not a separator.
+        while rest.chars().count() > 1 {
+            let (prefix, modifier) = if let Some(r) = rest.strip_prefix("Ctrl-") {
+                (r, KeyModifiers::CONTROL)
+            } else if let Some(r) = rest.strip_prefix("Alt-") {
+                (r, KeyModifiers::ALT)
+            } else if let Some(r) = rest.strip_prefix("Shift-") {
+                (r, KeyModifiers::SHIFT)
+            } else {
+                break;
+            };
+            rest = prefix;
+            modifiers |= modifier;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest {
+                "Esc" => KeyCode::Esc,
+                "Enter" => KeyCode::Enter,
+                "Tab" => KeyCode::Tab,
+                "BackTab" => KeyCode::BackTab,
+                "Backspace" => KeyCode::Backspace,
+                "Space" => KeyCode::Char(' '),
+                "Left" => KeyCode::Left,
+                "Right" => KeyCode::Right,
+                "Up" => KeyCode::Up,
+                "Down" => KeyCode::Down,
+                "Home" => KeyCode::Home,
+                "End" => KeyCode::End,
+                "PageUp" => KeyCode::PageUp,
+                "PageDown" => KeyCode::PageDown,
+                _ => return None,
+            },
+        };
+        Some(Self::normalized(code, modifiers))
+    }
+
+    /// Terminals disagree on whether shifted characters (`?`, `+`, `A`) and
+    /// `BackTab` also report `SHIFT`; the character already encodes it, so
+    /// drop it for those keys on both the binding and the event side.
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Display form for the help overlay.
+    fn label(self) -> String {
+        let mut s = String::new();
+        for (m, name) in [
+            (KeyModifiers::CONTROL, "Ctrl-"),
+            (KeyModifiers::ALT, "Alt-"),
+            (KeyModifiers::SHIFT, "Shift-"),
+        ] {
+            if self.modifiers.contains(m) {
+                s.push_str(name);
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => s.push_str("Space"),
+            KeyCode::Char(c) => s.push(c),
+            KeyCode::Left => s.push('←'),
+            KeyCode::Right => s.push('→'),
+            KeyCode::Up => s.push('↑'),
+            KeyCode::Down => s.push('↓'),
+            KeyCode::BackTab => s.push_str("Shift-Tab"),
+            code => s.push_str(&format!("{code:?}")),
+        }
+        s
+    }
+}
+
+/// The fixed quit binding, outside the overridable map.
+const FORCE_QUIT: Binding = Binding {
+    code: KeyCode::Char('c'),
+    modifiers: KeyModifiers::CONTROL,
+};
+
+/// Resolved bindings, in declaration order.
+#[derive(Clone, Debug)]
+pub(crate) struct Keymap {
+    bindings: Vec<(Binding, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .flat_map(|&action| {
+                action.default_keys().iter().map(move |k| {
+                    let binding = Binding::parse(k).expect("valid default binding");
+                    (binding, action)
+                })
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// The defaults with the configured `[keymap]` overrides applied. An
+    /// overridden action loses all of its default keys, and a key claimed by
+    /// an override is taken away from whichever action had it by default.
+    /// `Ctrl-c` cannot be bound: it always quits.
+    pub(crate) fn with_overrides(overrides: &BTreeMap<String, Vec<String>>) -> Result<Self> {
+        let mut keymap = Self::default();
+        for (name, keys) in overrides {
+            let action = ACTIONS
+                .iter()
+                .copied()
+                .find(|a| a.name() == *name)
+                .ok_or_else(|| Error::Config(format!("keymap: unknown action `{name}`")))?;
+            let bindings = keys
+                .iter()
+                .map(|k| {
+                    Binding::parse(k)
+                        .ok_or_else(|| Error::Config(format!("keymap: invalid key `{k}`")))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if bindings.contains(&FORCE_QUIT) {
+                return Err(Error::Config(
+                    "keymap: `Ctrl-c` always quits and cannot be bound".into(),
+                ));
+            }
+            keymap
+                .bindings
+                .retain(|(b, a)| *a != action && !bindings.contains(b));
+            keymap
+                .bindings
+                .extend(bindings.into_iter().map(|b| (b, action)));
+        }
+        Ok(keymap)
+    }
+
+    /// The action bound to a key event, if any; always [`Action::Quit`] for
+    /// `Ctrl-c`.
+    pub(crate) fn action(&self, event: &KeyEvent) -> Option<Action> {
+        let pressed = Binding::normalized(event.code, event.modifiers);
+        if pressed == FORCE_QUIT {
+            return Some(Action::Quit);
+        }
+        self.bindings
+            .iter()
+            .find(|(b, _)| *b == pressed)
+            .map(|&(_, a)| a)
+    }
+
+    /// `(keys, description)` rows for the help overlay, one per bound action.
+    pub(crate) fn help_rows(&self) -> Vec<(String, String)> {
+        ACTIONS
+            .iter()
+            .filter_map(|&action| {
+                let mut keys: Vec<String> = self
+                    .bindings
+                    .iter()
+                    .filter(|(_, a)| *a == action)
+                    .map(|(b, _)| b.label())
+                    .collect();
+                if action == Action::Quit {
+                    keys.push(FORCE_QUIT.label());
+                }
+                (!keys.is_empty()).then(|| (keys.join(" "), action.description()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iocraft::prelude::KeyEventKind;
+
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        let mut event = KeyEvent::new(KeyEventKind::Press, code);
+        event.modifiers = modifiers;
+        event
+    }
+
+    fn key(c: char) -> KeyEvent {
+        press(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn defaults_cover_the_documented_keys() {
+        let km = Keymap::default();
+        assert_eq!(km.action(&key('q')), Some(Action::Quit));
+        assert_eq!(
+            km.action(&press(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        assert_eq!(km.action(&key('c')), Some(Action::ChartStyle));
+        assert_eq!(km.action(&key('l')), Some(Action::NextTab));
+        assert_eq!(km.action(&key('3')), Some(Action::Tab(2)));
+        assert_eq!(km.action(&key(' ')), Some(Action::Pause));
+        assert_eq!(
+            km.action(&press(KeyCode::Left, KeyModifiers::NONE)),
+            Some(Action::PrevTab)
+        );
+        assert_eq!(
+            km.action(&press(KeyCode::Left, KeyModifiers::SHIFT)),
+            Some(Action::StepBack)
+        );
+        // Shifted characters match whether or not the terminal reports SHIFT.
+        assert_eq!(
+            km.action(&press(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+            Some(Action::Help)
+        );
+        assert_eq!(
+            km.action(&press(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            Some(Action::PrevTab)
+        );
+    }
+
+    #[test]
+    fn binding_syntax() {
+        let b = Binding::parse("-").expect("lone dash");
+        assert_eq!(b.code, KeyCode::Char('-'));
+        let b = Binding::parse("Ctrl--").expect("ctrl dash");
+        assert_eq!(
+            (b.code, b.modifiers),
+            (KeyCode::Char('-'), KeyModifiers::CONTROL)
+        );
+        let b = Binding::parse("Ctrl-Alt-Down").expect("two modifiers");
+        assert_eq!(b.modifiers, KeyModifiers::CONTROL | KeyModifiers::ALT);
+        assert_eq!(b.label(), "Ctrl-Alt-↓");
+        assert!(Binding::parse("Hyper-x").is_none());
+        assert!(Binding::parse("").is_none());
+    }
+
+    #[test]
+    fn overrides_replace_defaults_and_steal_keys() {
+        let overrides = BTreeMap::from([
+            ("quit".to_string(), vec!["Ctrl-d".to_string()]),
+            ("pause".to_string(), vec!["x".to_string()]),
+            ("help".to_string(), vec![]),
+        ]);
+        let km = Keymap::with_overrides(&overrides).expect("valid overrides");
+        assert_eq!(km.action(&key('q')), None);
+        assert_eq!(
+            km.action(&press(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        // Ctrl-c is outside the map: it still quits.
+        assert_eq!(
+            km.action(&press(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        let quit_keys = km.help_rows().into_iter().find(|(_, d)| d == "Quit");
+        assert_eq!(quit_keys.map(|(k, _)| k).as_deref(), Some("Ctrl-d Ctrl-c"));
+        assert_eq!(km.action(&key('x')), Some(Action::Pause));
+        assert_eq!(km.action(&key('p')), None);
+        assert_eq!(km.action(&key('?')), None);
+        assert!(km.help_rows().iter().all(|(_, d)| !d.contains("help")));
+
+        let unknown = BTreeMap::from([("fly".to_string(), vec!["f".to_string()])]);
+        assert!(Keymap::with_overrides(&unknown).is_err());
+        let invalid = BTreeMap::from([("quit".to_string(), vec!["Hyper-q".to_string()])]);
+        assert!(Keymap::with_overrides(&invalid).is_err());
+        let stolen = BTreeMap::from([("pause".to_string(), vec!["Ctrl-c".to_string()])]);
+        assert!(Keymap::with_overrides(&stolen).is_err());
+    }
+}
@@ -9,7 +9,27 @@
 //!
 //! [`Frame`]: crate::backend::frame::Frame
 
-use crate::backend::frame::OverviewFrame;
+use crate::{
+    backend::frame::{ColorRole, CpuFrame, MemoryFrame, OverviewFrame, PowerFrame, SocRows},
+    config::{ChartStyle, CpuView},
+    ui::components::tab_bar::TAB_TITLES,
+};
+
+/// Screen rows above the tab body: the title bar (1) and the bordered tab bar
+/// (3).
+pub(crate) const BODY_TOP: usize = 4;
+
+/// Smallest usable terminal: the tab bar and a CPU core row need 60 columns,
+/// and below 12 rows the body is too short to scroll through meaningfully.
+pub(crate) const MIN_WIDTH: usize = 60;
+pub(crate) const MIN_HEIGHT: usize = 12;
+
+/// Most rows `--inline` draws, title bar included, so the shell history above
+/// it stays on screen.
+pub(crate) const INLINE_MAX_HEIGHT: usize = 24;
+
+/// Screen rows covered by the bordered tab bar.
+pub(crate) const TAB_BAR_ROWS: std::ops::RangeInclusive<usize> = 1..=3;
 
 /// 2-column gap between paired halves.
 pub(crate) const GAP: usize = 2;
@@ -22,6 +42,15 @@ const GAUGE_HEIGHT: usize = 2;
 /// Sparkline height.
 const SPARKLINE_HEIGHT: usize = 3;
 
+/// Plot rows of the GPU tab's history chart (line-chart style).
+pub(crate) const GPU_CHART_HEIGHT: usize = 8;
+
+/// Plot rows of the Power tab's stacked chart.
+pub(crate) const POWER_CHART_HEIGHT: usize = 12;
+
+/// X-axis tick spacing of the line charts, in cells.
+pub(crate) const CHART_TICK_SPACING: usize = 16;
+
 /// A blank row between stacked cluster blocks.
 const CLUSTER_SPACING: usize = 1;
 
@@ -34,6 +63,9 @@ pub(crate) fn num_blocks_for(n: usize) -> usize {
     n.div_ceil(2)
 }
 
+/// Samples per sparkline column for each zoom level (`]` zooms in, `[` out).
+pub(crate) const ZOOM_LEVELS: [usize; 5] = [1, 2, 4, 8, 16];
+
 /// Return the last `n` values of `data` (the original draws each sparkline from
 /// `as_slice_last_n(area.width)`; the backend ships full history, so the view
 /// trims here to its allocated width).
@@ -42,6 +74,123 @@ pub(crate) fn last_n(data: &[u64], n: usize) -> Vec<u64> {
     data[start..].to_vec()
 }
 
+/// Fit `data` into `n` columns at `per_col` samples per column: the last
+/// `n * per_col` values, max-pooled in groups of `per_col` so short spikes
+/// survive zooming out. Groups are aligned on the newest sample, so only the
+/// oldest column may be partial. `per_col <= 1` is exactly [`last_n`].
+pub(crate) fn zoomed(data: &[u64], n: usize, per_col: usize) -> Vec<u64> {
+    let per_col = per_col.max(1);
+    let tail = last_n(data, n * per_col);
+    tail.rchunks(per_col)
+        .rev()
+        .map(|group| group.iter().copied().max().unwrap_or(0))
+        .collect()
+}
+
+/// The index into `data` of the sample drawn at `column` of a [`zoomed`]
+/// sparkline `n` columns wide: the largest sample of that column's group (the
+/// newest one on ties), i.e. the one whose bar is on screen. `None` past the
+/// drawn columns.
+pub(crate) fn zoomed_source(
+    data: &[u64],
+    n: usize,
+    per_col: usize,
+    column: usize,
+) -> Option<usize> {
+    let per_col = per_col.max(1);
+    let start = data.len().saturating_sub(n * per_col);
+    let tail = &data[start..];
+    // `rchunks` groups from the newest sample; column 0 is the oldest group.
+    let groups = tail.len().div_ceil(per_col);
+    let group = groups.checked_sub(1)?.checked_sub(column)?;
+    let end = tail.len() - group * per_col;
+    let begin = end.saturating_sub(per_col);
+    let (offset, _) = tail[begin..end]
+        .iter()
+        .enumerate()
+        .max_by_key(|&(_, v)| *v)?;
+    Some(start + begin + offset)
+}
+
+/// The color role of each column of a [`zoomed`] sparkline: the role of the
+/// sample drawn there ([`zoomed_source`]), from `roles` (one per `data`
+/// sample). Stops early if `roles` is short.
+pub(crate) fn zoomed_roles(
+    data: &[u64],
+    roles: &[ColorRole],
+    n: usize,
+    per_col: usize,
+) -> Vec<ColorRole> {
+    let columns = last_n(data, n * per_col.max(1))
+        .len()
+        .div_ceil(per_col.max(1));
+    (0..columns)
+        .map_while(|column| zoomed_source(data, n, per_col, column).and_then(|i| roles.get(i)))
+        .copied()
+        .collect()
+}
+
+/// The tab whose label (plus its padding) is at screen `column`, when `row`
+/// is on the tab bar. Mirrors [`tab_bar`](crate::ui::components::tab_bar)'s
+/// `│ Overview │ CPU │ … │` string: a border column, then ` title ` per tab,
+/// separated by `│`.
+pub(crate) fn tab_at(column: usize, row: usize) -> Option<usize> {
+    if !TAB_BAR_ROWS.contains(&row) {
+        return None;
+    }
+    let mut start = 1; // after the left border
+    for (i, title) in TAB_TITLES.iter().enumerate() {
+        let end = start + title.chars().count() + 2; // ` title `
+        if (start..end).contains(&column) {
+            return Some(i);
+        }
+        start = end + 1; // the `│` divider
+    }
+    None
+}
+
+/// A sparkline the pointer can hover: the full-history ones on the Overview
+/// tab and the row sparklines of the CPU and GPU tabs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SparkId {
+    /// A CPU cluster, indexing E, then P, then S meters.
+    Cluster(usize),
+    Gpu,
+    Ane,
+    Package,
+    Ram,
+    Swap,
+    /// A core's activity on the CPU tab, indexing cores across clusters.
+    CoreActivity(usize),
+    /// A core's frequency on the CPU tab, indexed like `CoreActivity`.
+    CoreFrequency(usize),
+    /// GPU activity on the GPU tab.
+    GpuActivity,
+    /// GPU frequency on the GPU tab.
+    GpuFrequency,
+    /// GPU power on the GPU tab.
+    GpuPower,
+}
+
+/// Where a sparkline is drawn, relative to the top-left of the tab body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SparkRegion {
+    pub id: SparkId,
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl SparkRegion {
+    /// The sparkline column under body position (`x`, `y`), if any.
+    pub(crate) fn column_at(&self, x: usize, y: usize) -> Option<usize> {
+        let inside = (self.x..self.x + self.width).contains(&x)
+            && (self.y..self.y + self.height).contains(&y);
+        inside.then(|| x - self.x)
+    }
+}
+
 /// Computed Overview geometry. All widths/heights are in terminal cells.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct OverviewLayout {
@@ -60,14 +209,23 @@ pub(crate) struct OverviewLayout {
     /// Effective sparkline height. The original GPU/ANE/RAM/SWAP sparklines request
     /// a nominal 9 rows but the outer block is only sized for
     /// `GAUGE_HEIGHT + SPARKLINE_HEIGHT` inner rows, so the original clips them to
-    /// `SPARKLINE_HEIGHT` (= 3). Every Overview sparkline therefore renders at 3.
+    /// `SPARKLINE_HEIGHT` (= 3). Every Overview sparkline therefore renders at 3,
+    /// unless [`fit_height`](Self::fit_height) shrinks them (0 = compact cells,
+    /// no sparklines). For line charts this is the plot height.
     pub spark_height: usize,
+    /// How history is drawn.
+    pub chart: ChartStyle,
+    /// Rows a history block takes: `spark_height`, plus the time-tick row of
+    /// a line chart.
+    pub spark_rows: usize,
     /// Outer width of the Package panel (`7/10` of `width`).
     pub package_width: usize,
     /// Package sparkline width (`package_width - 2`, inside its borders).
     pub package_inner: usize,
     /// Outer width of the Thermals panel (`3/10` of `width`).
     pub thermals_width: usize,
+    /// Number of cluster blocks (paired rows) in the CPU Clusters panel.
+    pub blocks: usize,
     /// Outer height (incl. borders) of the CPU Clusters panel.
     pub cpu_panel_height: usize,
     /// Outer height of the GPU & ANE panel.
@@ -76,21 +234,33 @@ pub(crate) struct OverviewLayout {
     pub pkg_panel_height: usize,
     /// Outer height of the Memory & SWAP panel.
     pub mem_panel_height: usize,
+    /// History samples per sparkline column (the zoom level, see [`zoomed`]).
+    pub per_col: usize,
 }
 
 impl OverviewLayout {
     /// Compute the Overview geometry for a terminal `width` and the given
     /// number of efficiency / performance / super clusters.
     pub(crate) fn new(width: usize, n_e: usize, n_p: usize, n_s: usize) -> Self {
+        let blocks = num_blocks_for(n_e) + num_blocks_for(n_p) + num_blocks_for(n_s);
+        Self::sized(width, blocks, SPARKLINE_HEIGHT, ChartStyle::Sparkline)
+    }
+
+    /// The geometry for `blocks` cluster blocks at a given sparkline height
+    /// and chart style.
+    fn sized(width: usize, blocks: usize, spark_height: usize, chart: ChartStyle) -> Self {
         let inner_width = width.saturating_sub(2);
         let half_width = inner_width.saturating_sub(GAP) / 2;
+        let spark_rows = match chart {
+            ChartStyle::Braille if spark_height > 0 => spark_height + 1,
+            _ => spark_height,
+        };
 
         // CPU Clusters panel height = borders + per-block heights + the
         // CLUSTER_SPACING blank rows between blocks.
-        let n_blocks = num_blocks_for(n_e) + num_blocks_for(n_p) + num_blocks_for(n_s);
-        let cls_block_height = GAUGE_HEIGHT + SPARKLINE_HEIGHT;
+        let cls_block_height = GAUGE_HEIGHT + spark_rows;
         let cpu_block_height =
-            cls_block_height * n_blocks + n_blocks.saturating_sub(1) * CLUSTER_SPACING;
+            cls_block_height * blocks + blocks.saturating_sub(1) * CLUSTER_SPACING;
 
         // Package + Thermals share the row 7/10 vs 3/10 (the original Ratio).
         let package_width = width * 7 / 10;
@@ -102,21 +272,199 @@ impl OverviewLayout {
             half_width,
             gap: GAP,
             gauge_height: 1,
-            spark_height: SPARKLINE_HEIGHT,
+            spark_height,
+            chart,
+            spark_rows,
             package_width,
             package_inner: package_width.saturating_sub(2),
             thermals_width,
+            blocks,
             cpu_panel_height: 2 + cpu_block_height,
-            gpu_panel_height: 2 + (GAUGE_HEIGHT + SPARKLINE_HEIGHT),
-            pkg_panel_height: 2 + (PKG_TEXT_HEIGHT + SPARKLINE_HEIGHT),
-            mem_panel_height: 2 + (GAUGE_HEIGHT + SPARKLINE_HEIGHT),
+            gpu_panel_height: 2 + (GAUGE_HEIGHT + spark_rows),
+            pkg_panel_height: 2 + (PKG_TEXT_HEIGHT + spark_rows),
+            mem_panel_height: 2 + (GAUGE_HEIGHT + spark_rows),
+            per_col: 1,
         }
     }
 
+    /// Shrink the sparklines until the tab fits in `height` rows: from the
+    /// nominal 3 rows down to 1, then compact cells without sparklines. When
+    /// even those overflow, keep 1-row sparklines and let the view scroll.
+    pub(crate) fn fit_height(self, height: usize) -> Self {
+        let fitted = (0..=SPARKLINE_HEIGHT)
+            .rev()
+            .map(|h| Self::sized(self.width, self.blocks, h, self.chart))
+            .find(|lay| lay.content_height() <= height)
+            .unwrap_or_else(|| Self::sized(self.width, self.blocks, 1, self.chart));
+        fitted.with_per_col(self.per_col)
+    }
+
+    /// Draw history in the given style (line charts add a tick row).
+    pub(crate) fn with_chart(self, chart: ChartStyle) -> Self {
+        Self::sized(self.width, self.blocks, self.spark_height, chart).with_per_col(self.per_col)
+    }
+
     /// Compute the geometry directly from an [`OverviewFrame`].
     pub(crate) fn for_frame(width: usize, f: &OverviewFrame) -> Self {
         Self::new(width, f.e_meters.len(), f.p_meters.len(), f.s_meters.len())
     }
+
+    /// Set the sparkline zoom (history samples per column).
+    pub(crate) fn with_per_col(self, per_col: usize) -> Self {
+        Self { per_col, ..self }
+    }
+
+    /// Total height of the four stacked panels.
+    pub(crate) fn content_height(&self) -> usize {
+        self.cpu_panel_height
+            + self.gpu_panel_height
+            + self.pkg_panel_height
+            + self.mem_panel_height
+    }
+
+    /// Every sparkline's region, following the view's stacking: each panel's
+    /// content starts one row and one column inside its titled top border, and
+    /// a meter cell's sparkline sits below its title row and gauge row.
+    pub(crate) fn spark_regions(&self, f: &OverviewFrame) -> Vec<SparkRegion> {
+        let spark_top = GAUGE_HEIGHT;
+        let region = |id, x, y, width| SparkRegion {
+            id,
+            x,
+            y,
+            width,
+            height: self.spark_height,
+        };
+        let mut regions = Vec::new();
+
+        // CPU Clusters: per-kind pairs, E then P then S.
+        let mut y = 1;
+        let mut index = 0;
+        for kind in [f.e_meters.len(), f.p_meters.len(), f.s_meters.len()] {
+            for pair_start in (0..kind).step_by(2) {
+                if pair_start + 1 < kind {
+                    regions.push(region(
+                        SparkId::Cluster(index),
+                        1,
+                        y + spark_top,
+                        self.half_width,
+                    ));
+                    let right_x = 1 + self.half_width + self.gap;
+                    let right = SparkId::Cluster(index + 1);
+                    regions.push(region(right, right_x, y + spark_top, self.half_width));
+                    index += 2;
+                } else {
+                    let id = SparkId::Cluster(index);
+                    regions.push(region(id, 1, y + spark_top, self.inner_width));
+                    index += 1;
+                }
+                y += GAUGE_HEIGHT + self.spark_rows + CLUSTER_SPACING;
+            }
+        }
+
+        let right_x = 1 + self.half_width + self.gap;
+        let y = self.cpu_panel_height + 1 + spark_top;
+        regions.push(region(SparkId::Gpu, 1, y, self.half_width));
+        regions.push(region(SparkId::Ane, right_x, y, self.half_width));
+
+        let y = self.cpu_panel_height + self.gpu_panel_height + 1 + PKG_TEXT_HEIGHT;
+        regions.push(region(SparkId::Package, 1, y, self.package_inner));
+
+        let y =
+            self.cpu_panel_height + self.gpu_panel_height + self.pkg_panel_height + 1 + spark_top;
+        regions.push(region(SparkId::Ram, 1, y, self.half_width));
+        regions.push(region(SparkId::Swap, right_x, y, self.half_width));
+        regions
+    }
+}
+
+/// Heatmap label column width on the CPU tab (`"P0  12"` plus a gap).
+pub(crate) const HEAT_LABEL_WIDTH: usize = 7;
+
+/// Total height of the CPU tab: one titled panel per cluster (a row per core),
+/// or in the heatmap views one panel with a row per core, the tick row and the
+/// legend; plus the `Frequencies` table panel.
+pub(crate) fn cpu_content_height(f: &CpuFrame, view: CpuView) -> usize {
+    let cores: usize = f.clusters.iter().map(|c| c.cpus.len()).sum();
+    let cores = match view {
+        CpuView::Rows => 2 * f.clusters.len() + cores,
+        CpuView::Activity | CpuView::Frequency => 2 + cores + 2,
+    };
+    cores + 2 + FREQ_TABLE_INNER_ROWS
+}
+
+/// Total height of the GPU tab: the GPU block, Thermals and `Frequencies`
+/// panels, plus the history chart panel (a legend row, the plot and its tick
+/// row) in line-chart style.
+pub(crate) fn gpu_content_height(chart: ChartStyle) -> usize {
+    let base = (2 + 2) + (2 + 1) + (2 + FREQ_TABLE_INNER_ROWS);
+    match chart {
+        ChartStyle::Sparkline => base,
+        ChartStyle::Braille => base + 2 + 1 + GPU_CHART_HEIGHT + 1,
+    }
+}
+
+/// Total height of the Power tab: the chart panel (the plot and its tick row)
+/// and the table panel (a header row, then a row per domain and the package).
+pub(crate) fn power_content_height(f: &PowerFrame) -> usize {
+    (2 + POWER_CHART_HEIGHT + 1) + (2 + 1 + f.rows.len())
+}
+
+/// Inner rows of the Memory tab's `VM Statistics` block (`18 - 2` borders).
+pub(crate) const VM_INNER_ROWS: usize = 16;
+
+/// Inner rows of the Memory tab's `Sysinfo Statistics` block (`8 - 2`
+/// borders).
+pub(crate) const SYSINFO_INNER_ROWS: usize = 6;
+
+/// Total height of the Memory tab: the 1-row top margin, then the two text
+/// blocks, each padded to its fixed inner rows (or as tall as its lines).
+pub(crate) fn memory_content_height(f: &MemoryFrame) -> usize {
+    1 + (2 + f.vm_lines.len().max(VM_INNER_ROWS))
+        + (2 + f.sysinfo_lines.len().max(SYSINFO_INNER_ROWS))
+}
+
+/// Total height of the SoC tab: a row per SoC row and power ceiling, then the
+/// uptime.
+pub(crate) fn soc_content_height(rows: &SocRows, ceilings: &[(String, String)]) -> usize {
+    rows.rows.len() + ceilings.len() + 1
+}
+
+/// Inner rows of the `Frequencies` table block (`FREQUENCY_TABLE_HEIGHT`); the
+/// body is padded to this so the bordered block is always `2 + 5` tall.
+pub(crate) const FREQ_TABLE_INNER_ROWS: usize = 5;
+
+/// The scroll-indicator thumb for a `viewport`-row window onto `content`
+/// rows scrolled down by `offset`: its first row and length, both within the
+/// viewport. `None` when everything fits.
+pub(crate) fn scroll_thumb(
+    viewport: usize,
+    content: usize,
+    offset: usize,
+) -> Option<(usize, usize)> {
+    let max_offset = content.checked_sub(viewport).filter(|&m| m > 0)?;
+    let len = (viewport * viewport / content).max(1);
+    let start = offset.min(max_offset) * (viewport - len) / max_offset;
+    Some((start, len))
+}
+
+/// Top-left screen position of a `width` × `height` tooltip for the pointer at
+/// (`column`, `row`): just below the pointer (above it near the bottom edge),
+/// shifted left to stay on a `screen_w` × `screen_h` screen.
+pub(crate) fn tooltip_origin(
+    column: usize,
+    row: usize,
+    width: usize,
+    height: usize,
+    screen_w: usize,
+    screen_h: usize,
+) -> (usize, usize) {
+    let x = column.min(screen_w.saturating_sub(width));
+    let y = if row + 1 + height <= screen_h {
+        row + 1
+    } else {
+        row.saturating_sub(height)
+    };
+    (x, y)
 }
 
 // ─── CPU/GPU row geometry ───────────────────────────
@@ -130,10 +478,30 @@ const FREQ_LABEL_WIDTH: usize = 6;
 /// Frequency value column width (10 columns, e.g. `"1085 MHz "`).
 const FREQ_VALUE_WIDTH: usize = 10;
 
-/// Sparkline history slot: 8 data cells + 1 trailing space.
+/// Data cells of the CPU and GPU row sparklines.
+pub(crate) const SPARK_CELLS: usize = 8;
+
+/// Data cells of a row sparkline drawn in a `slot`-wide history slot (the
+/// last column is the gap before the gauge).
+pub(crate) fn slot_cells(slot: usize) -> usize {
+    slot.saturating_sub(1)
+}
+
+/// A one-row sparkline region in a `slot`-wide history slot at (`x`, `y`).
+fn slot_region(id: SparkId, x: usize, y: usize, slot: usize) -> SparkRegion {
+    SparkRegion {
+        id,
+        x,
+        y,
+        width: slot_cells(slot),
+        height: 1,
+    }
+}
+
+/// Sparkline history slot: [`SPARK_CELLS`] data cells + 1 trailing space.
 /// The sparkline emits exactly 8 cells; the `+1` is one trailing space before
 /// the gauge so the gauge column-aligns with the original.
-const HISTORY_SLOT: usize = 9;
+const HISTORY_SLOT: usize = SPARK_CELLS + 1;
 
 /// Geometry of one CPU core row.
 ///
@@ -192,6 +560,29 @@ impl CpuRowLayout {
     pub(crate) fn freq_bar(&self, label_len: usize) -> usize {
         self.freq_gauge_w.saturating_sub(label_len + 1)
     }
+
+    /// Every row sparkline of the CPU tab in its rows view, following the
+    /// view's stacking: one bordered panel per cluster, a row per core inside
+    /// its `±1` borders.
+    pub(crate) fn spark_regions(&self, f: &CpuFrame) -> Vec<SparkRegion> {
+        let act_x = 1 + self.id_w;
+        let freq_x = act_x + self.act_spark_slot + self.act_gauge_w + self.freq_label_w;
+        let mut regions = Vec::new();
+        let mut y = 0;
+        let mut core = 0;
+        for c in &f.clusters {
+            for i in 0..c.cpus.len() {
+                let row = y + 1 + i;
+                let act = SparkId::CoreActivity(core);
+                regions.push(slot_region(act, act_x, row, self.act_spark_slot));
+                let freq = SparkId::CoreFrequency(core);
+                regions.push(slot_region(freq, freq_x, row, self.freq_spark_slot));
+                core += 1;
+            }
+            y += 2 + c.cpus.len();
+        }
+        regions
+    }
 }
 
 /// Geometry of the single GPU block.
@@ -250,11 +641,23 @@ impl GpuLayout {
     pub(crate) fn freq_bar(&self, label_len: usize) -> usize {
         self.freq_gauge_w.saturating_sub(label_len + 1)
     }
+
+    /// The three sparklines of the GPU block, which tops the GPU tab: the
+    /// top row inside its top border, the bottom row below it.
+    pub(crate) fn spark_regions(&self) -> [SparkRegion; 3] {
+        let freq_x = 1 + self.act_spark_slot + self.act_gauge_w + self.freq_label_w;
+        [
+            slot_region(SparkId::GpuActivity, 1, 1, self.act_spark_slot),
+            slot_region(SparkId::GpuFrequency, freq_x, 1, self.freq_spark_slot),
+            slot_region(SparkId::GpuPower, 1, 2, self.power_spark_slot),
+        ]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::fixtures::overview_shape;
 
     #[test]
     fn num_blocks_for_is_ceil_half() {
@@ -273,6 +676,139 @@ mod tests {
         assert_eq!(last_n(&[], 4), Vec::<u64>::new());
     }
 
+    #[test]
+    fn zoomed_max_pools_from_the_newest_sample() {
+        let data = [1, 5, 2, 3, 9, 4, 7];
+        // One sample per column is plain trimming.
+        assert_eq!(zoomed(&data, 3, 1), last_n(&data, 3));
+        // Two per column: [5,2] [3,9] [4,7] -> maxima.
+        assert_eq!(zoomed(&data, 3, 2), vec![5, 9, 7]);
+        // Not enough history: the oldest group is partial.
+        assert_eq!(zoomed(&data, 4, 3), vec![1, 5, 9]);
+        assert_eq!(zoomed(&[], 4, 2), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn zoomed_source_points_at_the_drawn_sample() {
+        let data = [1, 5, 2, 3, 9, 4, 7];
+        assert_eq!(zoomed_source(&data, 3, 1, 0), Some(4));
+        assert_eq!(zoomed_source(&data, 3, 1, 2), Some(6));
+        assert_eq!(zoomed_source(&data, 3, 1, 3), None);
+        // [5,2] [3,9] [4,7]: each column's maximum.
+        assert_eq!(zoomed_source(&data, 3, 2, 0), Some(1));
+        assert_eq!(zoomed_source(&data, 3, 2, 1), Some(4));
+        assert_eq!(zoomed_source(&data, 3, 2, 2), Some(6));
+        // Partial oldest group: [1] [5,2,3] [9,4,7].
+        assert_eq!(zoomed_source(&data, 4, 3, 0), Some(0));
+        assert_eq!(zoomed_source(&data, 4, 3, 1), Some(1));
+        assert_eq!(zoomed_source(&data, 4, 3, 3), None);
+        // Consistent with `zoomed`.
+        for col in 0..3 {
+            let i = zoomed_source(&data, 3, 2, col).expect("drawn column");
+            assert_eq!(data[i], zoomed(&data, 3, 2)[col]);
+        }
+        assert_eq!(zoomed_source(&[], 4, 2, 0), None);
+    }
+
+    #[test]
+    fn line_charts_add_a_tick_row_per_block() {
+        let l = OverviewLayout::new(120, 1, 2, 0).with_chart(ChartStyle::Braille);
+        assert_eq!((l.spark_height, l.spark_rows), (3, 4));
+        let sparks = OverviewLayout::new(120, 1, 2, 0);
+        // Two cluster blocks, GPU, Package and Memory panels.
+        assert_eq!(l.content_height(), sparks.content_height() + 5);
+        let compact = l.fit_height(18);
+        assert_eq!((compact.spark_height, compact.spark_rows), (0, 0));
+        assert_eq!(gpu_content_height(ChartStyle::Sparkline), 14);
+        assert_eq!(gpu_content_height(ChartStyle::Braille), 26);
+    }
+
+    #[test]
+    fn zoomed_roles_follow_the_drawn_samples() {
+        use ColorRole::{Critical, HistoryFg as Fg, Warning};
+        let data = [1, 5, 2, 3, 9, 4, 7];
+        let roles = [Fg, Warning, Fg, Fg, Critical, Fg, Warning];
+        assert_eq!(zoomed_roles(&data, &roles, 3, 1), [Critical, Fg, Warning]);
+        assert_eq!(
+            zoomed_roles(&data, &roles, 3, 2),
+            [Warning, Critical, Warning]
+        );
+        assert!(zoomed_roles(&data, &[], 3, 2).is_empty());
+    }
+
+    #[test]
+    fn tab_hit_testing_follows_the_labels() {
+        // `│ Overview │ CPU │ GPU │ Memory │ SoC │ Power │`
+        assert_eq!(tab_at(0, 2), None, "left border");
+        assert_eq!(tab_at(1, 2), Some(0));
+        assert_eq!(tab_at(10, 2), Some(0));
+        assert_eq!(tab_at(11, 2), None, "divider");
+        assert_eq!(tab_at(13, 1), Some(1));
+        assert_eq!(tab_at(28, 3), Some(3));
+        assert_eq!(tab_at(36, 2), Some(4));
+        assert_eq!(tab_at(41, 2), Some(5));
+        assert_eq!(tab_at(46, 2), None, "right border");
+        assert_eq!(tab_at(13, 4), None, "below the tab bar");
+    }
+
+    #[test]
+    fn spark_regions_follow_the_panel_stack() {
+        let f = OverviewLayout::new(120, 0, 2, 1);
+        // Fixture shape of the views snapshots: one P pair, one single S.
+        let frame = overview_shape(0, 2, 1);
+        let regions = f.spark_regions(&frame);
+        let ids: Vec<SparkId> = regions.iter().map(|r| r.id).collect();
+        assert_eq!(
+            ids,
+            [
+                SparkId::Cluster(0),
+                SparkId::Cluster(1),
+                SparkId::Cluster(2),
+                SparkId::Gpu,
+                SparkId::Ane,
+                SparkId::Package,
+                SparkId::Ram,
+                SparkId::Swap,
+            ]
+        );
+        // P0: title row 1, gauge row 2, sparkline rows 3..6.
+        assert_eq!((regions[0].x, regions[0].y, regions[0].width), (1, 3, 58));
+        assert_eq!((regions[1].x, regions[1].y), (61, 3));
+        // S: below the pair and the spacing row, full inner width.
+        assert_eq!((regions[2].x, regions[2].y, regions[2].width), (1, 9, 118));
+        // GPU panel starts after the CPU panel (2 borders + 2 blocks + gap).
+        assert_eq!(f.cpu_panel_height, 13);
+        assert_eq!(regions[3].y, 13 + 3);
+        assert_eq!(regions[5].y, 13 + 7 + 2);
+        assert_eq!(regions[6].y, 13 + 7 + 6 + 3);
+        assert_eq!(f.content_height(), 13 + 7 + 6 + 7);
+
+        assert_eq!(regions[2].column_at(1, 9), Some(0));
+        assert_eq!(regions[2].column_at(118, 11), Some(117));
+        assert_eq!(regions[2].column_at(119, 11), None);
+        assert_eq!(regions[2].column_at(5, 12), None);
+    }
+
+    #[test]
+    fn scroll_thumb_tracks_the_offset() {
+        assert_eq!(scroll_thumb(20, 20, 0), None);
+        assert_eq!(scroll_thumb(20, 10, 0), None);
+        // Half the content visible: a half-height thumb.
+        assert_eq!(scroll_thumb(20, 40, 0), Some((0, 10)));
+        assert_eq!(scroll_thumb(20, 40, 10), Some((5, 10)));
+        assert_eq!(scroll_thumb(20, 40, 20), Some((10, 10)));
+        assert_eq!(scroll_thumb(20, 40, 99), Some((10, 10)), "clamped");
+        // Very tall content: the thumb never vanishes.
+        assert_eq!(scroll_thumb(10, 1000, 990), Some((9, 1)));
+    }
+
+    #[test]
+    fn tooltips_stay_on_screen() {
+        assert_eq!(tooltip_origin(10, 5, 20, 3, 80, 24), (10, 6));
+        assert_eq!(tooltip_origin(70, 5, 20, 3, 80, 24), (60, 6));
+        assert_eq!(tooltip_origin(10, 22, 20, 3, 80, 24), (10, 19));
+    }
+
     #[test]
     fn paired_halves_plus_gap_fill_inner_width() {
         // At width 120 each paired cluster half is 58 wide with a 2-col gap.
@@ -309,6 +845,27 @@ mod tests {
         assert_eq!(big.cpu_panel_height, 43);
     }
 
+    #[test]
+    fn fit_height_shrinks_sparklines_then_compacts() {
+        // Nominal: 13 + 7 + 6 + 7 = 33 rows.
+        let l = OverviewLayout::new(120, 0, 2, 1);
+        assert_eq!(l.fit_height(40), l);
+        assert_eq!(l.fit_height(33).spark_height, 3);
+        // One row less per sparkline: 11 + 6 + 5 + 6 = 28.
+        let two = l.fit_height(32);
+        assert_eq!(two.spark_height, 2);
+        assert_eq!(two.content_height(), 28);
+        assert_eq!(l.fit_height(23).spark_height, 1);
+        // Compact: 7 + 4 + 3 + 4 = 18.
+        let compact = l.fit_height(18);
+        assert_eq!(compact.spark_height, 0);
+        assert_eq!(compact.content_height(), 18);
+        // Too small even for compact cells: 1-row sparklines, scrolled.
+        assert_eq!(l.fit_height(10).spark_height, 1);
+        // The zoom survives refitting.
+        assert_eq!(l.with_per_col(4).fit_height(20).per_col, 4);
+    }
+
     #[test]
     fn fixed_panel_heights() {
         let l = OverviewLayout::new(120, 0, 2, 1);
@@ -2,13 +2,23 @@
 //!
 //! The iocraft frontend: [`app_root::PumasApp`] drives the render loop, fed by
 //! the backend `Frame` data plane. Tabs live under [`views`], shared widgets
-//! under [`components`], and all pixel geometry is computed in [`layout`].
+//! under [`components`], and all pixel geometry is computed in [`layout`];
+//! [`screen`] assembles them into the whole screen, which [`export`] can save
+//! as text, ANSI or SVG. [`cast`] records the terminal output of a session.
 
 pub(crate) mod app_root;
+pub(crate) mod cast;
 pub(crate) mod components;
+pub(crate) mod export;
+pub(crate) mod hover;
+pub(crate) mod keymap;
 pub(crate) mod layout;
+pub(crate) mod screen;
+pub(crate) mod scrollback;
 pub(crate) mod theme;
 pub(crate) mod views;
 
+#[cfg(test)]
+mod fixtures;
 #[cfg(test)]
 mod snapshot;
@@ -0,0 +1,227 @@
+//! The whole screen for one frame: title bar, tab bar and the selected tab's
+//! scrolled body, plus the overlays (scroll indicator, sample tooltip, help).
+//!
+//! [`screen`] is a pure function of a [`Screen`], so the live
+//! [`PumasApp`](super::app_root::PumasApp), the screen export key and
+//! `pumas snapshot` all draw exactly the same canvas.
+
+use iocraft::prelude::*;
+
+use crate::{
+    backend::frame::{Frame, RenderedHeader, SocRows},
+    config::{ChartStyle, CpuView},
+    ui::{
+        components::{
+            scrollbar::scrollbar,
+            tab_bar::tab_bar,
+            title_bar::title_bar,
+            tooltip::{TOOLTIP_HEIGHT, tooltip, tooltip_width},
+        },
+        hover::spark_detail,
+        layout::{
+            BODY_TOP, OverviewLayout, cpu_content_height, gpu_content_height,
+            memory_content_height, power_content_height, scroll_thumb, soc_content_height,
+            tooltip_origin,
+        },
+        theme::Theme,
+        views::{
+            Clock, cpu::cpu, gpu::gpu, help::help, memory::memory, overview::overview,
+            power::power, soc::soc,
+        },
+    },
+};
+
+/// Height of a tab's content at `width` once fitted to `height` rows in the
+/// given chart style and CPU view, for scrolling. The SoC tab draws the
+/// session-static `soc_rows`.
+pub(crate) fn content_height(
+    tab: usize,
+    (frame, soc_rows): (&Frame, &SocRows),
+    (width, height): (usize, usize),
+    (chart, cpu_view): (ChartStyle, CpuView),
+) -> usize {
+    match tab {
+        0 => OverviewLayout::for_frame(width, &frame.overview)
+            .with_chart(chart)
+            .fit_height(height)
+            .content_height(),
+        1 => cpu_content_height(&frame.cpu, cpu_view),
+        2 => gpu_content_height(chart),
+        3 => memory_content_height(&frame.memory),
+        4 => soc_content_height(soc_rows, &frame.soc_ceilings),
+        5 => power_content_height(&frame.power),
+        _ => 0,
+    }
+}
+
+/// Everything one screen is drawn from.
+pub(crate) struct Screen<'a> {
+    /// The frame to show.
+    pub frame: &'a Frame,
+    /// Session-static title-bar strings.
+    pub header: &'a RenderedHeader,
+    /// Session-static SoC-tab rows.
+    pub soc_rows: &'a SocRows,
+    /// Title-bar status (e.g. `PAUSED @ t-42s`).
+    pub status: Option<String>,
+    /// Selected tab.
+    pub tab: usize,
+    /// Screen width and height.
+    pub size: (usize, usize),
+    /// History samples per chart column.
+    pub per_col: usize,
+    /// Chart style.
+    pub chart: ChartStyle,
+    /// CPU tab layout.
+    pub cpu_view: CpuView,
+    /// Sample rate (ms), shown in the tab bar.
+    pub sample_rate_ms: u16,
+    /// Rows the body is scrolled by (clamped to the content).
+    pub scroll: usize,
+    /// Mouse pointer, for the sample tooltip.
+    pub pointer: Option<(usize, usize)>,
+    /// Help overlay rows, when it is open.
+    pub help: Option<Vec<(String, String)>>,
+    /// Inline mode: no tab bar, and the body no taller than its content.
+    pub inline: bool,
+    /// Theme.
+    pub theme: Theme,
+}
+
+/// Render `s`: exactly its width, and its height (inline, no taller than the
+/// content needs).
+pub(crate) fn screen(s: &Screen) -> AnyElement<'static> {
+    let (w, height) = s.size;
+    let (frame, theme) = (s.frame, s.theme);
+    // Inline there is no tab bar: the body starts under the title bar.
+    let body_top = if s.inline { 1 } else { BODY_TOP };
+    let body_h = height.saturating_sub(body_top);
+    let content_h = content_height(
+        s.tab,
+        (frame, s.soc_rows),
+        (w, body_h),
+        (s.chart, s.cpu_view),
+    );
+    let max_scroll = content_h.saturating_sub(body_h);
+
+    let clock = Clock {
+        timeline: &frame.timeline,
+        elapsed: frame.elapsed,
+    };
+    let per_col = s.per_col;
+    let body: AnyElement<'static> = match s.tab {
+        1 => cpu(&frame.cpu, s.cpu_view, clock, w, per_col, theme),
+        2 => gpu(&frame.gpu, clock, w, (per_col, s.chart), theme),
+        3 => memory(&frame.memory, w, theme),
+        4 => soc(s.soc_rows, (&frame.soc_ceilings, &frame.uptime), w, theme),
+        5 => power(&frame.power, clock, w, per_col, theme),
+        _ => overview(&frame.overview, clock, w, body_h, per_col, s.chart, theme),
+    };
+    let mut info = format!("{} ms · zoom {per_col}x", s.sample_rate_ms);
+    if let Some(range) = frame.range.label() {
+        info += &format!(" · {range}");
+    }
+
+    // The body scrolls by shifting it up inside a clipped viewport. Inline,
+    // the viewport shrinks to the content rather than padding the scrollback.
+    let offset = s.scroll.min(max_scroll);
+    let body_h = if s.inline {
+        body_h.min(content_h)
+    } else {
+        body_h
+    };
+    #[expect(clippy::cast_possible_truncation)]
+    let (viewport_h, shift) = (body_h as u32, -(offset as i32));
+    let body = element! {
+        View(height: viewport_h, overflow: Overflow::Hidden, flex_direction: FlexDirection::Column) {
+            View(margin_top: shift, flex_direction: FlexDirection::Column, flex_shrink: 0.0) {
+                #(vec![body])
+            }
+        }
+    }
+    .into_any();
+
+    let header = s.header;
+    let mut chrome = vec![title_bar(
+        header.program_name.clone(),
+        header.machine_desc.clone(),
+        s.status.clone(),
+        theme,
+        w,
+    )];
+    if !s.inline {
+        chrome.push(tab_bar(s.tab, Some(info), theme, w));
+    }
+    chrome.push(body);
+
+    // Scroll indicator over the right border, when the tab overflows.
+    if let Some((start, len)) = scroll_thumb(body_h, content_h, offset) {
+        #[expect(clippy::cast_possible_truncation)]
+        let (top, left) = ((body_top + start) as u32, w.saturating_sub(1) as u32);
+        let thumb = scrollbar(len, theme);
+        chrome.push(
+            element! {
+                View(position: Position::Absolute, top: top, left: left) {
+                    #(vec![thumb])
+                }
+            }
+            .into_any(),
+        );
+    }
+
+    // Sample details under the pointer (sparklines only; line charts and
+    // heatmaps carry their own axes). Inline leaves the mouse uncaptured.
+    let detail = match s.pointer {
+        Some((column, row)) if !s.inline && row >= BODY_TOP => spark_detail(
+            s.tab,
+            frame,
+            (w, body_h),
+            (s.chart, s.cpu_view, per_col),
+            (column, row - BODY_TOP + offset),
+        )
+        .map(|text| (column, row, text)),
+        _ => None,
+    };
+    if let Some((column, row, text)) = detail {
+        let tip_w = tooltip_width(&text);
+        let (x, y) = tooltip_origin(column, row, tip_w, TOOLTIP_HEIGHT, w, height);
+        #[expect(clippy::cast_possible_truncation)]
+        let (left, top) = (x as u32, y as u32);
+        let tip = tooltip(text, theme);
+        chrome.push(
+            element! {
+                View(position: Position::Absolute, top: top, left: left) {
+                    #(vec![tip])
+                }
+            }
+            .into_any(),
+        );
+    }
+
+    if let Some(rows) = &s.help {
+        let overlay = help(rows, w, theme);
+        #[expect(clippy::cast_possible_truncation)]
+        let top = body_top as u32;
+        chrome.push(
+            element! {
+                View(position: Position::Absolute, top: top, left: 0u32) {
+                    #(vec![overlay])
+                }
+            }
+            .into_any(),
+        );
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    let (width, total_h) = (w as u32, (body_top + body_h) as u32);
+    element! {
+        View(
+            flex_direction: FlexDirection::Column,
+            width: width,
+            height: total_h,
+        ) {
+            #(chrome)
+        }
+    }
+    .into_any()
+}
@@ -0,0 +1,153 @@
+//! Frame scrollback: pause + time-travel cursor.
+//!
+//! The frontend keeps the last `capacity` received [`Frame`]s so the user can
+//! freeze the view and step back through past samples. The collector is never
+//! paused: frames keep arriving and are appended here, but while paused the
+//! cursor stays pinned to the same sample, so the screen does not move.
+//!
+//! This is not metric history — every retained `Frame` is already fully
+//! prepared by the backend (each one carries the sparkline window as it was at
+//! that sample), so rendering a past frame needs no recomputation.
+
+use std::collections::VecDeque;
+
+use crate::backend::frame::Frame;
+
+/// Bounded log of received frames plus the pause/cursor state.
+pub(crate) struct Scrollback {
+    /// Received frames, oldest first.
+    frames: VecDeque<Frame>,
+    /// Maximum number of retained frames.
+    capacity: usize,
+    /// `true` while the view is frozen.
+    paused: bool,
+    /// Samples back from the newest frame (0 = newest). Only meaningful while
+    /// paused.
+    cursor: usize,
+}
+
+impl Scrollback {
+    /// Create an empty scrollback retaining at most `capacity` frames.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            paused: false,
+            cursor: 0,
+        }
+    }
+
+    /// Append a newly received frame, evicting the oldest when full. While
+    /// paused the cursor moves back by one so it keeps pointing at the same
+    /// sample (clamped to the oldest retained frame).
+    pub(crate) fn push(&mut self, frame: Frame) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+        if self.paused {
+            self.cursor = (self.cursor + 1).min(self.frames.len() - 1);
+        }
+    }
+
+    /// The frame to render: the newest when live, the cursor's when paused.
+    pub(crate) fn current(&self) -> Option<&Frame> {
+        let newest = self.frames.len().checked_sub(1)?;
+        let offset = if self.paused { self.cursor } else { 0 };
+        self.frames.get(newest - offset.min(newest))
+    }
+
+    /// Freeze on the newest frame, or resume live rendering.
+    pub(crate) fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.cursor = 0;
+    }
+
+    /// Step one sample back in time (pausing if live).
+    pub(crate) fn step_back(&mut self) {
+        self.paused = true;
+        self.cursor = (self.cursor + 1).min(self.frames.len().saturating_sub(1));
+    }
+
+    /// Step one sample forward in time, stopping at the newest frame. Stays
+    /// paused; resuming is an explicit toggle.
+    pub(crate) fn step_forward(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Title-bar indicator, e.g. `"PAUSED @ t-42s"`, or `None` when live. The
+    /// offset is the time between the selected and the newest received sample.
+    pub(crate) fn status(&self) -> Option<String> {
+        if !self.paused {
+            return None;
+        }
+        let newest = self.frames.back()?;
+        let current = self.current()?;
+        let ago = newest.elapsed.saturating_sub(current.elapsed);
+        Some(format!("PAUSED @ t-{}s", ago.as_secs_f64().round()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::fixtures::frame_at;
+
+    fn current_secs(sb: &Scrollback) -> u64 {
+        sb.current().expect("a frame").elapsed.as_secs()
+    }
+
+    #[test]
+    fn live_tracks_newest_and_evicts_oldest() {
+        let mut sb = Scrollback::new(3);
+        assert!(sb.current().is_none());
+        for s in 0..5 {
+            sb.push(frame_at(s));
+        }
+        assert_eq!(current_secs(&sb), 4);
+        assert_eq!(sb.status(), None);
+    }
+
+    #[test]
+    fn paused_view_stays_pinned_while_frames_arrive() {
+        let mut sb = Scrollback::new(10);
+        sb.push(frame_at(0));
+        sb.push(frame_at(1));
+        sb.toggle_pause();
+        sb.push(frame_at(2));
+        sb.push(frame_at(3));
+        assert_eq!(current_secs(&sb), 1);
+        assert_eq!(sb.status().as_deref(), Some("PAUSED @ t-2s"));
+
+        // Resuming jumps back to live.
+        sb.toggle_pause();
+        assert_eq!(sb.status(), None);
+        assert_eq!(current_secs(&sb), 3);
+    }
+
+    #[test]
+    fn cursor_steps_are_clamped_to_the_retained_window() {
+        let mut sb = Scrollback::new(3);
+        for s in 0..3 {
+            sb.push(frame_at(s));
+        }
+        // Stepping back pauses implicitly.
+        sb.step_back();
+        assert!(sb.status().is_some());
+        assert_eq!(current_secs(&sb), 1);
+        sb.step_back();
+        sb.step_back();
+        assert_eq!(current_secs(&sb), 0, "clamped at the oldest frame");
+
+        // Eviction while paused on the oldest frame keeps the cursor valid.
+        sb.push(frame_at(3));
+        assert_eq!(current_secs(&sb), 1);
+
+        sb.step_forward();
+        sb.step_forward();
+        sb.step_forward();
+        assert_eq!(current_secs(&sb), 3, "clamped at the newest frame");
+        assert!(sb.status().is_some(), "stepping forward never resumes");
+        assert_eq!(sb.status().as_deref(), Some("PAUSED @ t-0s"));
+    }
+}
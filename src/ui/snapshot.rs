@@ -57,7 +57,10 @@ mod tests {
             max: 105,
             height: 3,
             fg: theme.history_fg,
+            colors: Vec::new(),
+            bold: Vec::new(),
             bg: theme.history_bg,
+            glyphs: theme.glyphs,
         };
         let el = element! { Sparkline(sparkline: Some(spark)) }.into_any();
         assert_snapshot("sparkline_h3", &render_to_text(el, 24));
@@ -71,7 +74,10 @@ mod tests {
             max: 105,
             height: 9,
             fg: theme.history_fg,
+            colors: Vec::new(),
+            bold: Vec::new(),
             bg: theme.history_bg,
+            glyphs: theme.glyphs,
         };
         let el = element! { Sparkline(sparkline: Some(spark)) }.into_any();
         assert_snapshot("sparkline_h9", &render_to_text(el, 24));
@@ -85,7 +91,10 @@ mod tests {
             max: 105,
             height: 1,
             fg: theme.history_fg,
+            colors: Vec::new(),
+            bold: Vec::new(),
             bg: theme.history_bg,
+            glyphs: theme.glyphs,
         };
         let el = element! { Sparkline(sparkline: Some(spark)) }.into_any();
         assert_snapshot("sparkline_h1", &render_to_text(el, 16));
@@ -102,6 +111,8 @@ mod tests {
             height: 2,
             fg: theme.gauge_fg,
             bg: theme.gauge_bg,
+            bold: false,
+            glyphs: theme.glyphs,
         };
         let el = element! { Gauge(gauge: Some(g)) }.into_any();
         assert_snapshot("gauge_41pct", &render_to_text(el, 30));
@@ -120,6 +131,8 @@ mod tests {
                 label: Some("43.0%".to_string()),
                 fg: theme.gauge_fg,
                 bg: theme.gauge_bg,
+                bold: false,
+                glyphs: theme.glyphs,
             }))
         }
         .into_any();
@@ -131,6 +144,8 @@ mod tests {
                 label: None,
                 fg: theme.gauge_fg,
                 bg: theme.gauge_bg,
+                bold: false,
+                glyphs: theme.glyphs,
             }))
         }
         .into_any();
@@ -158,6 +173,8 @@ mod tests {
                 height: 2,
                 fg: theme.gauge_fg,
                 bg: theme.gauge_bg,
+                bold: false,
+                glyphs: theme.glyphs,
             }))
         }
         .into_any();
@@ -167,7 +184,10 @@ mod tests {
                 max: 105,
                 height: 3,
                 fg: theme.history_fg,
+                colors: Vec::new(),
+                bold: Vec::new(),
                 bg: theme.history_bg,
+                glyphs: theme.glyphs,
             }))
         }
         .into_any();
@@ -180,7 +200,7 @@ mod tests {
         let p = panel(
             " P1-Cluster: 6.6 % @ 1437 MHz (peak: 9.8 %) ",
             62,
-            Color::Reset,
+            theme,
             body,
         );
         assert_snapshot("panel_cluster_cell", &render_to_text(p, 62));
@@ -200,6 +220,8 @@ mod tests {
                 height: 2,
                 fg: theme.gauge_fg,
                 bg: theme.gauge_bg,
+                bold: false,
+                glyphs: theme.glyphs,
             }))
         }
         .into_any();
@@ -220,7 +242,10 @@ mod tests {
                 max: 105,
                 height: 1,
                 fg: theme.history_fg,
+                colors: vec![theme.history_fg, theme.critical],
+                bold: Vec::new(),
                 bg: theme.history_bg,
+                glyphs: theme.glyphs,
             }))
         }
         .into_any();
@@ -237,5 +262,29 @@ mod tests {
             Some(theme.history_bg),
             "sparkline background must be history_bg",
         );
+        // A per-column color (a critical sample) overrides history_fg.
+        let cell = canvas.cell(1, 0).expect("sparkline cell (1,0)");
+        assert_eq!(
+            cell.text_style().and_then(|st| st.color),
+            Some(theme.critical),
+            "a critical bar takes the critical color",
+        );
+    }
+
+    #[test]
+    fn panel_border_and_title_colors() {
+        let theme = Theme::from(&crate::config::UiColors::builtin("solarized").expect("theme"));
+        let body = element! { View(height: 1u32) }.into_any();
+        let mut p = panel(" CPU ", 12, theme, body);
+        let canvas = p.render(Some(12));
+        let color = |x: usize, y: usize| {
+            canvas
+                .cell(x, y)
+                .and_then(|c| c.text_style())
+                .and_then(|s| s.color)
+        };
+        assert_eq!(color(0, 0), Some(theme.border), "corner takes border");
+        assert_eq!(color(2, 0), Some(theme.title), "title text takes title");
+        assert_eq!(color(8, 0), Some(theme.border), "filler takes border");
     }
 }
@@ -1,12 +1,101 @@
 //! Color theme for the iocraft UI.
 //!
-//! Maps the user-configurable [`UiColors`](crate::config::UiColors) (indexed
-//! ANSI codes) onto `iocraft::Color` values. `iocraft::Color` re-exports
-//! `crossterm::style::Color`, whose indexed variant is `Color::AnsiValue(u8)`.
+//! Maps the user-configurable [`UiColors`](crate::config::UiColors) (palette
+//! indexes, `#rrggbb` values or the terminal default) onto `iocraft::Color`
+//! values. `iocraft::Color` re-exports `crossterm::style::Color`: an index
+//! becomes `Color::AnsiValue(u8)`, a truecolor value `Color::Rgb`, and the
+//! terminal default `Color::Reset`.
+//!
+//! The theme also carries the [`Glyphs`] the components draw with (Unicode
+//! blocks and box-drawing, or plain ASCII for `--ascii`) and, for
+//! `--monochrome` / `NO_COLOR`, a flag that drops every color and marks
+//! warning and critical state in bold instead.
+
+use iocraft::prelude::{BorderStyle, Color, Weight};
+
+use crate::{
+    backend::frame::ColorRole,
+    config::{UiColor, UiColors},
+    metric_key::PowerDomain,
+};
+
+/// The characters the components draw with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Glyphs {
+    /// Sparkline and stacked-chart levels: index 0 = empty, 1..=8 = increasing
+    /// heights.
+    pub bars: [char; 9],
+    /// Heatmap levels: index 0 = empty, 1..=4 = increasing quarters.
+    pub shades: [char; 5],
+    /// Filled gauge cell (also the Power tab legend swatch).
+    pub block: char,
+    /// Filled line-gauge cell.
+    pub line: char,
+    /// Unfilled line-gauge cell: the same line in color, a thinner one in
+    /// monochrome where only the glyph tells the two apart.
+    pub line_empty: char,
+    /// Thin line, the unfilled line-gauge cell in monochrome.
+    pub line_thin: char,
+    /// Panel, tab bar and tooltip borders.
+    pub border: BorderStyle,
+    /// Tab bar divider.
+    pub divider: char,
+    /// Scroll thumb.
+    pub thumb: char,
+    /// Chart Y axis line.
+    pub axis: char,
+    /// Chart Y axis tick next to a label.
+    pub axis_tick: char,
+    /// Stacked-chart reference line.
+    pub dash: char,
+    /// Whether line charts plot braille dots; otherwise each cell shows `'`,
+    /// `.` or `:` for dots in its upper half, lower half or both.
+    pub braille: bool,
+}
+
+impl Glyphs {
+    /// Block elements, box-drawing and braille.
+    pub(crate) const UNICODE: Self = Self {
+        bars: [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+        shades: [' ', '░', '▒', '▓', '█'],
+        block: '█',
+        line: '━',
+        line_empty: '━',
+        line_thin: '─',
+        border: BorderStyle::Single,
+        divider: '│',
+        thumb: '┃',
+        axis: '│',
+        axis_tick: '┤',
+        dash: '╌',
+        braille: true,
+    };
 
-use iocraft::prelude::Color;
+    /// Plain ASCII (`--ascii`), for terminals and log viewers that mangle
+    /// anything else.
+    pub(crate) const ASCII: Self = Self {
+        bars: [' ', '.', ',', ':', '-', '=', '+', '*', '#'],
+        shades: [' ', '.', ':', '+', '#'],
+        block: '#',
+        line: '=',
+        line_empty: '=',
+        line_thin: '-',
+        border: BorderStyle::Classic,
+        divider: '|',
+        thumb: '#',
+        axis: '|',
+        axis_tick: '+',
+        dash: '-',
+        braille: false,
+    };
 
-use crate::config::UiColors;
+    /// The panel title line's corners and fill: top-left, top, top-right.
+    pub(crate) fn top_border(&self) -> (char, char, char) {
+        self.border
+            .border_characters()
+            .map_or((' ', ' ', ' '), |b| (b.top_left, b.top, b.top_right))
+    }
+}
 
 /// Resolved theme colors, ready to hand to components. `Copy` so it can be
 /// passed by value into props without ceremony.
@@ -17,30 +106,166 @@ pub(crate) struct Theme {
     pub gauge_bg: Color,
     pub history_fg: Color,
     pub history_bg: Color,
+    pub warning: Color,
+    pub critical: Color,
+    pub border: Color,
+    pub title: Color,
+    pub power_cpu: Color,
+    pub power_gpu: Color,
+    pub power_ane: Color,
+    pub power_other: Color,
+    /// Glyph set.
+    pub glyphs: Glyphs,
+    /// No colors: state is marked in bold instead.
+    pub mono: bool,
+}
+
+/// The terminal color for a configured color.
+fn color(c: UiColor) -> Color {
+    match c {
+        UiColor::Default => Color::Reset,
+        UiColor::Indexed(i) => Color::AnsiValue(i),
+        UiColor::Rgb(r, g, b) => Color::Rgb { r, g, b },
+    }
 }
 
 impl From<&UiColors> for Theme {
     fn from(c: &UiColors) -> Self {
         Self {
-            accent: Color::AnsiValue(c.accent),
-            gauge_fg: Color::AnsiValue(c.gauge_fg),
-            gauge_bg: Color::AnsiValue(c.gauge_bg),
-            history_fg: Color::AnsiValue(c.history_fg),
-            history_bg: Color::AnsiValue(c.history_bg),
+            accent: color(c.accent),
+            gauge_fg: color(c.gauge_fg),
+            gauge_bg: color(c.gauge_bg),
+            history_fg: color(c.history_fg),
+            history_bg: color(c.history_bg),
+            warning: color(c.warning),
+            critical: color(c.critical),
+            border: color(c.border),
+            title: color(c.title),
+            power_cpu: color(c.power_cpu),
+            power_gpu: color(c.power_gpu),
+            power_ane: color(c.power_ane),
+            power_other: color(c.power_other),
+            glyphs: Glyphs::UNICODE,
+            mono: false,
         }
     }
 }
 
 impl Default for Theme {
-    /// The documented CLI defaults: accent/gauge_fg green (2), gauge_bg/
-    /// history_bg white (7), history_fg blue (4).
+    /// The `dark` theme: accent/gauge_fg green (2), gauge_bg/history_bg white
+    /// (7), history_fg blue (4), warning yellow (3), critical red (1), borders
+    /// and titles in the terminal's default color; power layers blue, green,
+    /// magenta and grey (4, 2, 5, 8).
     fn default() -> Self {
+        Self::from(&UiColors::default())
+    }
+}
+
+impl Theme {
+    /// The same theme drawn with [`Glyphs::ASCII`].
+    pub(crate) fn ascii(self) -> Self {
+        Self {
+            glyphs: Glyphs {
+                line_empty: if self.mono {
+                    Glyphs::ASCII.line_thin
+                } else {
+                    Glyphs::ASCII.line_empty
+                },
+                ..Glyphs::ASCII
+            },
+            ..self
+        }
+    }
+
+    /// The same glyphs without any color: every color is the terminal
+    /// default, and unfilled line-gauge cells switch to the thin line.
+    pub(crate) fn monochrome(self) -> Self {
+        let none = Color::Reset;
         Self {
-            accent: Color::AnsiValue(2),
-            gauge_fg: Color::AnsiValue(2),
-            gauge_bg: Color::AnsiValue(7),
-            history_fg: Color::AnsiValue(4),
-            history_bg: Color::AnsiValue(7),
+            accent: none,
+            gauge_fg: none,
+            gauge_bg: none,
+            history_fg: none,
+            history_bg: none,
+            warning: none,
+            critical: none,
+            border: none,
+            title: none,
+            power_cpu: none,
+            power_gpu: none,
+            power_ane: none,
+            power_other: none,
+            glyphs: Glyphs {
+                line_empty: self.glyphs.line_thin,
+                ..self.glyphs
+            },
+            mono: true,
+        }
+    }
+
+    /// Whether a cell or label in `role` is drawn bold: warning and critical
+    /// state in monochrome, where there is no color to carry it.
+    pub(crate) fn bold(&self, role: ColorRole) -> bool {
+        self.mono && matches!(role, ColorRole::Warning | ColorRole::Critical)
+    }
+
+    /// [`bold`](Self::bold) as a text weight.
+    pub(crate) fn weight(&self, role: ColorRole) -> Weight {
+        if self.bold(role) {
+            Weight::Bold
+        } else {
+            Weight::Normal
+        }
+    }
+
+    /// Map a backend [`ColorRole`] to its concrete theme color.
+    pub(crate) fn role(&self, role: ColorRole) -> Color {
+        match role {
+            ColorRole::Accent => self.accent,
+            ColorRole::GaugeFg => self.gauge_fg,
+            ColorRole::HistoryFg => self.history_fg,
+            ColorRole::Warning => self.warning,
+            ColorRole::Critical => self.critical,
+            ColorRole::Power(PowerDomain::Cpu) => self.power_cpu,
+            ColorRole::Power(PowerDomain::Gpu) => self.power_gpu,
+            ColorRole::Power(PowerDomain::Ane) => self.power_ane,
+            ColorRole::Power(PowerDomain::Other) => self.power_other,
+            ColorRole::Default => Color::Reset,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truecolor_and_default_map_to_terminal_colors() {
+        let theme = Theme::from(&UiColors::builtin("solarized").expect("theme"));
+        assert_eq!(
+            theme.accent,
+            Color::Rgb {
+                r: 0x26,
+                g: 0x8b,
+                b: 0xd2
+            }
+        );
+        let theme = Theme::default();
+        assert_eq!(theme.accent, Color::AnsiValue(2));
+        assert_eq!(theme.border, Color::Reset);
+        assert_eq!(theme.role(ColorRole::Warning), Color::AnsiValue(3));
+    }
+
+    #[test]
+    fn monochrome_drops_colors_and_bolds_state() {
+        let theme = Theme::default().monochrome().ascii();
+        assert_eq!(theme.role(ColorRole::Critical), Color::Reset);
+        assert_eq!(theme.accent, Color::Reset);
+        assert!(theme.bold(ColorRole::Warning));
+        assert!(!theme.bold(ColorRole::HistoryFg));
+        assert!(!Theme::default().bold(ColorRole::Critical));
+        assert_eq!(theme.glyphs.line_empty, '-');
+        assert_eq!(theme.glyphs.top_border(), ('+', '-', '+'));
+        assert_eq!(Theme::default().glyphs.top_border(), ('┌', '─', '┐'));
+    }
+}
@@ -12,32 +12,39 @@
 //!
 //! The line-gauge bar width is **variable**: the original fills `area - label - 1`,
 //! so the bar shrinks as the label grows.
+//!
+//! In the heatmap views a single panel replaces the cluster panels: a row per
+//! core (grouped by cluster) and a column per sample over the whole history,
+//! shaded by activity or frequency, then a legend row.
 
 use iocraft::prelude::*;
 
 use crate::{
     backend::frame::{CpuCluster, CpuFrame, CpuRow},
+    config::CpuView,
     ui::{
         components::{
+            heatmap::{HeatRow, Heatmap, RenderedHeatmap, heat_width},
             line_gauge::{LineGauge, RenderedLineGauge},
             panel::panel,
         },
-        layout::CpuRowLayout,
+        layout::{CpuRowLayout, HEAT_LABEL_WIDTH, zoomed, zoomed_roles},
         theme::Theme,
-        views::{freq_table_panel, spark_slot, text_col},
+        views::{Clock, freq_table_panel, spark_slot, text_col, time_ticks},
     },
 };
 
-/// Build one CPU core row.
-fn cpu_row(row: &CpuRow, lay: &CpuRowLayout, theme: Theme) -> AnyElement<'static> {
+/// Build one CPU core row, its sparklines at `per_col` samples per cell.
+fn cpu_row(row: &CpuRow, lay: &CpuRowLayout, per_col: usize, theme: Theme) -> AnyElement<'static> {
     let id = text_col(row.id_label.clone(), lay.id_w, theme.accent);
 
     // Activity: sparkline slot + line gauge (label "{:.1}%").
     let act_label_len = row.act_label.chars().count();
     let act_histo = spark_slot(
-        row.act_spark.clone(),
+        &row.act_spark,
+        &row.act_spark_roles,
         row.act_spark_max,
-        lay.act_spark_slot,
+        (lay.act_spark_slot, per_col),
         theme,
     );
     let act_gauge = element! {
@@ -45,8 +52,10 @@ fn cpu_row(row: &CpuRow, lay: &CpuRowLayout, theme: Theme) -> AnyElement<'static
             ratio: row.act_ratio,
             width: lay.act_bar(act_label_len),
             label: Some(row.act_label.clone()),
-            fg: theme.gauge_fg,
+            fg: theme.role(row.act_role),
             bg: theme.gauge_bg,
+            bold: theme.bold(row.act_role),
+            glyphs: theme.glyphs,
         }))
     }
     .into_any();
@@ -59,9 +68,10 @@ fn cpu_row(row: &CpuRow, lay: &CpuRowLayout, theme: Theme) -> AnyElement<'static
     let freq_label_len = row.freq_label.chars().count();
     let freq_lbl = text_col("freq:".to_string(), lay.freq_label_w, Color::Reset);
     let freq_histo = spark_slot(
-        row.freq_spark.clone(),
+        &row.freq_spark,
+        &[],
         row.freq_spark_max,
-        lay.freq_spark_slot,
+        (lay.freq_spark_slot, per_col),
         theme,
     );
     let freq_val = text_col(row.freq_value.clone(), lay.freq_value_w, Color::Reset);
@@ -72,6 +82,8 @@ fn cpu_row(row: &CpuRow, lay: &CpuRowLayout, theme: Theme) -> AnyElement<'static
             label: Some(row.freq_label.clone()),
             fg: theme.gauge_fg,
             bg: theme.gauge_bg,
+            bold: false,
+            glyphs: theme.glyphs,
         }))
     }
     .into_any();
@@ -89,24 +101,122 @@ fn cpu_row(row: &CpuRow, lay: &CpuRowLayout, theme: Theme) -> AnyElement<'static
 }
 
 /// Build one bordered cluster panel (`title` is `" {name}: "`).
-fn cluster_panel(c: &CpuCluster, width: usize, theme: Theme) -> AnyElement<'static> {
+fn cluster_panel(
+    c: &CpuCluster,
+    width: usize,
+    per_col: usize,
+    theme: Theme,
+) -> AnyElement<'static> {
     let lay = CpuRowLayout::new(width);
-    let rows: Vec<AnyElement<'static>> = c.cpus.iter().map(|r| cpu_row(r, &lay, theme)).collect();
+    let rows: Vec<AnyElement<'static>> = c
+        .cpus
+        .iter()
+        .map(|r| cpu_row(r, &lay, per_col, theme))
+        .collect();
     let body = element! {
         View(flex_direction: FlexDirection::Column) { #(rows) }
     }
     .into_any();
-    panel(&c.title, width, Color::Reset, body)
+    panel(&c.title, width, theme, body)
 }
 
-/// Render the full CPU tab at `width`.
-pub(crate) fn cpu(f: &CpuFrame, width: usize, theme: Theme) -> AnyElement<'static> {
-    let mut blocks: Vec<AnyElement<'static>> = f
-        .clusters
-        .iter()
-        .map(|c| cluster_panel(c, width, theme))
-        .collect();
-    blocks.push(freq_table_panel(&f.freq_table, width));
+/// One heatmap row: a core's activity (threshold-colored) or frequency
+/// (accent) history, fitted to `plot_w` columns.
+fn heat_row(row: &CpuRow, view: CpuView, plot_w: usize, per_col: usize, theme: Theme) -> HeatRow {
+    let widen = |history: &[u8]| history.iter().copied().map(u64::from).collect::<Vec<_>>();
+    let (data, roles) = match view {
+        CpuView::Frequency => {
+            let history = widen(&row.freq_history);
+            (zoomed(&history, plot_w, per_col), Vec::new())
+        }
+        _ => {
+            let history = widen(&row.act_history);
+            let roles = zoomed_roles(&history, &row.act_history_roles, plot_w, per_col);
+            (zoomed(&history, plot_w, per_col), roles)
+        }
+    };
+    HeatRow {
+        label: row.heat_label.clone(),
+        data,
+        colors: roles.iter().map(|&role| theme.role(role)).collect(),
+        bold: roles.iter().map(|&role| theme.bold(role)).collect(),
+    }
+}
+
+/// Build the heatmap panel: every core of every cluster, then the time ticks
+/// and a legend row.
+fn heatmap_panel(
+    f: &CpuFrame,
+    view: CpuView,
+    clock: Clock,
+    width: usize,
+    per_col: usize,
+    theme: Theme,
+) -> AnyElement<'static> {
+    let inner = width.saturating_sub(2);
+    let plot_w = heat_width(inner, HEAT_LABEL_WIDTH);
+    let cores = f.clusters.iter().flat_map(|c| &c.cpus);
+    let len = cores
+        .clone()
+        .map(|r| r.act_history.len())
+        .max()
+        .unwrap_or(0);
+    let (title, fg, legend) = match view {
+        CpuView::Frequency => (" Frequency ", theme.accent, "of max frequency"),
+        _ => (" Activity ", theme.history_fg, "active"),
+    };
+    let heatmap = element! {
+        Heatmap(heatmap: Some(RenderedHeatmap {
+            rows: cores.map(|r| heat_row(r, view, plot_w, per_col, theme)).collect(),
+            max: 100,
+            width: inner,
+            label_w: HEAT_LABEL_WIDTH,
+            x_ticks: time_ticks(len, plot_w, per_col, clock),
+            fg,
+            label_color: theme.accent,
+            axis: theme.border,
+            glyphs: theme.glyphs,
+        }))
+    }
+    .into_any();
+    let legend = element! {
+        MixedText(
+            wrap: TextWrap::NoWrap,
+            contents: vec![
+                MixedTextContent::new(" ".repeat(HEAT_LABEL_WIDTH)),
+                MixedTextContent::new(theme.glyphs.shades[1..].iter().collect::<String>())
+                    .color(fg),
+                MixedTextContent::new(format!(" 25 / 50 / 75 / 100 % {legend}")),
+            ],
+        )
+    }
+    .into_any();
+    let body = element! {
+        View(flex_direction: FlexDirection::Column) { #(vec![heatmap, legend]) }
+    }
+    .into_any();
+    panel(title, width, theme, body)
+}
+
+/// Render the full CPU tab at `width` in the given view, drawing `per_col`
+/// history samples per sparkline cell or heatmap column.
+pub(crate) fn cpu(
+    f: &CpuFrame,
+    view: CpuView,
+    clock: Clock,
+    width: usize,
+    per_col: usize,
+    theme: Theme,
+) -> AnyElement<'static> {
+    let mut blocks: Vec<AnyElement<'static>> = match view {
+        CpuView::Rows => f
+            .clusters
+            .iter()
+            .map(|c| cluster_panel(c, width, per_col, theme))
+            .collect(),
+        _ => vec![heatmap_panel(f, view, clock, width, per_col, theme)],
+    };
+    blocks.push(freq_table_panel(&f.freq_table, width, theme));
 
     element! {
         View(flex_direction: FlexDirection::Column) { #(blocks) }
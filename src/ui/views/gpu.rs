@@ -2,30 +2,34 @@
 //!
 //! A single bordered `GPU:` block (height 4) with two inner rows — top =
 //! activity | frequency, bottom = power | peak — then a `Thermals` block and the
-//! shared `Frequencies` table. Widths come from [`GpuLayout`]; strings arrive
-//! pre-formatted in the [`GpuFrame`].
+//! shared `Frequencies` table. In the line-chart style a ` History ` panel
+//! with a braille chart of activity and frequency follows the GPU block.
+//! Widths come from [`GpuLayout`]; strings arrive pre-formatted in the
+//! [`GpuFrame`].
 
 use iocraft::prelude::*;
 
 use crate::{
-    backend::frame::{GpuFrame, Thermals},
+    backend::frame::{GpuFrame, SparkUnit, Thermals},
+    config::ChartStyle,
     ui::{
         components::{
             line_gauge::{LineGauge, RenderedLineGauge},
             panel::panel,
         },
-        layout::GpuLayout,
+        layout::{GPU_CHART_HEIGHT, GpuLayout},
         theme::Theme,
-        views::{freq_table_panel, spark_slot, text_col},
+        views::{ChartData, ChartLine, Clock, freq_table_panel, line_chart, spark_slot, text_col},
     },
 };
 
 /// Build the top row: activity (sparkline + line gauge) | frequency.
-fn top_row(f: &GpuFrame, lay: &GpuLayout, theme: Theme) -> AnyElement<'static> {
+fn top_row(f: &GpuFrame, lay: &GpuLayout, per_col: usize, theme: Theme) -> AnyElement<'static> {
     let act_histo = spark_slot(
-        f.act_spark.clone(),
+        &f.act_spark,
+        &f.act_spark_roles,
         f.act_spark_max,
-        lay.act_spark_slot,
+        (lay.act_spark_slot, per_col),
         theme,
     );
     let act_gauge = element! {
@@ -33,8 +37,10 @@ fn top_row(f: &GpuFrame, lay: &GpuLayout, theme: Theme) -> AnyElement<'static> {
             ratio: f.act_ratio,
             width: lay.act_bar(f.act_label.chars().count()),
             label: Some(f.act_label.clone()),
-            fg: theme.gauge_fg,
+            fg: theme.role(f.act_role),
             bg: theme.gauge_bg,
+            bold: theme.bold(f.act_role),
+            glyphs: theme.glyphs,
         }))
     }
     .into_any();
@@ -45,9 +51,10 @@ fn top_row(f: &GpuFrame, lay: &GpuLayout, theme: Theme) -> AnyElement<'static> {
 
     let freq_lbl = text_col("freq:".to_string(), lay.freq_label_w, Color::Reset);
     let freq_histo = spark_slot(
-        f.freq_spark.clone(),
+        &f.freq_spark,
+        &[],
         f.freq_spark_max,
-        lay.freq_spark_slot,
+        (lay.freq_spark_slot, per_col),
         theme,
     );
     let freq_val = text_col(f.freq_value.clone(), lay.freq_value_w, Color::Reset);
@@ -58,6 +65,8 @@ fn top_row(f: &GpuFrame, lay: &GpuLayout, theme: Theme) -> AnyElement<'static> {
             label: Some(f.freq_label.clone()),
             fg: theme.gauge_fg,
             bg: theme.gauge_bg,
+            bold: false,
+            glyphs: theme.glyphs,
         }))
     }
     .into_any();
@@ -75,11 +84,12 @@ fn top_row(f: &GpuFrame, lay: &GpuLayout, theme: Theme) -> AnyElement<'static> {
 }
 
 /// Build the bottom row: power (sparkline + value) | peak text.
-fn bottom_row(f: &GpuFrame, lay: &GpuLayout, theme: Theme) -> AnyElement<'static> {
+fn bottom_row(f: &GpuFrame, lay: &GpuLayout, per_col: usize, theme: Theme) -> AnyElement<'static> {
     let pow_histo = spark_slot(
-        f.power_spark.clone(),
+        &f.power_spark,
+        &[],
         f.power_spark_max,
-        lay.power_spark_slot,
+        (lay.power_spark_slot, per_col),
         theme,
     );
     let pow_val = text_col(f.power_value.clone(), lay.power_value_w, Color::Reset);
@@ -94,47 +104,100 @@ fn bottom_row(f: &GpuFrame, lay: &GpuLayout, theme: Theme) -> AnyElement<'static
     .into_any()
 }
 
-/// Build the `Thermals` block body (`Pressure: {x}`, accent when nominal else
-/// Yellow).
+/// Build the `Thermals` block body (`Pressure: {x}`, colored by its role:
+/// accent when nominal, else warning or critical, which are bold in
+/// monochrome).
 fn thermals_panel(t: &Thermals, width: usize, theme: Theme) -> AnyElement<'static> {
-    let p_color = if t.is_nominal {
-        theme.accent
-    } else {
-        // ANSI index 3 (the standard-index yellow); the crossterm-named
-        // `Color::Yellow` is the bright index 11, which Solarized renders grey.
-        Color::AnsiValue(3)
-    };
+    let p_color = theme.role(t.role);
     let body = element! {
         MixedText(
             wrap: TextWrap::NoWrap,
             contents: vec![
                 MixedTextContent::new("Pressure: "),
-                MixedTextContent::new(t.pressure.clone()).color(p_color),
+                MixedTextContent::new(t.pressure.clone())
+                    .color(p_color)
+                    .weight(theme.weight(t.role)),
             ],
         )
     }
     .into_any();
-    panel(" Thermals ", width, Color::Reset, body)
+    panel(" Thermals ", width, theme, body)
+}
+
+/// Build the ` History ` panel: a legend row, then activity and frequency
+/// (both in percent) as lines of one braille chart.
+fn history_panel(
+    f: &GpuFrame,
+    width: usize,
+    (clock, per_col): (Clock, usize),
+    theme: Theme,
+) -> AnyElement<'static> {
+    let line = theme.glyphs.line;
+    let legend = element! {
+        MixedText(
+            wrap: TextWrap::NoWrap,
+            contents: vec![
+                MixedTextContent::new(format!("{line} Active")).color(theme.history_fg),
+                MixedTextContent::new("  "),
+                MixedTextContent::new(format!("{line} Frequency")).color(theme.accent),
+            ],
+        )
+    }
+    .into_any();
+    let data = ChartData {
+        lines: vec![
+            ChartLine {
+                data: &f.freq_history,
+                roles: &[],
+                color: theme.accent,
+            },
+            ChartLine {
+                data: &f.act_history,
+                roles: &f.act_history_roles,
+                color: theme.history_fg,
+            },
+        ],
+        max: 100,
+        unit: SparkUnit::Percent,
+    };
+    let size = (width.saturating_sub(2), GPU_CHART_HEIGHT);
+    let chart = line_chart(&data, size, per_col, clock, theme);
+    let body = element! {
+        View(flex_direction: FlexDirection::Column) { #(vec![legend, chart]) }
+    }
+    .into_any();
+    panel(" History ", width, theme, body)
 }
 
-/// Render the full GPU tab at `width`.
-pub(crate) fn gpu(f: &GpuFrame, width: usize, theme: Theme) -> AnyElement<'static> {
+/// Render the full GPU tab at `width`, with the history chart in the
+/// line-chart style, drawing `per_col` history samples per sparkline cell or
+/// chart dot column.
+pub(crate) fn gpu(
+    f: &GpuFrame,
+    clock: Clock,
+    width: usize,
+    (per_col, chart): (usize, ChartStyle),
+    theme: Theme,
+) -> AnyElement<'static> {
     let lay = GpuLayout::new(width);
     let gpu_body = element! {
         View(flex_direction: FlexDirection::Column) {
-            #(vec![top_row(f, &lay, theme), bottom_row(f, &lay, theme)])
+            #(vec![top_row(f, &lay, per_col, theme), bottom_row(f, &lay, per_col, theme)])
         }
     }
     .into_any();
-    let gpu_block = panel("GPU: ", width, Color::Reset, gpu_body);
+    let gpu_block = panel("GPU: ", width, theme, gpu_body);
+    let history =
+        (chart == ChartStyle::Braille).then(|| history_panel(f, width, (clock, per_col), theme));
 
     element! {
         View(flex_direction: FlexDirection::Column) {
-            #(vec![
-                gpu_block,
-                thermals_panel(&f.thermals, width, theme),
-                freq_table_panel(&f.freq_table, width),
-            ])
+            #(std::iter::once(gpu_block)
+                .chain(history)
+                .chain([
+                    thermals_panel(&f.thermals, width, theme),
+                    freq_table_panel(&f.freq_table, width, theme),
+                ]))
         }
     }
     .into_any()
@@ -0,0 +1,64 @@
+//! Help overlay.
+//!
+//! A bordered ` Help ` panel listing every bound action as a 2-column table
+//! (keys in the accent color, description), horizontally centered in `width`.
+//! The rows come from [`Keymap::help_rows`](crate::ui::keymap::Keymap::help_rows),
+//! so remapped keys show up as configured. The panel has an opaque background: `PumasApp`
+//! draws it absolutely positioned over the active tab.
+
+use iocraft::prelude::*;
+
+use crate::ui::{components::panel::panel, theme::Theme, views::text_col};
+
+/// Render the help overlay, centered in `width`.
+pub(crate) fn help(rows: &[(String, String)], width: usize, theme: Theme) -> AnyElement<'static> {
+    let keys_w = rows
+        .iter()
+        .map(|(k, _)| k.chars().count())
+        .max()
+        .unwrap_or(0);
+    let desc_w = rows
+        .iter()
+        .map(|(_, d)| d.chars().count())
+        .max()
+        .unwrap_or(0);
+    // `│ keys␣␣desc │`: two borders, two 1-column margins, a 2-column gap.
+    let panel_w = (keys_w + desc_w + 6).min(width);
+
+    let body: Vec<AnyElement<'static>> = rows
+        .iter()
+        .map(|(k, d)| {
+            let keys = text_col(k.clone(), keys_w + 2, theme.accent);
+            element! {
+                View(flex_direction: FlexDirection::Row) {
+                    #(vec![keys])
+                    Text(content: d.clone(), wrap: TextWrap::NoWrap)
+                }
+            }
+            .into_any()
+        })
+        .collect();
+    let body = element! {
+        View(flex_direction: FlexDirection::Column, padding_left: 1u32) {
+            #(body)
+        }
+    }
+    .into_any();
+    let framed = Theme {
+        border: theme.accent,
+        title: theme.accent,
+        ..theme
+    };
+    let panel = panel(" Help ", panel_w, framed, body);
+
+    #[expect(clippy::cast_possible_truncation)]
+    let w = width as u32;
+    element! {
+        View(width: w, justify_content: JustifyContent::Center) {
+            View(background_color: Color::Reset) {
+                #(vec![panel])
+            }
+        }
+    }
+    .into_any()
+}
@@ -11,26 +11,14 @@
 use iocraft::prelude::*;
 
 use crate::{
-    backend::frame::{ColorRole, MemLine, MemoryFrame},
-    ui::{components::panel::panel, theme::Theme},
+    backend::frame::{MemLine, MemoryFrame},
+    ui::{
+        components::panel::panel,
+        layout::{SYSINFO_INNER_ROWS, VM_INNER_ROWS},
+        theme::Theme,
+    },
 };
 
-/// Inner rows of the `VM Statistics` block (`18 - 2` borders).
-const VM_INNER_ROWS: usize = 16;
-
-/// Inner rows of the `Sysinfo Statistics` block (`8 - 2` borders).
-const SYSINFO_INNER_ROWS: usize = 6;
-
-/// Map a [`ColorRole`] to its concrete theme color.
-fn role_color(role: ColorRole, theme: Theme) -> Color {
-    match role {
-        ColorRole::Accent => theme.accent,
-        ColorRole::GaugeFg => theme.gauge_fg,
-        ColorRole::HistoryFg => theme.history_fg,
-        ColorRole::Default => Color::Reset,
-    }
-}
-
 /// Render one memory line: a blank line becomes a 1-row spacer; otherwise a
 /// `MixedText` of the colored spans.
 fn mem_line(line: &MemLine, theme: Theme) -> AnyElement<'static> {
@@ -40,7 +28,7 @@ fn mem_line(line: &MemLine, theme: Theme) -> AnyElement<'static> {
     let contents: Vec<MixedTextContent> = line
         .spans
         .iter()
-        .map(|s| MixedTextContent::new(s.text.clone()).color(role_color(s.role, theme)))
+        .map(|s| MixedTextContent::new(s.text.clone()).color(theme.role(s.role)))
         .collect();
     element! { MixedText(wrap: TextWrap::NoWrap, contents: contents) }.into_any()
 }
@@ -61,7 +49,7 @@ fn mem_block(
         View(flex_direction: FlexDirection::Column) { #(rows) }
     }
     .into_any();
-    panel(title, width, Color::Reset, body)
+    panel(title, width, theme, body)
 }
 
 /// Render the full Memory tab at `width`.
@@ -94,7 +82,7 @@ pub(crate) fn memory(f: &MemoryFrame, width: usize, theme: Theme) -> AnyElement<
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::frame::MemSpan;
+    use crate::backend::frame::{ColorRole, MemSpan};
 
     /// Color parity: an `Accent`-role span renders with the theme accent
     /// foreground. Plain-text snapshots cannot see color, so we inspect the
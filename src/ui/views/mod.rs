@@ -2,59 +2,164 @@
 //!
 //! Each view is a plain function that turns an owned `Frame` sub-struct plus the
 //! frontend [`OverviewLayout`](crate::ui::layout) geometry into an
-//! `AnyElement<'static>`. One module per tab: splash, Overview, CPU, GPU, Memory
-//! and SoC.
+//! `AnyElement<'static>`. One module per tab: splash, Overview, CPU, GPU, Memory,
+//! SoC and Power, plus the help overlay and the "terminal too small" screen.
 
 pub(crate) mod cpu;
 pub(crate) mod gpu;
+pub(crate) mod help;
 pub(crate) mod memory;
 pub(crate) mod overview;
+pub(crate) mod power;
 pub(crate) mod soc;
 pub(crate) mod splash;
+pub(crate) mod too_small;
 
 #[cfg(test)]
 mod tests;
 
+use std::time::Duration;
+
 use iocraft::prelude::*;
 
 use crate::{
-    backend::frame::FreqTable,
+    backend::frame::{ColorRole, FreqTable, SparkUnit},
     ui::{
         components::{
+            line_chart::{ChartSeries, LineChart, RenderedLineChart, plot_width},
             panel::panel,
             sparkline::{RenderedSparkline, Sparkline},
         },
+        hover::age_label,
+        layout::{CHART_TICK_SPACING, FREQ_TABLE_INNER_ROWS, slot_cells, zoomed, zoomed_roles},
         theme::Theme,
     },
 };
 
+/// Sample times for chart time axes: the frame's `timeline` (aligned with the
+/// end of every full-history series) and the newest sample's time.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Clock<'a> {
+    pub timeline: &'a [Duration],
+    pub elapsed: Duration,
+}
+
+/// One line of a chart: full-history samples, their color roles (may be
+/// empty) and the line color.
+pub(crate) struct ChartLine<'a> {
+    pub data: &'a [u64],
+    pub roles: &'a [ColorRole],
+    pub color: Color,
+}
+
+/// The history behind one chart: its lines, the top of the scale, and the
+/// sample unit (for the Y axis labels).
+pub(crate) struct ChartData<'a> {
+    pub lines: Vec<ChartLine<'a>>,
+    pub max: u64,
+    pub unit: SparkUnit,
+}
+
+/// Time ticks for a chart `plot_w` cells wide over `len` samples at
+/// `per_cell` samples per cell: every [`CHART_TICK_SPACING`] cells from the
+/// right edge, labelled with the age of the sample drawn there (`now`,
+/// `t-16s`, …).
+fn time_ticks(len: usize, plot_w: usize, per_cell: usize, clock: Clock) -> Vec<(usize, String)> {
+    let per_cell = per_cell.max(1);
+    (0..plot_w)
+        .rev()
+        .step_by(CHART_TICK_SPACING)
+        .map_while(|col| {
+            // The newest sample of the cell; `back` samples before the newest.
+            let back = (plot_w - 1 - col) * per_cell;
+            if back >= len {
+                return None;
+            }
+            let at = clock.timeline.len().checked_sub(back + 1)?;
+            let label = match back {
+                0 => "now".to_string(),
+                _ => age_label(clock.elapsed.saturating_sub(clock.timeline[at])),
+            };
+            Some((col, label))
+        })
+        .collect()
+}
+
+/// A braille line chart of `chart`, `width` wide with `height` plot rows (plus
+/// the tick row), at `per_col` samples per dot column. Line segments take the
+/// color of their sample's role, else the line color.
+pub(crate) fn line_chart(
+    chart: &ChartData,
+    (width, height): (usize, usize),
+    per_col: usize,
+    clock: Clock,
+    theme: Theme,
+) -> AnyElement<'static> {
+    let y_labels = (chart.unit.format(chart.max), chart.unit.format(0));
+    let plot_w = plot_width(width, &y_labels);
+    let dots = plot_w * 2;
+    let series = chart
+        .lines
+        .iter()
+        .map(|line| ChartSeries {
+            data: zoomed(line.data, dots, per_col),
+            color: line.color,
+            colors: zoomed_roles(line.data, line.roles, dots, per_col)
+                .into_iter()
+                .map(|role| match role {
+                    ColorRole::HistoryFg => line.color,
+                    role => theme.role(role),
+                })
+                .collect(),
+        })
+        .collect();
+    let len = chart.lines.iter().map(|l| l.data.len()).max().unwrap_or(0);
+    element! {
+        LineChart(chart: Some(RenderedLineChart {
+            series,
+            max: chart.max,
+            width,
+            height,
+            x_ticks: time_ticks(len, plot_w, 2 * per_col.max(1), clock),
+            y_labels,
+            axis: theme.border,
+            glyphs: theme.glyphs,
+        }))
+    }
+    .into_any()
+}
+
 /// Frequency-table label column width (the original `label_width = 10`, shared by the
 /// CPU and GPU `Frequencies` tables).
 const FREQ_TABLE_LABEL_WIDTH: usize = 10;
 
-/// Inner rows of the `Frequencies` table block (`FREQUENCY_TABLE_HEIGHT`); the
-/// body is padded to this so the bordered block is always `2 + 5` tall.
-const FREQ_TABLE_INNER_ROWS: usize = 5;
-
 /// A single-row sparkline confined to its fixed `slot` column (8 data cells +
 /// the trailing gap), so the following gauge column-aligns regardless of data
-/// length.
+/// length. It draws the newest `data` at `per_col` samples per cell. Bars take
+/// the color (and monochrome bold mark) of their sample's role (`roles` may be
+/// empty).
 pub(crate) fn spark_slot(
-    data: Vec<u64>,
+    data: &[u64],
+    roles: &[ColorRole],
     max: u64,
-    slot: usize,
+    (slot, per_col): (usize, usize),
     theme: Theme,
 ) -> AnyElement<'static> {
     #[expect(clippy::cast_possible_truncation)]
     let w = slot as u32;
+    let cells = slot_cells(slot);
+    let roles = zoomed_roles(data, roles, cells, per_col);
     element! {
         View(width: w) {
             Sparkline(sparkline: Some(RenderedSparkline {
-                data,
+                data: zoomed(data, cells, per_col),
                 max,
                 height: 1,
                 fg: theme.history_fg,
+                colors: roles.iter().map(|&role| theme.role(role)).collect(),
+                bold: roles.iter().map(|&role| theme.bold(role)).collect(),
                 bg: theme.history_bg,
+                glyphs: theme.glyphs,
             }))
         }
     }
@@ -89,7 +194,7 @@ pub(crate) fn two_col_row(label: &str, value: &str, label_w: usize) -> AnyElemen
 
 /// Build the bordered `Frequencies` table panel (height `2 + 5`), shared by the
 /// CPU and GPU tabs.
-pub(crate) fn freq_table_panel(ft: &FreqTable, width: usize) -> AnyElement<'static> {
+pub(crate) fn freq_table_panel(ft: &FreqTable, width: usize, theme: Theme) -> AnyElement<'static> {
     let mut rows: Vec<AnyElement<'static>> = ft
         .rows
         .iter()
@@ -102,5 +207,5 @@ pub(crate) fn freq_table_panel(ft: &FreqTable, width: usize) -> AnyElement<'stat
         View(flex_direction: FlexDirection::Column) { #(rows) }
     }
     .into_any();
-    panel("Frequencies", width, Color::Reset, body)
+    panel("Frequencies", width, theme, body)
 }
@@ -8,23 +8,27 @@
 //!
 //! Only the four outer panels are bordered; the inner cells are NOT. Each
 //! cell is a plain `Text` title row, a single-row gauge bar, and a 3-row
-//! sparkline. All strings arrive pre-formatted in the [`Frame`]; all widths come
-//! from [`OverviewLayout`].
+//! sparkline — or, in the line-chart style, a braille chart with axes. All
+//! strings arrive pre-formatted in the [`Frame`]; all widths come from
+//! [`OverviewLayout`]. On a short terminal the layout shrinks the sparklines
+//! (down to none at all) so the tab fits its height.
 //!
 //! [`Frame`]: crate::backend::frame::Frame
 
 use iocraft::prelude::*;
 
 use crate::{
-    backend::frame::{Meter, OverviewFrame, SparkText, Thermals},
+    backend::frame::{ColorRole, Meter, OverviewFrame, SparkText, SparkUnit, Thermals},
+    config::ChartStyle,
     ui::{
         components::{
             gauge::{Gauge, RenderedGauge},
             panel::panel,
             sparkline::{RenderedSparkline, Sparkline},
         },
-        layout::{OverviewLayout, last_n},
+        layout::{OverviewLayout, zoomed, zoomed_roles},
         theme::Theme,
+        views::{ChartData, ChartLine, Clock, line_chart},
     },
 };
 
@@ -40,9 +44,58 @@ fn blank_row(height: u32) -> AnyElement<'static> {
     element! { View(height: height) }.into_any()
 }
 
+/// A `width`-column sparkline (or line chart) at the layout's height, or
+/// nothing in compact mode (`spark_height == 0`). Bars take the color of their
+/// sample's role (`roles` may be empty).
+fn spark(
+    (data, roles): (&[u64], &[ColorRole]),
+    (max, unit): (u64, SparkUnit),
+    width: usize,
+    lay: &OverviewLayout,
+    clock: Clock,
+    theme: Theme,
+) -> Option<AnyElement<'static>> {
+    if lay.spark_height == 0 {
+        return None;
+    }
+    if lay.chart == ChartStyle::Braille {
+        let chart = ChartData {
+            lines: vec![ChartLine {
+                data,
+                roles,
+                color: theme.history_fg,
+            }],
+            max,
+            unit,
+        };
+        let size = (width, lay.spark_height);
+        return Some(line_chart(&chart, size, lay.per_col, clock, theme));
+    }
+    let roles = zoomed_roles(data, roles, width, lay.per_col);
+    let sparkline = element! {
+        Sparkline(sparkline: Some(RenderedSparkline {
+            data: zoomed(data, width, lay.per_col),
+            max,
+            height: lay.spark_height,
+            fg: theme.history_fg,
+            colors: roles.iter().map(|&role| theme.role(role)).collect(),
+            bold: roles.iter().map(|&role| theme.bold(role)).collect(),
+            bg: theme.history_bg,
+            glyphs: theme.glyphs,
+        }))
+    };
+    Some(sparkline.into_any())
+}
+
 /// A single meter cell: title row + 1-row gauge + 3-row sparkline. The
 /// inner cells are unbordered; only the outer panel draws a border.
-fn meter_cell(m: &Meter, width: usize, spark_height: usize, theme: Theme) -> AnyElement<'static> {
+fn meter_cell(
+    m: &Meter,
+    width: usize,
+    lay: &OverviewLayout,
+    clock: Clock,
+    theme: Theme,
+) -> AnyElement<'static> {
     #[expect(clippy::cast_possible_truncation)]
     let w = width as u32;
     let gauge = element! {
@@ -50,28 +103,28 @@ fn meter_cell(m: &Meter, width: usize, spark_height: usize, theme: Theme) -> Any
             ratio: m.ratio,
             width,
             height: 1,
-            fg: theme.gauge_fg,
+            fg: theme.role(m.role),
             bg: theme.gauge_bg,
+            bold: theme.bold(m.role),
+            glyphs: theme.glyphs,
         }))
     }
     .into_any();
-    let spark = element! {
-        Sparkline(sparkline: Some(RenderedSparkline {
-            data: last_n(&m.spark, width),
-            max: m.spark_max,
-            height: spark_height,
-            fg: theme.history_fg,
-            bg: theme.history_bg,
-        }))
-    }
-    .into_any();
+    let spark = spark(
+        (&m.spark, &m.spark_roles),
+        (m.spark_max, m.unit),
+        width,
+        lay,
+        clock,
+        theme,
+    );
     // `Overflow::Hidden` clips an over-long title to the cell width, matching
     // the original text rendering, which truncates at the block boundary rather than
     // spilling into the neighbouring half.
     element! {
         View(flex_direction: FlexDirection::Column, width: w, overflow: Overflow::Hidden) {
             Text(content: m.title.clone(), wrap: TextWrap::NoWrap)
-            #(vec![gauge, spark])
+            #(std::iter::once(gauge).chain(spark))
         }
     }
     .into_any()
@@ -82,12 +135,13 @@ fn pair_row(
     left: &Meter,
     right: &Meter,
     lay: &OverviewLayout,
+    clock: Clock,
     theme: Theme,
 ) -> AnyElement<'static> {
     let cells = vec![
-        meter_cell(left, lay.half_width, lay.spark_height, theme),
+        meter_cell(left, lay.half_width, lay, clock, theme),
         h_gap(lay.gap),
-        meter_cell(right, lay.half_width, lay.spark_height, theme),
+        meter_cell(right, lay.half_width, lay, clock, theme),
     ];
     element! {
         View(flex_direction: FlexDirection::Row) {
@@ -99,7 +153,12 @@ fn pair_row(
 
 /// Build the CPU Clusters panel body: E then P then S clusters, each kind paired
 /// two-up via `chunks(2)`, with a `CLUSTER_SPACING` blank row between blocks.
-fn cluster_blocks(f: &OverviewFrame, lay: &OverviewLayout, theme: Theme) -> AnyElement<'static> {
+fn cluster_blocks(
+    f: &OverviewFrame,
+    lay: &OverviewLayout,
+    clock: Clock,
+    theme: Theme,
+) -> AnyElement<'static> {
     // Collect (left, optional right) per block, kept per-kind so an odd E count
     // never pairs with a P cluster.
     let mut specs: Vec<(&Meter, Option<&Meter>)> = Vec::new();
@@ -117,8 +176,8 @@ fn cluster_blocks(f: &OverviewFrame, lay: &OverviewLayout, theme: Theme) -> AnyE
     let mut rows: Vec<AnyElement<'static>> = Vec::new();
     for (i, (left, right)) in specs.iter().enumerate() {
         let block = match right {
-            Some(r) => pair_row(left, r, lay, theme),
-            None => meter_cell(left, lay.inner_width, lay.spark_height, theme),
+            Some(r) => pair_row(left, r, lay, clock, theme),
+            None => meter_cell(left, lay.inner_width, lay, clock, theme),
         };
         rows.push(block);
         if i != last {
@@ -135,44 +194,44 @@ fn cluster_blocks(f: &OverviewFrame, lay: &OverviewLayout, theme: Theme) -> AnyE
 }
 
 /// Build the Package panel body: title text + 3-row sparkline (no overshoot).
-fn package_body(pkg: &SparkText, lay: &OverviewLayout, theme: Theme) -> AnyElement<'static> {
-    let spark = element! {
-        Sparkline(sparkline: Some(RenderedSparkline {
-            data: last_n(&pkg.spark, lay.package_inner),
-            max: pkg.spark_max,
-            height: lay.spark_height,
-            fg: theme.history_fg,
-            bg: theme.history_bg,
-        }))
-    }
-    .into_any();
+fn package_body(
+    pkg: &SparkText,
+    lay: &OverviewLayout,
+    clock: Clock,
+    theme: Theme,
+) -> AnyElement<'static> {
+    let spark = spark(
+        (&pkg.spark, &[]),
+        (pkg.spark_max, pkg.unit),
+        lay.package_inner,
+        lay,
+        clock,
+        theme,
+    );
     element! {
         View(flex_direction: FlexDirection::Column) {
             Text(content: pkg.title.clone(), wrap: TextWrap::NoWrap)
-            #(vec![spark])
+            #(spark)
         }
     }
     .into_any()
 }
 
 /// Build the Thermals panel body: the `Pressure: {x}` line (accent when nominal,
-/// else Yellow), padded to the Package body height so the two panels align.
-fn thermals_body(t: &Thermals, theme: Theme) -> AnyElement<'static> {
-    let p_color = if t.is_nominal {
-        theme.accent
-    } else {
-        // ANSI index 3 (the standard-index yellow); the crossterm-named
-        // `Color::Yellow` is the bright index 11, which Solarized renders grey.
-        Color::AnsiValue(3)
-    };
-    let pads = vec![blank_row(1), blank_row(1), blank_row(1)];
+/// else warning or critical, bold in monochrome), padded to the Package body
+/// height so the two panels align.
+fn thermals_body(t: &Thermals, lay: &OverviewLayout, theme: Theme) -> AnyElement<'static> {
+    let p_color = theme.role(t.role);
+    let pads: Vec<AnyElement<'static>> = (0..lay.spark_rows).map(|_| blank_row(1)).collect();
     element! {
         View(flex_direction: FlexDirection::Column) {
             MixedText(
                 wrap: TextWrap::NoWrap,
                 contents: vec![
                     MixedTextContent::new("Pressure: "),
-                    MixedTextContent::new(t.pressure.clone()).color(p_color),
+                    MixedTextContent::new(t.pressure.clone())
+                        .color(p_color)
+                        .weight(theme.weight(t.role)),
                 ],
             )
             #(pads)
@@ -181,36 +240,47 @@ fn thermals_body(t: &Thermals, theme: Theme) -> AnyElement<'static> {
     .into_any()
 }
 
-/// Render the full Overview tab at `width` (and `height`, only the trailing
-/// spacer below the four panels depends on it).
-pub(crate) fn overview(f: &OverviewFrame, width: usize, theme: Theme) -> AnyElement<'static> {
-    let lay = OverviewLayout::for_frame(width, f);
+/// Render the full Overview tab at `width`, fitted to `height` rows, drawing
+/// `per_col` history samples per sparkline column in the given chart style.
+pub(crate) fn overview(
+    f: &OverviewFrame,
+    clock: Clock,
+    width: usize,
+    height: usize,
+    per_col: usize,
+    chart: ChartStyle,
+    theme: Theme,
+) -> AnyElement<'static> {
+    let lay = OverviewLayout::for_frame(width, f)
+        .with_per_col(per_col)
+        .with_chart(chart)
+        .fit_height(height);
 
     let cpu_panel = panel(
         &f.cpu_clusters_title,
         lay.width,
-        Color::Reset,
-        cluster_blocks(f, &lay, theme),
+        theme,
+        cluster_blocks(f, &lay, clock, theme),
     );
 
     let gpu_panel = panel(
         " GPU & ANE ",
         lay.width,
-        Color::Reset,
-        pair_row(&f.gpu, &f.ane, &lay, theme),
+        theme,
+        pair_row(&f.gpu, &f.ane, &lay, clock, theme),
     );
 
     let pkg_panel = panel(
         " Package ",
         lay.package_width,
-        Color::Reset,
-        package_body(&f.package, &lay, theme),
+        theme,
+        package_body(&f.package, &lay, clock, theme),
     );
     let thr_panel = panel(
         " Thermals ",
         lay.thermals_width,
-        Color::Reset,
-        thermals_body(&f.thermals, theme),
+        theme,
+        thermals_body(&f.thermals, &lay, theme),
     );
     let pkg_thr_row = element! {
         View(flex_direction: FlexDirection::Row) {
@@ -222,8 +292,8 @@ pub(crate) fn overview(f: &OverviewFrame, width: usize, theme: Theme) -> AnyElem
     let mem_panel = panel(
         " Memory & SWAP ",
         lay.width,
-        Color::Reset,
-        pair_row(&f.ram, &f.swap, &lay, theme),
+        theme,
+        pair_row(&f.ram, &f.swap, &lay, clock, theme),
     );
 
     element! {
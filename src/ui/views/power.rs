@@ -0,0 +1,151 @@
+//! Power tab.
+//!
+//! Two bordered panels stacked vertically:
+//! 1. Power — a [`StackedChart`] of CPU, GPU, ANE and "other" power over the
+//!    history window, with the SoC ceilings as reference lines;
+//! 2. Breakdown — current / average / peak / energy per domain, then the
+//!    package total. Each domain's swatch doubles as the chart legend.
+//!
+//! Strings and the chart scale arrive prepared in the [`PowerFrame`]; only
+//! column padding happens here.
+
+use iocraft::prelude::*;
+
+use crate::{
+    backend::frame::{ColorRole, PowerFrame, PowerRow},
+    ui::{
+        components::{
+            line_chart::plot_width,
+            panel::panel,
+            stacked_chart::{ReferenceLine, RenderedStackedChart, StackLayer, StackedChart},
+        },
+        layout::{POWER_CHART_HEIGHT, zoomed},
+        theme::Theme,
+        views::{Clock, time_ticks},
+    },
+};
+
+/// Table label column width (`Package` plus a gap).
+const LABEL_WIDTH: usize = 9;
+
+/// Table value column width.
+const VALUE_WIDTH: usize = 12;
+
+/// A table line: the left-aligned label, then right-aligned values.
+fn table_text(label: &str, values: [&str; 4]) -> String {
+    let values: String = values
+        .iter()
+        .map(|v| format!("{v:>VALUE_WIDTH$}"))
+        .collect();
+    format!("{label:<LABEL_WIDTH$}{values}")
+}
+
+/// One table row: a swatch in the domain color (none for the package), then
+/// the row text.
+fn table_row(row: &PowerRow, theme: Theme) -> AnyElement<'static> {
+    let swatch = match row.role {
+        ColorRole::Default => "  ".to_string(),
+        _ => format!("{} ", theme.glyphs.block),
+    };
+    let text = table_text(
+        &row.label,
+        [&row.current, &row.average, &row.peak, &row.energy],
+    );
+    element! {
+        MixedText(
+            wrap: TextWrap::NoWrap,
+            contents: vec![
+                MixedTextContent::new(swatch).color(theme.role(row.role)),
+                MixedTextContent::new(text),
+            ],
+        )
+    }
+    .into_any()
+}
+
+/// Build the stacked chart of the domain histories, `width` wide, at
+/// `per_col` samples per column.
+fn chart(
+    f: &PowerFrame,
+    width: usize,
+    per_col: usize,
+    clock: Clock,
+    theme: Theme,
+) -> AnyElement<'static> {
+    let y_labels = (f.unit.format(f.chart_max), f.unit.format(0));
+    let plot_w = plot_width(width, &y_labels);
+    let layers = f
+        .layers
+        .iter()
+        .map(|layer| StackLayer {
+            data: zoomed(&layer.history, plot_w, per_col),
+            color: theme.role(layer.role),
+        })
+        .collect();
+    let references = f
+        .ceilings
+        .iter()
+        .map(|c| ReferenceLine {
+            value: c.value,
+            label: c.label.clone(),
+            color: theme.role(c.role),
+        })
+        .collect();
+    let len = f.layers.iter().map(|l| l.history.len()).max().unwrap_or(0);
+    element! {
+        StackedChart(chart: Some(RenderedStackedChart {
+            layers,
+            max: f.chart_max,
+            width,
+            height: POWER_CHART_HEIGHT,
+            x_ticks: time_ticks(len, plot_w, per_col, clock),
+            y_labels,
+            references,
+            axis: theme.border,
+            glyphs: theme.glyphs,
+        }))
+    }
+    .into_any()
+}
+
+/// Render the full Power tab at `width`, drawing `per_col` history samples per
+/// chart column.
+pub(crate) fn power(
+    f: &PowerFrame,
+    clock: Clock,
+    width: usize,
+    per_col: usize,
+    theme: Theme,
+) -> AnyElement<'static> {
+    let inner = width.saturating_sub(2);
+    let chart_panel = panel(
+        " Power ",
+        width,
+        theme,
+        chart(f, inner, per_col, clock, theme),
+    );
+
+    let header = element! {
+        Text(
+            content: format!("  {}", table_text("", ["Current", "Average", "Peak", "Energy"])),
+            weight: Weight::Bold,
+            wrap: TextWrap::NoWrap,
+        )
+    }
+    .into_any();
+    let rows: Vec<AnyElement<'static>> = std::iter::once(header)
+        .chain(f.rows.iter().map(|row| table_row(row, theme)))
+        .collect();
+    let table = element! {
+        View(flex_direction: FlexDirection::Column) { #(rows) }
+    }
+    .into_any();
+    let table_panel = panel(" Breakdown ", width, theme, table);
+
+    element! {
+        View(flex_direction: FlexDirection::Column) {
+            #(vec![chart_panel, table_panel])
+        }
+    }
+    .into_any()
+}
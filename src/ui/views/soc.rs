@@ -3,7 +3,9 @@
 //! A borderless 2-column table (label width 20, value width 16, the original default
 //! `column_spacing = 1`), right column bold. The rows are session-static —
 //! built once by [`render_soc_rows`](crate::backend::frame::render_soc_rows) and
-//! threaded through as a one-time prop, not per-frame.
+//! threaded through as a one-time prop, not per-frame — except the power
+//! ceilings, which calibration can raise, and the uptime underneath, which
+//! come with each frame.
 
 use iocraft::prelude::*;
 
@@ -15,15 +17,23 @@ use crate::{
 /// SoC table label column width (20 columns).
 const SOC_LABEL_WIDTH: usize = 20;
 
-/// Render the SoC tab at `width`.
-pub(crate) fn soc(rows: &SocRows, width: usize, _theme: Theme) -> AnyElement<'static> {
+/// Render the SoC tab at `width`: the session-static `rows`, then the frame's
+/// `ceilings` and the `uptime` last.
+pub(crate) fn soc(
+    rows: &SocRows,
+    (ceilings, uptime): (&[(String, String)], &str),
+    width: usize,
+    _theme: Theme,
+) -> AnyElement<'static> {
     #[expect(clippy::cast_possible_truncation)]
     let w = width as u32;
-    let body: Vec<AnyElement<'static>> = rows
+    let mut body: Vec<AnyElement<'static>> = rows
         .rows
         .iter()
+        .chain(ceilings)
         .map(|(l, r)| two_col_row(l, r, SOC_LABEL_WIDTH))
         .collect();
+    body.push(two_col_row("Uptime:", uptime, SOC_LABEL_WIDTH));
     element! {
         View(flex_direction: FlexDirection::Column, width: w) {
             #(body)
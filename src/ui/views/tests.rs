@@ -4,22 +4,51 @@
 //! (two P-clusters paired + a single S-cluster, no separate E-clusters). All
 //! snapshots capture **glyph/layout only, not color**; color
 //! parity is guarded by `snapshot::tests::gauge_and_sparkline_colors` and the
-//! live smoke check.
+//! live smoke check; the monochrome test inspects cell styles itself.
+
+use std::time::Duration;
+
+use iocraft::prelude::{Color, ElementExt, Weight};
 
 use crate::backend::frame::{
-    ColorRole, CpuCluster, CpuFrame, CpuRow, FreqTable, GpuFrame, MemLine, MemSpan, MemoryFrame,
-    Meter, OverviewFrame, SocRows, SparkText, Thermals,
+    ColorRole, CpuCluster, CpuFrame, CpuRow, Frame, FreqTable, GpuFrame, MemLine, MemSpan,
+    MemoryFrame, Meter, OverviewFrame, PowerCeiling, PowerFrame, PowerLayer, PowerRow,
+    RenderedHeader, SocRows, SparkText, SparkUnit, Thermals,
 };
+use crate::config::{ChartStyle, CpuView, ExportFormat, TimeRange};
+use crate::metric_key::PowerDomain;
 use crate::ui::components::tab_bar::tab_bar;
 use crate::ui::components::title_bar::title_bar;
+use crate::ui::export;
+use crate::ui::keymap::Keymap;
+use crate::ui::layout::{
+    BODY_TOP, CpuRowLayout, GpuLayout, INLINE_MAX_HEIGHT, OverviewLayout, SparkRegion,
+    memory_content_height, soc_content_height,
+};
+use crate::ui::screen::{Screen, screen};
 use crate::ui::snapshot::{assert_snapshot, render_to_text};
 use crate::ui::theme::Theme;
+use crate::ui::views::Clock;
 use crate::ui::views::cpu::cpu;
 use crate::ui::views::gpu::gpu;
+use crate::ui::views::help::help;
 use crate::ui::views::memory::memory;
 use crate::ui::views::overview::overview;
+use crate::ui::views::power::power;
 use crate::ui::views::soc::soc;
 use crate::ui::views::splash::splash;
+use crate::ui::views::too_small::too_small;
+
+/// No sample timestamps: charts draw no time ticks beyond `now`.
+const NO_CLOCK: Clock<'static> = Clock {
+    timeline: &[],
+    elapsed: Duration::ZERO,
+};
+
+/// One sample per second for `n` seconds, the newest at `n` s.
+fn timeline(n: u64) -> Vec<Duration> {
+    (1..=n).map(Duration::from_secs).collect()
+}
 
 /// Deterministic ascending sparkline data (length `n`, ceiling `max`).
 fn ramp(n: usize, max: u64) -> Vec<u64> {
@@ -33,40 +62,61 @@ fn meter(title: &str, ratio: f64) -> Meter {
     Meter {
         title: title.to_string(),
         ratio,
+        role: ColorRole::GaugeFg,
         spark: ramp(120, 100),
+        spark_span: Vec::new(),
+        spark_roles: Vec::new(),
         spark_max: 105,
+        unit: SparkUnit::Percent,
     }
 }
 
 /// Build the M5 Max Overview fixture matching the reference screenshot.
 fn fixture() -> OverviewFrame {
     OverviewFrame {
-        cpu_clusters_title: " CPU Clusters: 178.98 mW (peak: 2.94 W) ".to_string(),
+        cpu_clusters_title: " CPU Clusters: 178.98 mW (peak: 2.94 W, session: 6.12 W) ".to_string(),
         e_meters: vec![],
         p_meters: vec![
-            meter("P0-Cluster: 0.0 % @ 0 MHz (peak: 0.0 %)", 0.0),
-            meter("P1-Cluster: 6.6 % @ 1437 MHz (peak: 9.8 %)", 0.066),
+            meter(
+                "P0-Cluster: 0.0 % @ 0 MHz (peak: 0.0 %, session: 3.1 %)",
+                0.0,
+            ),
+            meter(
+                "P1-Cluster: 6.6 % @ 1437 MHz (peak: 9.8 %, session: 41.2 %)",
+                0.066,
+            ),
         ],
-        s_meters: vec![meter("S-Cluster: 0.6 % @ 1991 MHz (peak: 10.8 %)", 0.006)],
+        s_meters: vec![meter(
+            "S-Cluster: 0.6 % @ 1991 MHz (peak: 10.8 %, session: 10.8 %)",
+            0.006,
+        )],
         gpu: meter(
-            "GPU: 5.8 % @ 338 MHz | 72.95 mW (peak: 10.2 % | 121.64 mW)",
+            "GPU: 5.8 % @ 338 MHz | 72.95 mW (peak: 10.2 % | 121.64 mW, session: 12.5 % | 140.20 mW)",
             0.058,
         ),
-        ane: meter("ANE: 0.0 % | 0.00 W (peak: 0.0 % | 0.00 W)", 0.0),
+        ane: meter(
+            "ANE: 0.0 % | 0.00 W (peak: 0.0 % | 0.00 W, session: 0.0 % | 0.00 W)",
+            0.0,
+        ),
         package: SparkText {
-            title: "CPU+GPU+ANE: 251.93 mW (peak: 2.97 W)".to_string(),
-            spark: ramp(120, 300),
-            spark_max: 300,
+            title: "CPU+GPU+ANE: 251.93 mW (peak: 2.97 W, session: 6.30 W)".to_string(),
+            spark: ramp(120, 300_000),
+            spark_span: Vec::new(),
+            spark_max: 300_000,
+            unit: SparkUnit::Milliwatts,
         },
         thermals: Thermals {
             pressure: "Nominal".to_string(),
-            is_nominal: true,
+            role: ColorRole::Accent,
         },
         ram: meter(
-            "Memory Used: 41.4 % = 53.0 GiB / 128.0 GiB (peak: 41.5 % = 53.1 GiB)",
+            "Memory Used: 41.4 % = 53.0 GiB / 128.0 GiB (peak: 41.5 % = 53.1 GiB, session: 41.5 % = 53.1 GiB)",
             0.414,
         ),
-        swap: meter("SWAP: 0.0 % = 0.0 B / 0.0 B (peak: 0.0 B)", 0.0),
+        swap: meter(
+            "SWAP: 0.0 % = 0.0 B / 0.0 B (peak: 0.0 B, session: 0.0 B)",
+            0.0,
+        ),
     }
 }
 
@@ -76,19 +126,48 @@ fn title_bar_snapshot() {
     let el = title_bar(
         "Pumas v0.5.0".to_string(),
         " Apple M5 Max (cores: 12E+6P+40GPU) ".to_string(),
-        theme.accent,
+        None,
+        theme,
         120,
     );
     assert_snapshot("title_bar", &render_to_text(el, 120));
 }
 
+#[test]
+fn title_bar_paused_snapshot() {
+    let theme = Theme::default();
+    let el = title_bar(
+        "Pumas v0.5.0".to_string(),
+        " Apple M5 Max (cores: 12E+6P+40GPU) ".to_string(),
+        Some("PAUSED @ t-42s".to_string()),
+        theme,
+        120,
+    );
+    assert_snapshot("title_bar_paused", &render_to_text(el, 120));
+}
+
 #[test]
 fn tab_bar_overview_active_snapshot() {
     let theme = Theme::default();
-    let el = tab_bar(0, theme.accent, 120);
+    let el = tab_bar(0, None, theme, 120);
     assert_snapshot("tab_bar_overview", &render_to_text(el, 120));
 }
 
+#[test]
+fn tab_bar_with_info_snapshot() {
+    let theme = Theme::default();
+    let el = tab_bar(2, Some("500 ms · zoom 4x".to_string()), theme, 120);
+    assert_snapshot("tab_bar_info", &render_to_text(el, 120));
+}
+
+#[test]
+fn tab_bar_narrow_snapshot() {
+    // At the minimum width the settings no longer fit beside the labels.
+    let theme = Theme::default();
+    let el = tab_bar(5, Some("1000 ms · zoom 16x".to_string()), theme, 60);
+    assert_snapshot("tab_bar_narrow", &render_to_text(el, 60));
+}
+
 #[test]
 fn splash_snapshot() {
     let el = splash(120, 40);
@@ -99,7 +178,7 @@ fn splash_snapshot() {
 fn overview_snapshot() {
     let theme = Theme::default();
     let f = fixture();
-    let el = overview(&f, 120, theme);
+    let el = overview(&f, NO_CLOCK, 120, 60, 1, ChartStyle::Sparkline, theme);
     assert_snapshot("overview", &render_to_text(el, 120));
 }
 
@@ -109,12 +188,25 @@ fn overview_snapshot() {
 fn overview_wide_snapshot() {
     let theme = Theme::default();
     let f = fixture();
-    let el = overview(&f, 160, theme);
+    let el = overview(&f, NO_CLOCK, 160, 60, 1, ChartStyle::Sparkline, theme);
     assert_snapshot("overview_wide", &render_to_text(el, 160));
 }
 
 // ─── CPU fixture (mirrors screenshots/3.tab-cpu.png) ─────────────────────────
 
+/// Heatmap history of core `id` at sample `i` of 120: work migrates from the
+/// P0 cores to the S cores halfway through, with noise on P1.
+fn core_load(id: u16, i: usize) -> u8 {
+    let id = usize::from(id);
+    let load = match id {
+        0..6 if i < 60 => 90 - id * 10,
+        6..12 if !i.is_multiple_of(4) => (i * 3 + id * 11) % 100,
+        12.. if i >= 60 => 40 + (id - 12) * 12,
+        _ => 0,
+    };
+    u8::try_from(load).unwrap_or(u8::MAX)
+}
+
 /// Build one CPU core row. Sparklines are flat (`level` repeated 8×) for a
 /// deterministic golden; geometry/alignment is what the snapshot guards.
 fn cpu_core(id: u16, act: f64, freq_mhz: &str, freq_pct: f64) -> CpuRow {
@@ -125,25 +217,44 @@ fn cpu_core(id: u16, act: f64, freq_mhz: &str, freq_pct: f64) -> CpuRow {
     CpuRow {
         id_label: format!("{id:2} -"),
         act_ratio: act / 100.0,
+        act_role: ColorRole::GaugeFg,
         act_label: format!("{act:.1}%"),
         act_spark: vec![act_level; 8],
+        act_spark_roles: Vec::new(),
         act_spark_max: 105,
         freq_value: freq_mhz.to_string(),
         freq_ratio: freq_pct / 100.0,
         freq_label: format!("{freq_pct:3.0}%"),
         freq_spark: vec![freq_level; 8],
         freq_spark_max: 105,
+        heat_label: format!("{:<3}{id:>3}", ""),
+        act_history: (0..120).map(|i| core_load(id, i)).collect(),
+        act_history_roles: Vec::new(),
+        freq_history: (0..120).map(|i| core_load(id, i).min(70) + 30).collect(),
+    }
+}
+
+/// A cluster of `cpus`, the group label `short` on its first heatmap row.
+fn cpu_cluster(name: &str, short: &str, mut cpus: Vec<CpuRow>) -> CpuCluster {
+    if let Some(first) = cpus.first_mut() {
+        first.heat_label = format!("{short:<3}{}", &first.heat_label[3..]);
+    }
+    CpuCluster {
+        title: format!(" {name}: "),
+        cpus,
     }
 }
 
 fn cpu_fixture() -> CpuFrame {
-    let p0 = CpuCluster {
-        title: " P0-Cluster: ".to_string(),
-        cpus: (0..6).map(|id| cpu_core(id, 0.0, "0 MHz", 0.0)).collect(),
-    };
-    let p1 = CpuCluster {
-        title: " P1-Cluster: ".to_string(),
-        cpus: vec![
+    let p0 = cpu_cluster(
+        "P0-Cluster",
+        "P0",
+        (0..6).map(|id| cpu_core(id, 0.0, "0 MHz", 0.0)).collect(),
+    );
+    let p1 = cpu_cluster(
+        "P1-Cluster",
+        "P1",
+        vec![
             cpu_core(6, 15.4, "1447 MHz", 3.0),
             cpu_core(7, 7.8, "1404 MHz", 2.0),
             cpu_core(8, 7.8, "1426 MHz", 3.0),
@@ -151,10 +262,11 @@ fn cpu_fixture() -> CpuFrame {
             cpu_core(10, 1.9, "1514 MHz", 6.0),
             cpu_core(11, 0.0, "1500 MHz", 5.0),
         ],
-    };
-    let s = CpuCluster {
-        title: " S-Cluster: ".to_string(),
-        cpus: vec![
+    );
+    let s = cpu_cluster(
+        "S-Cluster",
+        "S",
+        vec![
             cpu_core(12, 1.9, "2138 MHz", 25.0),
             cpu_core(13, 1.9, "2271 MHz", 29.0),
             cpu_core(14, 0.0, "2165 MHz", 26.0),
@@ -162,7 +274,7 @@ fn cpu_fixture() -> CpuFrame {
             cpu_core(16, 0.0, "0 MHz", 0.0),
             cpu_core(17, 0.0, "0 MHz", 0.0),
         ],
-    };
+    );
     CpuFrame {
         clusters: vec![p0, p1, s],
         freq_table: FreqTable {
@@ -191,17 +303,43 @@ fn cpu_fixture() -> CpuFrame {
 fn cpu_snapshot() {
     let theme = Theme::default();
     let f = cpu_fixture();
-    let el = cpu(&f, 120, theme);
+    let el = cpu(&f, CpuView::Rows, NO_CLOCK, 120, 1, theme);
     assert_snapshot("cpu", &render_to_text(el, 120));
 }
 
+#[test]
+fn cpu_heatmap_snapshots() {
+    let theme = Theme::default();
+    let f = cpu_fixture();
+    let timeline = timeline(120);
+    let clock = Clock {
+        timeline: &timeline,
+        elapsed: Duration::from_secs(120),
+    };
+    let el = cpu(&f, CpuView::Activity, clock, 120, 1, theme);
+    assert_snapshot("cpu_heatmap_activity", &render_to_text(el, 120));
+    let el = cpu(&f, CpuView::Frequency, clock, 100, 2, theme);
+    assert_snapshot("cpu_heatmap_frequency", &render_to_text(el, 100));
+}
+
+#[test]
+fn cpu_rows_follow_the_zoom() {
+    let theme = Theme::default();
+    let f = cpu_fixture();
+    let near = render_to_text(cpu(&f, CpuView::Rows, NO_CLOCK, 120, 1, theme), 120);
+    let far = render_to_text(cpu(&f, CpuView::Rows, NO_CLOCK, 120, 4, theme), 120);
+    assert_ne!(near, far, "zooming out must redraw the row sparklines");
+}
+
 // ─── GPU fixture (mirrors screenshots/4.tab-gpu.png) ─────────────────────────
 
 fn gpu_fixture() -> GpuFrame {
     GpuFrame {
         act_ratio: 0.011,
+        act_role: ColorRole::GaugeFg,
         act_label: "1.1%".to_string(),
         act_spark: vec![1; 8],
+        act_spark_roles: Vec::new(),
         act_spark_max: 105,
         freq_value: "338 MHz".to_string(),
         freq_ratio: 0.0,
@@ -211,10 +349,13 @@ fn gpu_fixture() -> GpuFrame {
         power_value: "9.73 mW".to_string(),
         power_spark: vec![1; 8],
         power_spark_max: 105,
+        act_history: vec![1; 8],
+        act_history_roles: Vec::new(),
+        freq_history: vec![0; 8],
         peak_text: "Peak: 10.9 % | 121.64 mW".to_string(),
         thermals: Thermals {
             pressure: "Nominal".to_string(),
-            is_nominal: true,
+            role: ColorRole::Accent,
         },
         freq_table: FreqTable {
             rows: vec![
@@ -236,10 +377,69 @@ fn gpu_fixture() -> GpuFrame {
 fn gpu_snapshot() {
     let theme = Theme::default();
     let f = gpu_fixture();
-    let el = gpu(&f, 120, theme);
+    let el = gpu(&f, NO_CLOCK, 120, (1, ChartStyle::Sparkline), theme);
     assert_snapshot("gpu", &render_to_text(el, 120));
 }
 
+/// Every hover region covers exactly a drawn sparkline: full bars inside
+/// (the data is pinned near its ceiling), none just left or right of it.
+fn assert_regions_on_sparklines(text: &str, regions: &[SparkRegion]) {
+    let bar = |c: Option<&char>| c.is_some_and(|c| "▁▂▃▄▅▆▇█".contains(*c));
+    let lines: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+    for r in regions {
+        for row in &lines[r.y..r.y + r.height] {
+            let inside = (r.x..r.x + r.width).all(|x| bar(row.get(x)));
+            assert!(inside, "{r:?} is not over its sparkline");
+            assert!(!bar(row.get(r.x - 1)), "{r:?} starts late");
+            assert!(!bar(row.get(r.x + r.width)), "{r:?} ends early");
+        }
+    }
+}
+
+#[test]
+fn hover_regions_match_the_drawn_sparklines() {
+    let theme = Theme::default();
+    let mut f = fixture();
+    for m in f
+        .e_meters
+        .iter_mut()
+        .chain(&mut f.p_meters)
+        .chain(&mut f.s_meters)
+    {
+        m.spark = vec![100; 240];
+    }
+    for m in [&mut f.gpu, &mut f.ane, &mut f.ram, &mut f.swap] {
+        m.spark = vec![100; 240];
+    }
+    f.package.spark = vec![300_000; 240];
+    let text = render_to_text(
+        overview(&f, NO_CLOCK, 120, 40, 1, ChartStyle::Sparkline, theme),
+        120,
+    );
+    let regions = OverviewLayout::for_frame(120, &f)
+        .fit_height(40)
+        .spark_regions(&f);
+    assert_regions_on_sparklines(&text, &regions);
+
+    let mut f = cpu_fixture();
+    for row in f.clusters.iter_mut().flat_map(|c| &mut c.cpus) {
+        row.act_spark = vec![100; 16];
+        row.freq_spark = vec![100; 16];
+    }
+    let text = render_to_text(cpu(&f, CpuView::Rows, NO_CLOCK, 120, 1, theme), 120);
+    assert_regions_on_sparklines(&text, &CpuRowLayout::new(120).spark_regions(&f));
+
+    let mut f = gpu_fixture();
+    f.act_spark = vec![100; 16];
+    f.freq_spark = vec![100; 16];
+    f.power_spark = vec![100; 16];
+    let text = render_to_text(
+        gpu(&f, NO_CLOCK, 120, (1, ChartStyle::Sparkline), theme),
+        120,
+    );
+    assert_regions_on_sparklines(&text, &GpuLayout::new(120).spark_regions());
+}
+
 // ─── Memory fixture (mirrors screenshots/5.tab-memory.png) ───────────────────
 
 fn ml(spans: Vec<(&str, ColorRole)>) -> MemLine {
@@ -318,7 +518,9 @@ fn memory_snapshot() {
     let theme = Theme::default();
     let f = memory_fixture();
     let el = memory(&f, 120, theme);
-    assert_snapshot("memory", &render_to_text(el, 120));
+    let text = render_to_text(el, 120);
+    assert_eq!(text.lines().count(), memory_content_height(&f));
+    assert_snapshot("memory", &text);
 }
 
 // ─── SoC fixture (mirrors screenshots/6.tab-soc.png) ─────────────────────────
@@ -327,21 +529,418 @@ fn soc_fixture() -> SocRows {
     SocRows {
         rows: vec![
             ("SoC brand name:".to_string(), "Apple M5 Max".to_string()),
+            ("Model:".to_string(), "Mac17,7".to_string()),
+            ("macOS:".to_string(), "26.4 (25E246)".to_string()),
+            ("Unified memory:".to_string(), "64 GiB".to_string()),
+            ("Dies:".to_string(), "1".to_string()),
             ("CPU cores:".to_string(), "18".to_string()),
             ("- Efficiency cores:".to_string(), "12".to_string()),
             ("- Performance cores:".to_string(), "6".to_string()),
             ("GPU cores:".to_string(), "40".to_string()),
-            ("Max CPU power:".to_string(), "78 W".to_string()),
-            ("Max GPU power:".to_string(), "75 W".to_string()),
-            ("Max ANE power:".to_string(), "12 W".to_string()),
+            (
+                "Performance caches:".to_string(),
+                "L1i 192 KiB / L1d 128 KiB per core, L2 16 MiB".to_string(),
+            ),
         ],
     }
 }
 
+fn soc_ceilings_fixture() -> Vec<(String, String)> {
+    vec![
+        ("Max CPU power:".to_string(), "78 W".to_string()),
+        ("Max GPU power:".to_string(), "75 W".to_string()),
+        ("Max ANE power:".to_string(), "12 W".to_string()),
+    ]
+}
+
 #[test]
 fn soc_snapshot() {
     let theme = Theme::default();
-    let rows = soc_fixture();
-    let el = soc(&rows, 120, theme);
-    assert_snapshot("soc", &render_to_text(el, 120));
+    let (rows, ceilings) = (soc_fixture(), soc_ceilings_fixture());
+    let el = soc(&rows, (&ceilings, "3d 4h 12m"), 120, theme);
+    let text = render_to_text(el, 120);
+    assert_eq!(text.lines().count(), soc_content_height(&rows, &ceilings));
+    assert_snapshot("soc", &text);
+}
+
+#[test]
+fn help_snapshot() {
+    let theme = Theme::default();
+    let el = help(&Keymap::default().help_rows(), 80, theme);
+    assert_snapshot("help", &render_to_text(el, 80));
+}
+
+#[test]
+fn overview_short_snapshot() {
+    // 28 rows: the sparklines shrink to 2 rows so the tab still fits.
+    let theme = Theme::default();
+    let el = overview(
+        &fixture(),
+        NO_CLOCK,
+        120,
+        28,
+        1,
+        ChartStyle::Sparkline,
+        theme,
+    );
+    assert_snapshot("overview_short", &render_to_text(el, 120));
+}
+
+#[test]
+fn overview_compact_snapshot() {
+    // 18 rows: compact cells, no sparklines.
+    let theme = Theme::default();
+    let el = overview(
+        &fixture(),
+        NO_CLOCK,
+        120,
+        18,
+        1,
+        ChartStyle::Sparkline,
+        theme,
+    );
+    assert_snapshot("overview_compact", &render_to_text(el, 120));
+}
+
+#[test]
+fn overview_braille_snapshot() {
+    // Line-chart style: each history gains a Y axis and a time-tick row.
+    let theme = Theme::default();
+    let timeline = timeline(120);
+    let clock = Clock {
+        timeline: &timeline,
+        elapsed: Duration::from_secs(120),
+    };
+    let el = overview(&fixture(), clock, 120, 60, 1, ChartStyle::Braille, theme);
+    assert_snapshot("overview_braille", &render_to_text(el, 120));
+}
+
+#[test]
+fn gpu_braille_snapshot() {
+    let theme = Theme::default();
+    let mut f = gpu_fixture();
+    f.act_history = ramp(120, 60);
+    f.freq_history = ramp(120, 100).into_iter().rev().collect();
+    let timeline = timeline(120);
+    let clock = Clock {
+        timeline: &timeline,
+        elapsed: Duration::from_secs(120),
+    };
+    let el = gpu(&f, clock, 120, (1, ChartStyle::Braille), theme);
+    assert_snapshot("gpu_braille", &render_to_text(el, 120));
+}
+
+#[test]
+fn too_small_snapshot() {
+    assert_snapshot("too_small", &render_to_text(too_small(50, 9), 50));
+}
+
+// ─── Power fixture ───────────────────────────────────────────────────────────
+
+fn power_row(label: &str, role: ColorRole, values: [&str; 4]) -> PowerRow {
+    PowerRow {
+        label: label.to_string(),
+        role,
+        current: values[0].to_string(),
+        average: values[1].to_string(),
+        peak: values[2].to_string(),
+        energy: values[3].to_string(),
+    }
+}
+
+fn power_fixture() -> PowerFrame {
+    // A CPU burst over a steady GPU load, in mW.
+    let cpu = (0..120u64)
+        .map(|i| {
+            if (40..80).contains(&i) {
+                9_000 + i * 50
+            } else {
+                2_000
+            }
+        })
+        .collect();
+    let layers = [
+        (PowerDomain::Cpu, cpu),
+        (PowerDomain::Gpu, ramp(120, 6_000)),
+        (PowerDomain::Ane, vec![0; 120]),
+        (PowerDomain::Other, vec![400; 120]),
+    ];
+    let ceiling = |label: &str, value, role| PowerCeiling {
+        label: label.to_string(),
+        value,
+        role,
+    };
+    PowerFrame {
+        layers: layers
+            .into_iter()
+            .map(|(domain, history)| PowerLayer {
+                role: ColorRole::Power(domain),
+                history,
+            })
+            .collect(),
+        chart_max: 20_000,
+        unit: SparkUnit::Milliwatts,
+        ceilings: vec![
+            ceiling("ANE max 8 W", 8_000, ColorRole::Power(PowerDomain::Ane)),
+            ceiling("CPU max 20 W", 20_000, ColorRole::Power(PowerDomain::Cpu)),
+            ceiling("GPU max 20 W", 20_000, ColorRole::Power(PowerDomain::Gpu)),
+            ceiling("Package max 48 W", 48_000, ColorRole::Default),
+        ],
+        rows: vec![
+            power_row(
+                "CPU",
+                ColorRole::Power(PowerDomain::Cpu),
+                ["2.00 W", "6.12 W", "17.95 W", "734.4 J"],
+            ),
+            power_row(
+                "GPU",
+                ColorRole::Power(PowerDomain::Gpu),
+                ["6.00 W", "3.00 W", "6.00 W", "360.0 J"],
+            ),
+            power_row(
+                "ANE",
+                ColorRole::Power(PowerDomain::Ane),
+                ["0.00 W", "0.00 W", "0.00 W", "0.0 J"],
+            ),
+            power_row(
+                "Other",
+                ColorRole::Power(PowerDomain::Other),
+                ["400.00 mW", "400.00 mW", "400.00 mW", "48.0 J"],
+            ),
+            power_row(
+                "Package",
+                ColorRole::Default,
+                ["8.40 W", "9.52 W", "24.35 W", "1.1 kJ"],
+            ),
+        ],
+    }
+}
+
+#[test]
+fn power_snapshot() {
+    let theme = Theme::default();
+    let timeline = timeline(120);
+    let clock = Clock {
+        timeline: &timeline,
+        elapsed: Duration::from_secs(120),
+    };
+    let el = power(&power_fixture(), clock, 120, 1, theme);
+    assert_snapshot("power", &render_to_text(el, 120));
+}
+
+// ─── ASCII and monochrome modes ──────────────────────────────────────────────
+
+#[test]
+fn ascii_snapshots() {
+    let theme = Theme::default().ascii();
+    let timeline = timeline(120);
+    let clock = Clock {
+        timeline: &timeline,
+        elapsed: Duration::from_secs(120),
+    };
+    let snapshots = [
+        ("ascii_tab_bar", tab_bar(1, None, theme, 120)),
+        (
+            "ascii_overview",
+            overview(
+                &fixture(),
+                NO_CLOCK,
+                120,
+                60,
+                1,
+                ChartStyle::Sparkline,
+                theme,
+            ),
+        ),
+        (
+            "ascii_overview_braille",
+            overview(&fixture(), clock, 120, 60, 1, ChartStyle::Braille, theme),
+        ),
+        (
+            "ascii_cpu",
+            cpu(&cpu_fixture(), CpuView::Rows, NO_CLOCK, 120, 1, theme),
+        ),
+        (
+            "ascii_cpu_heatmap",
+            cpu(&cpu_fixture(), CpuView::Activity, clock, 120, 1, theme),
+        ),
+        ("ascii_power", power(&power_fixture(), clock, 120, 1, theme)),
+    ];
+    for (name, el) in snapshots {
+        let text = render_to_text(el, 120);
+        assert!(text.is_ascii(), "{name} is not plain ASCII");
+        assert_snapshot(name, &text);
+    }
+}
+
+#[test]
+fn monochrome_snapshot_and_styles() {
+    let theme = Theme::default().monochrome();
+    let mut f = cpu_fixture();
+    let core = &mut f.clusters[1].cpus[0];
+    core.act_role = ColorRole::Critical;
+    let label = core.act_label.clone();
+    assert_snapshot(
+        "monochrome_cpu",
+        &render_to_text(cpu(&f, CpuView::Rows, NO_CLOCK, 120, 1, theme), 120),
+    );
+
+    let mut el = cpu(&f, CpuView::Rows, NO_CLOCK, 120, 1, theme);
+    let canvas = el.render(Some(120));
+    // The cluster panels; the frequency table below is bold by design.
+    let panel_rows: usize = f.clusters.iter().map(|c| c.cpus.len() + 2).sum();
+    let mut bold = String::new();
+    for y in 0..canvas.height() {
+        for x in 0..canvas.width() {
+            let cell = canvas.cell(x, y).expect("cell");
+            assert!(
+                matches!(cell.background_color, None | Some(Color::Reset)),
+                "background at ({x}, {y})",
+            );
+            let Some(style) = cell.text_style() else {
+                continue;
+            };
+            assert!(
+                matches!(style.color, None | Some(Color::Reset)),
+                "color at ({x}, {y})",
+            );
+            if y < panel_rows && style.weight == Weight::Bold {
+                bold.push_str(cell.text().unwrap_or_default());
+            }
+        }
+    }
+    // State shows as weight instead: only the critical core's label is bold.
+    assert_eq!(bold, label);
+}
+
+// ─── Whole screen (live UI, export key and `pumas snapshot`) ─────────────────
+
+#[test]
+fn screen_snapshot() {
+    let frame = Frame {
+        elapsed: Duration::from_secs(120),
+        range: TimeRange::Recent,
+        timeline: timeline(120),
+        overview: fixture(),
+        cpu: cpu_fixture(),
+        gpu: gpu_fixture(),
+        memory: memory_fixture(),
+        power: power_fixture(),
+        uptime: "3d 4h 12m".to_string(),
+        soc_ceilings: soc_ceilings_fixture(),
+    };
+    let header = RenderedHeader {
+        program_name: "Pumas v0.5.0".to_string(),
+        machine_desc: " Apple M5 Max (cores: 12E+6P+40GPU) ".to_string(),
+    };
+    // The CPU tab overflows 30 rows: scrolled, with the indicator.
+    let s = Screen {
+        frame: &frame,
+        header: &header,
+        soc_rows: &soc_fixture(),
+        status: Some("PAUSED @ t-3s".to_string()),
+        tab: 1,
+        size: (120, 30),
+        per_col: 1,
+        chart: ChartStyle::Sparkline,
+        cpu_view: CpuView::Rows,
+        sample_rate_ms: 1000,
+        scroll: 4,
+        pointer: None,
+        help: None,
+        inline: false,
+        theme: Theme::default(),
+    };
+    let text = render_to_text(screen(&s), 120);
+    assert_eq!(text.lines().count(), 30);
+    assert_snapshot("screen_cpu_scrolled", &text);
+    // Exports draw the very same canvas.
+    let canvas = screen(&s).render(Some(120));
+    assert_eq!(
+        export::encode(&canvas, ExportFormat::Text),
+        text.into_bytes()
+    );
+    // A rolled-up range is named in the tab bar.
+    let day = Frame {
+        range: TimeRange::Day,
+        ..frame.clone()
+    };
+    let s = Screen { frame: &day, ..s };
+    let text = render_to_text(screen(&s), 120);
+    assert!(
+        text.lines()
+            .nth(2)
+            .unwrap()
+            .contains("1000 ms · zoom 1x · 24h │")
+    );
+}
+
+#[test]
+fn inline_screen_has_no_tab_bar_and_ignores_the_pointer() {
+    let frame = Frame {
+        elapsed: Duration::from_secs(120),
+        range: TimeRange::Recent,
+        timeline: timeline(120),
+        overview: fixture(),
+        cpu: cpu_fixture(),
+        gpu: gpu_fixture(),
+        memory: memory_fixture(),
+        power: power_fixture(),
+        uptime: "3d 4h 12m".to_string(),
+        soc_ceilings: soc_ceilings_fixture(),
+    };
+    let (header, soc_rows) = (RenderedHeader::default(), soc_fixture());
+    let render = |inline: bool, height: usize, pointer: Option<(usize, usize)>| {
+        let s = Screen {
+            frame: &frame,
+            header: &header,
+            soc_rows: &soc_rows,
+            status: None,
+            tab: 0,
+            size: (120, height),
+            per_col: 1,
+            chart: ChartStyle::Sparkline,
+            cpu_view: CpuView::Rows,
+            sample_rate_ms: 1000,
+            scroll: 0,
+            pointer,
+            help: None,
+            inline,
+            theme: Theme::default(),
+        };
+        render_to_text(screen(&s), 120)
+    };
+    let regions = OverviewLayout::for_frame(120, &frame.overview)
+        .fit_height(40 - BODY_TOP)
+        .spark_regions(&frame.overview);
+    let r = regions.last().unwrap();
+    let x = r.x + r.width - 1;
+
+    // Fullscreen, pointing at a sparkline opens its tooltip.
+    assert_ne!(
+        render(false, 40, Some((x, BODY_TOP + r.y))),
+        render(false, 40, None)
+    );
+
+    // Inline, the body starts right under the title bar, and the mouse is
+    // not captured: pointing anywhere shows nothing.
+    let plain = render(true, INLINE_MAX_HEIGHT, None);
+    let body = render_to_text(
+        overview(
+            &frame.overview,
+            NO_CLOCK,
+            120,
+            INLINE_MAX_HEIGHT - 1,
+            1,
+            ChartStyle::Sparkline,
+            Theme::default(),
+        ),
+        120,
+    );
+    assert_eq!(plain.lines().nth(1), body.lines().next());
+    for y in 0..INLINE_MAX_HEIGHT {
+        for x in (0..120).step_by(8) {
+            assert_eq!(render(true, INLINE_MAX_HEIGHT, Some((x, y))), plain);
+        }
+    }
 }
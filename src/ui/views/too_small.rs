@@ -0,0 +1,28 @@
+//! "Terminal too small" screen.
+//!
+//! Replaces the whole UI (title and tab bars included) while the terminal is
+//! smaller than [`MIN_WIDTH`] × [`MIN_HEIGHT`], centered like the splash.
+
+use iocraft::prelude::*;
+
+use crate::ui::layout::{MIN_HEIGHT, MIN_WIDTH};
+
+/// Render the message, centered within `width` × `height`.
+pub(crate) fn too_small(width: usize, height: usize) -> AnyElement<'static> {
+    #[expect(clippy::cast_possible_truncation)]
+    let (w, h) = (width as u32, height as u32);
+    let size = format!("{width} × {height}, need at least {MIN_WIDTH} × {MIN_HEIGHT}");
+    element! {
+        View(
+            width: w,
+            height: h,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+        ) {
+            Text(content: "Terminal too small", weight: Weight::Bold, wrap: TextWrap::NoWrap)
+            Text(content: size, wrap: TextWrap::NoWrap)
+        }
+    }
+    .into_any()
+}
@@ -18,6 +18,14 @@ scale_fn!(watts2,
     unit: "W",
     doc: "Return a string with the value and its si-scaled unit of watts.");
 
+// defines the `joules1()` function: 1.2 kJ
+scale_fn!(joules1,
+    base: B1000,
+    constraint: UnitAndAbove,
+    mantissa_fmt: "{:.1}",
+    unit: "J",
+    doc: "Return a string with the value and its si-scaled unit of joules.");
+
 // defines the `percent1()` function: 23.6 %
 scale_fn!(percent1,
     base: B1000,
@@ -41,3 +49,23 @@ scale_fn!(bibytes1,
     mantissa_fmt: "{:.1}",
     unit: "B",
     doc: "Return a string with the value and its si-scaled unit of bibytes.");
+
+// defines the `bibytes0()` function: 128 KiB
+scale_fn!(bibytes0,
+    base: B1024,
+    constraint: UnitAndAbove,
+    mantissa_fmt: "{:.0}",
+    unit: "B",
+    doc: "Return a string with the value and its si-scaled unit of bibytes, without decimals.");
+
+/// A duration in days, hours and minutes, e.g. `3d 4h 12m`, leaving out
+/// leading zero units.
+pub(crate) fn uptime(d: std::time::Duration) -> String {
+    let minutes = d.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h {minutes}m"),
+    }
+}